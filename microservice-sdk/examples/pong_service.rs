@@ -0,0 +1,76 @@
+//! PongService - the ping/pong example written against `on_data_received`
+//!
+//! Unlike `simple_microservice`, this service never touches the LiveKit connection:
+//! the runner joins the room, dispatches data messages to the handler and notifies
+//! the session manager once connected.
+
+use async_trait::async_trait;
+use microservice_sdk::{
+    protocol::Response, DataContext, Envelope, MicroserviceConfig, MicroserviceHandler,
+    MicroserviceRunner, Result as SdkResult,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::info;
+
+/// PongService that responds to ping messages
+struct PongService {
+    service_name: String,
+}
+
+#[async_trait]
+impl MicroserviceHandler for PongService {
+    async fn on_data_received(&self, ctx: DataContext) -> SdkResult<()> {
+        info!(
+            "PongService {} received message from {:?}: {}",
+            self.service_name,
+            ctx.participant_identity,
+            ctx.text()
+        );
+
+        // Answer `ping` requests; anything else is not for us
+        if let Ok(Envelope::Request(request)) = ctx.envelope() {
+            if request.method == "ping" {
+                let pong = Response::ok(request.id, serde_json::json!("pong"));
+                ctx.reply_envelope(&pong.into()).await?;
+                info!("PongService {} sent pong response!", self.service_name);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> SdkResult<()> {
+    // Initialize tracing
+    tracing_subscriber::fmt::init();
+
+    // Configuration
+    let service_id = "pong-service-1".to_string();
+    let service_endpoint = "http://localhost:3001".to_string();
+    let session_manager_url = "http://localhost:8080".to_string();
+
+    let mut metadata = HashMap::new();
+    metadata.insert("type".to_string(), "pong-service".to_string());
+    metadata.insert(
+        "capabilities".to_string(),
+        "ping-pong-responder".to_string(),
+    );
+
+    let config = MicroserviceConfig::new(session_manager_url, service_id.clone(), service_endpoint)
+        .with_metadata(metadata)
+        .with_timeout(30);
+
+    let handler = Arc::new(PongService {
+        service_name: service_id,
+    });
+
+    // The runner joins rooms itself and hands data messages to the handler
+    let runner = MicroserviceRunner::new(config, handler)?.with_room_sessions();
+
+    info!("Starting PongService microservice...");
+    runner.start().await?;
+
+    Ok(())
+}
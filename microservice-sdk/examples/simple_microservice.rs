@@ -5,12 +5,13 @@
 //! 2. Actually connects to LiveKit rooms when requested
 //! 3. Listens for data messages containing "ping"
 //! 4. Responds with "pong" messages
+//! 5. Notifies the session manager once it is ready
 
 use async_trait::async_trait;
 use livekit::prelude::*;
 use microservice_sdk::{
     JoinRoomRequest, MicroserviceConfig, MicroserviceHandler, MicroserviceRunner,
-    Result as SdkResult,
+    Result as SdkResult, RoomConnectionState, RoomConnections, RoomSession, SessionManagerClient,
 };
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -19,11 +20,17 @@ use tracing::{error, info, warn};
 /// PongService that connects to LiveKit and responds to ping messages
 struct PongService {
     service_name: String,
+    client: SessionManagerClient,
+    rooms: RoomConnections,
 }
 
 impl PongService {
-    fn new(service_name: String) -> Self {
-        Self { service_name }
+    fn new(service_name: String, client: SessionManagerClient, rooms: RoomConnections) -> Self {
+        Self {
+            service_name,
+            client,
+            rooms,
+        }
     }
 }
 
@@ -35,14 +42,9 @@ impl MicroserviceHandler for PongService {
             self.service_name, request.room_name, request.session_id
         );
 
-        // Use the LiveKit URL provided in the request
-        let livekit_url = &request.livekit_url;
-
         // Connect to LiveKit room
-        let room_options = RoomOptions::default();
-
-        match Room::connect(livekit_url, &request.access_token, room_options).await {
-            Ok((room, mut event_rx)) => {
+        match RoomSession::connect_with_events(&request).await {
+            Ok((room_session, mut event_rx)) => {
                 info!(
                     "PongService {} successfully connected to room {}",
                     self.service_name, request.room_name
@@ -51,6 +53,9 @@ impl MicroserviceHandler for PongService {
                 // Spawn a task to handle room events
                 let room_name = request.room_name.clone();
                 let service_name = self.service_name.clone();
+                let session_id = request.session_id.clone();
+                let rooms = self.rooms.clone();
+                rooms.set(&session_id, RoomConnectionState::Connected);
 
                 tokio::spawn(async move {
                     info!(
@@ -59,6 +64,9 @@ impl MicroserviceHandler for PongService {
                     );
 
                     while let Some(event) = event_rx.recv().await {
+                        // Keep the runner's /health in step with the room connection
+                        rooms.handle_event(&session_id, &event);
+
                         match event {
                             RoomEvent::DataReceived {
                                 payload,
@@ -81,15 +89,7 @@ impl MicroserviceHandler for PongService {
                                     );
 
                                     // Send pong response
-                                    let pong_data = DataPacket {
-                                        payload: "pong".to_string().into_bytes(),
-                                        reliable: true,
-                                        ..Default::default()
-                                    };
-
-                                    if let Err(e) =
-                                        room.local_participant().publish_data(pong_data).await
-                                    {
+                                    if let Err(e) = room_session.publish_text(None, "pong").await {
                                         error!(
                                             "PongService {} failed to send pong: {}",
                                             service_name, e
@@ -133,6 +133,11 @@ impl MicroserviceHandler for PongService {
                     );
                 });
 
+                // Connected and listening - tell the session manager we're ready
+                self.client
+                    .notify_ready(&request.service_identity, &request.session_id)
+                    .await?;
+
                 Ok(())
             }
             Err(e) => {
@@ -140,9 +145,7 @@ impl MicroserviceHandler for PongService {
                     "PongService {} failed to connect to room {}: {}",
                     self.service_name, request.room_name, e
                 );
-                Err(microservice_sdk::MicroserviceError::JoinRoomFailed(
-                    format!("LiveKit connection failed: {}", e),
-                ))
+                Err(e)
             }
         }
     }
@@ -175,10 +178,12 @@ async fn main() -> SdkResult<()> {
         .with_timeout(30);
 
     // Create the microservice handler
-    let handler = Arc::new(PongService::new(service_id));
+    let client = SessionManagerClient::new(config.clone())?;
+    let rooms = RoomConnections::new();
+    let handler = Arc::new(PongService::new(service_id, client, rooms.clone()));
 
     // Create and start the microservice runner
-    let runner = MicroserviceRunner::new(config, handler)?;
+    let runner = MicroserviceRunner::new(config, handler)?.with_room_connections(rooms);
 
     info!("Starting PongService microservice...");
     runner.start().await?;
@@ -0,0 +1,178 @@
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::errors::{MicroserviceError, Result};
+
+/// State of a [`CircuitBreaker`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests go through
+    Closed,
+    /// Requests fail fast with [`MicroserviceError::CircuitOpen`] until the cooldown ends
+    Open,
+    /// The cooldown ended; one probe request is let through to test recovery
+    HalfOpen,
+}
+
+/// When a [`CircuitBreaker`] opens and how long it stays open
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failures that open the circuit; 0 disables the breaker
+    pub failure_threshold: u32,
+    /// How long the circuit stays open before probing again
+    pub cooldown: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+impl CircuitBreakerConfig {
+    /// A breaker that never opens
+    pub fn disabled() -> Self {
+        Self {
+            failure_threshold: 0,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug)]
+struct BreakerState {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    /// A half-open probe is in flight; other requests keep failing fast
+    probing: bool,
+}
+
+/// Stops calling the session manager after repeated failures
+///
+/// Opens after `failure_threshold` consecutive failures and short-circuits requests for
+/// `cooldown`, then lets a single probe through: its success closes the circuit, its
+/// failure opens it for another cooldown. Only transport errors and 5xx responses count
+/// as failures; a 4xx means the session manager is up and answering.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    state: Mutex<BreakerState>,
+}
+
+impl CircuitBreaker {
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(BreakerState {
+                consecutive_failures: 0,
+                opened_at: None,
+                probing: false,
+            }),
+        }
+    }
+
+    pub fn config(&self) -> CircuitBreakerConfig {
+        self.config
+    }
+
+    pub fn state(&self) -> CircuitState {
+        let state = self.state.lock().unwrap();
+        match state.opened_at {
+            None => CircuitState::Closed,
+            Some(opened_at) if opened_at.elapsed() < self.config.cooldown => CircuitState::Open,
+            Some(_) => CircuitState::HalfOpen,
+        }
+    }
+
+    /// Consecutive failures since the last success
+    pub fn consecutive_failures(&self) -> u32 {
+        self.state.lock().unwrap().consecutive_failures
+    }
+
+    /// Run `request` unless the circuit is open, recording its outcome
+    pub async fn call<T, F, Fut>(&self, request: F) -> Result<T>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        self.acquire()?;
+        let result = request().await;
+        match &result {
+            Err(e) if is_failure(e) => self.record_failure(),
+            _ => self.record_success(),
+        }
+        result
+    }
+
+    /// Check whether a request may go out, claiming the probe when half-open
+    fn acquire(&self) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        let Some(opened_at) = state.opened_at else {
+            return Ok(());
+        };
+
+        let elapsed = opened_at.elapsed();
+        if elapsed < self.config.cooldown {
+            return Err(MicroserviceError::CircuitOpen {
+                retry_after: self.config.cooldown - elapsed,
+            });
+        }
+        if state.probing {
+            return Err(MicroserviceError::CircuitOpen {
+                retry_after: Duration::ZERO,
+            });
+        }
+        state.probing = true;
+        Ok(())
+    }
+
+    fn record_success(&self) {
+        let mut state = self.state.lock().unwrap();
+        if state.opened_at.is_some() {
+            tracing::info!("Session manager recovered, closing circuit");
+        }
+        state.consecutive_failures = 0;
+        state.opened_at = None;
+        state.probing = false;
+    }
+
+    fn record_failure(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+        let threshold = self.config.failure_threshold;
+        if threshold == 0 {
+            return;
+        }
+
+        if state.probing || state.consecutive_failures >= threshold {
+            if state.opened_at.is_none() || state.probing {
+                tracing::warn!(
+                    "Session manager failed {} times in a row, opening circuit for {:?}",
+                    state.consecutive_failures,
+                    self.config.cooldown
+                );
+            }
+            state.opened_at = Some(Instant::now());
+            state.probing = false;
+        }
+    }
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::new(CircuitBreakerConfig::default())
+    }
+}
+
+/// Whether `error` suggests the session manager is unavailable
+fn is_failure(error: &MicroserviceError) -> bool {
+    match error {
+        MicroserviceError::HttpError(_) => true,
+        MicroserviceError::SessionManagerError { status, .. } => *status >= 500,
+        _ => false,
+    }
+}
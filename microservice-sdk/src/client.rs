@@ -1,19 +1,29 @@
 use reqwest::Client;
+use socket2::{Domain, Socket, Type};
+use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Duration;
 use tracing::{error, info};
 
 use crate::{
+    circuit_breaker::{CircuitBreaker, CircuitState},
     errors::{MicroserviceError, Result},
     models::*,
+    payload::Negotiated,
+    room_session::{ReconnectPolicy, RoomConnections, RoomSession},
     traits::MicroserviceHandler,
 };
 
 /// Client for communicating with the Session Manager
+///
+/// Requests go through a [`CircuitBreaker`] shared by all clones of the client: once the
+/// session manager keeps failing, calls fail fast with [`MicroserviceError::CircuitOpen`]
+/// instead of waiting on the network.
 #[derive(Debug, Clone)]
 pub struct SessionManagerClient {
     config: MicroserviceConfig,
     http_client: Client,
+    circuit_breaker: Arc<CircuitBreaker>,
 }
 
 impl SessionManagerClient {
@@ -24,9 +34,12 @@ impl SessionManagerClient {
             .build()
             .map_err(MicroserviceError::HttpError)?;
 
+        let circuit_breaker = Arc::new(CircuitBreaker::new(config.circuit_breaker));
+
         Ok(Self {
             config,
             http_client,
+            circuit_breaker,
         })
     }
 
@@ -52,47 +65,115 @@ impl SessionManagerClient {
             self.config.service_id
         );
 
-        let response = self.http_client.post(&url).json(&request).send().await?;
+        self.circuit_breaker
+            .call(|| async {
+                let response = self.http_client.post(&url).json(&request).send().await?;
 
-        if response.status().is_success() {
-            let register_response: RegisterMicroserviceResponse = response.json().await?;
-            info!(
-                "Successfully registered microservice: {}",
-                register_response.message
-            );
-            Ok(register_response)
-        } else {
-            let status = response.status().as_u16();
-            let error_text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
-
-            // Try to parse as ErrorResponse
-            if let Ok(error_response) = serde_json::from_str::<ErrorResponse>(&error_text) {
-                Err(MicroserviceError::SessionManagerError {
-                    status,
-                    message: error_response.message,
-                })
-            } else {
-                Err(MicroserviceError::SessionManagerError {
-                    status,
-                    message: error_text,
-                })
-            }
-        }
+                if response.status().is_success() {
+                    let register_response: RegisterMicroserviceResponse = response.json().await?;
+                    info!(
+                        "Successfully registered microservice: {}",
+                        register_response.message
+                    );
+                    Ok(register_response)
+                } else {
+                    Err(error_from_response(response).await)
+                }
+            })
+            .await
+    }
+
+    /// Tell the session manager this service is ready to serve a session
+    ///
+    /// Joining the room is not the same as being ready (e.g. a model may still be loading),
+    /// so call this once the service can actually handle traffic, typically at the end of
+    /// [`MicroserviceHandler::handle_join_room`]. The session becomes `Ready` once the ready
+    /// quorum (`min_ready_services`, by default every service) is reached.
+    pub async fn notify_ready(
+        &self,
+        service_id: &str,
+        session_id: &str,
+    ) -> Result<ServiceReadyResponse> {
+        let url = format!(
+            "{}/api/v1/sessions/{}/service-ready",
+            self.config.session_manager_url, session_id
+        );
+
+        let request = ServiceReadyRequest {
+            service_id: service_id.to_string(),
+        };
+
+        info!(
+            "Notifying session manager that {} is ready for session {}",
+            service_id, session_id
+        );
+
+        self.circuit_breaker
+            .call(|| async {
+                let response = self.http_client.post(&url).json(&request).send().await?;
+
+                if response.status().is_success() {
+                    let ready_response: ServiceReadyResponse = response.json().await?;
+                    if !ready_response.success {
+                        return Err(MicroserviceError::NotifyReadyFailed(ready_response.message));
+                    }
+                    info!(
+                        "Session {} acknowledged ready signal (all services ready: {})",
+                        session_id, ready_response.all_services_ready
+                    );
+                    Ok(ready_response)
+                } else {
+                    Err(error_from_response(response).await)
+                }
+            })
+            .await
     }
 
     /// Get the service configuration
     pub fn config(&self) -> &MicroserviceConfig {
         &self.config
     }
+
+    /// Current state of the session manager circuit
+    pub fn circuit_state(&self) -> CircuitState {
+        self.circuit_breaker.state()
+    }
+
+    /// The circuit breaker guarding requests to the session manager
+    pub fn circuit_breaker(&self) -> &CircuitBreaker {
+        &self.circuit_breaker
+    }
+}
+
+/// Convert a non-success response into a `SessionManagerError`
+pub(crate) async fn error_from_response(response: reqwest::Response) -> MicroserviceError {
+    let status = response.status().as_u16();
+    let error_text = response
+        .text()
+        .await
+        .unwrap_or_else(|_| "Unknown error".to_string());
+
+    // Try to parse as ErrorResponse
+    if let Ok(error_response) = serde_json::from_str::<ErrorResponse>(&error_text) {
+        MicroserviceError::SessionManagerError {
+            status,
+            message: error_response.message,
+        }
+    } else {
+        MicroserviceError::SessionManagerError {
+            status,
+            message: error_text,
+        }
+    }
 }
 
 /// Microservice runner that handles HTTP server and session manager integration
 pub struct MicroserviceRunner {
     client: SessionManagerClient,
     handler: Arc<dyn MicroserviceHandler>,
+    room_sessions: bool,
+    room_connections: RoomConnections,
+    reconnect: Option<ReconnectPolicy>,
 }
 
 impl MicroserviceRunner {
@@ -100,7 +181,51 @@ impl MicroserviceRunner {
     pub fn new(config: MicroserviceConfig, handler: Arc<dyn MicroserviceHandler>) -> Result<Self> {
         let client = SessionManagerClient::new(config)?;
 
-        Ok(Self { client, handler })
+        Ok(Self {
+            client,
+            handler,
+            room_sessions: false,
+            room_connections: RoomConnections::new(),
+            reconnect: None,
+        })
+    }
+
+    /// Let the runner join rooms itself instead of calling `handle_join_room`
+    ///
+    /// On every join-room request the runner connects a [`RoomSession`], which dispatches
+    /// data messages to [`MicroserviceHandler::on_data_received`], and then notifies the
+    /// session manager that the service is ready.
+    pub fn with_room_sessions(mut self) -> Self {
+        self.room_sessions = true;
+        self
+    }
+
+    /// Reconnect room sessions whose connection drops, per `policy`
+    ///
+    /// Only applies to rooms joined by the runner, see [`Self::with_room_sessions`]. Off by
+    /// default: a dropped room ends its event loop and is reported by `/health`.
+    pub fn with_reconnect(mut self, policy: ReconnectPolicy) -> Self {
+        self.reconnect = Some(policy);
+        self
+    }
+
+    /// Share a room connection tracker with the handler
+    ///
+    /// Handlers that join rooms themselves record their connection state in it, and can
+    /// read it from their own `health_check`.
+    pub fn with_room_connections(mut self, connections: RoomConnections) -> Self {
+        self.room_connections = connections;
+        self
+    }
+
+    /// Connection state of the rooms the service has joined
+    pub fn room_connections(&self) -> &RoomConnections {
+        &self.room_connections
+    }
+
+    /// Get the session manager client, e.g. to clone into a handler for `notify_ready`
+    pub fn client(&self) -> &SessionManagerClient {
+        &self.client
     }
 
     /// Start the microservice (register and start HTTP server)
@@ -126,37 +251,71 @@ impl MicroserviceRunner {
 
         #[derive(Clone)]
         struct AppState {
+            client: SessionManagerClient,
             handler: Arc<dyn MicroserviceHandler>,
+            room_sessions: bool,
+            room_connections: RoomConnections,
+            reconnect: Option<ReconnectPolicy>,
         }
 
         let app_state = AppState {
+            client: self.client.clone(),
             handler: self.handler.clone(),
+            room_sessions: self.room_sessions,
+            room_connections: self.room_connections.clone(),
+            reconnect: self.reconnect.clone(),
         };
 
+        // Connect the room on the handler's behalf and report readiness
+        async fn join_with_room_session(state: &AppState, request: &JoinRoomRequest) -> Result<()> {
+            // The event loop keeps the room alive until it disconnects
+            RoomSession::connect_reconnecting(
+                request,
+                state.handler.clone(),
+                state.room_connections.clone(),
+                state.reconnect.clone(),
+            )
+            .await?;
+            state
+                .client
+                .notify_ready(&request.service_identity, &request.session_id)
+                .await?;
+            Ok(())
+        }
+
         // Handler for join-room requests
         async fn handle_join_room(
             State(state): State<AppState>,
-            Json(request): Json<JoinRoomRequest>,
-        ) -> std::result::Result<Json<JoinRoomResponse>, (StatusCode, String)> {
+            payload: Negotiated<JoinRoomRequest>,
+        ) -> std::result::Result<Negotiated<JoinRoomResponse>, (StatusCode, String)> {
+            let request = &payload.value;
             info!(
                 "Received join-room request for session {}",
                 request.session_id
             );
 
             // Call the microservice handler
-            match state.handler.handle_join_room(request.clone()).await {
+            let result = if state.room_sessions {
+                join_with_room_session(&state, request).await
+            } else {
+                state.handler.handle_join_room(request.clone()).await
+            };
+
+            match result {
                 Ok(()) => {
                     info!(
                         "Successfully joined room for session {}",
                         request.session_id
                     );
+                    let capabilities = state.handler.capabilities(request).await;
                     let response = JoinRoomResponse {
                         success: true,
                         message: "Successfully joined room".to_string(),
-                        session_id: request.session_id,
-                        service_id: request.service_identity,
+                        session_id: request.session_id.clone(),
+                        service_id: request.service_identity.clone(),
+                        capabilities,
                     };
-                    Ok(Json(response))
+                    Ok(payload.reply(response))
                 }
                 Err(e) => {
                     error!("Failed to join room: {}", e);
@@ -168,10 +327,48 @@ impl MicroserviceRunner {
             }
         }
 
-        // Health check handler
+        // Handler for leave-room requests
+        async fn handle_leave_room(
+            State(state): State<AppState>,
+            Negotiated { value: request, .. }: Negotiated<LeaveRoomRequest>,
+        ) -> std::result::Result<StatusCode, (StatusCode, String)> {
+            info!(
+                "Received leave-room request for session {} ({:?})",
+                request.session_id, request.reason
+            );
+
+            // Leaving on request is not a lost connection
+            state.room_connections.remove(&request.session_id);
+
+            state
+                .handler
+                .handle_leave_room(request)
+                .await
+                .map_err(|e| {
+                    error!("Failed to leave room: {}", e);
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        format!("Failed to leave room: {}", e),
+                    )
+                })?;
+            Ok(StatusCode::OK)
+        }
+
+        // Health check handler: unhealthy while a joined room is lost or the handler fails
         async fn handle_health_check(
             State(state): State<AppState>,
         ) -> std::result::Result<Json<serde_json::Value>, (StatusCode, String)> {
+            let disconnected = state.room_connections.disconnected();
+            if !disconnected.is_empty() {
+                return Err((
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    format!(
+                        "Health check failed: lost room connection for sessions {}",
+                        disconnected.join(", ")
+                    ),
+                ));
+            }
+
             match state.handler.health_check().await {
                 Ok(()) => Ok(Json(serde_json::json!({"status": "healthy"}))),
                 Err(e) => Err((
@@ -183,16 +380,17 @@ impl MicroserviceRunner {
 
         let app = Router::new()
             .route("/join-room", post(handle_join_room))
+            .route("/leave-room", post(handle_leave_room))
             .route("/health", axum::routing::get(handle_health_check))
             .with_state(app_state);
 
         // Extract port from service endpoint
         let port = self.extract_port_from_endpoint()?;
-        let addr = format!("0.0.0.0:{}", port);
+        let addr = SocketAddr::from(([0, 0, 0, 0], port));
 
         info!("Starting HTTP server on {}", addr);
 
-        let listener = tokio::net::TcpListener::bind(&addr).await.map_err(|e| {
+        let listener = bind_listener(addr, self.client.config.listen_backlog).map_err(|e| {
             MicroserviceError::ConfigurationError(format!("Failed to bind to {}: {}", addr, e))
         })?;
 
@@ -225,3 +423,14 @@ impl MicroserviceRunner {
         Ok(port)
     }
 }
+
+/// Bind a listener with `SO_REUSEADDR` set, so a restarted service can take its port back
+/// while connections from the previous process are still in `TIME_WAIT`
+pub fn bind_listener(addr: SocketAddr, backlog: u32) -> std::io::Result<tokio::net::TcpListener> {
+    let socket = Socket::new(Domain::for_address(addr), Type::STREAM, None)?;
+    socket.set_reuse_address(true)?;
+    socket.set_nonblocking(true)?;
+    socket.bind(&addr.into())?;
+    socket.listen(backlog.min(i32::MAX as u32) as i32)?;
+    tokio::net::TcpListener::from_std(socket.into())
+}
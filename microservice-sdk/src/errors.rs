@@ -18,12 +18,33 @@ pub enum MicroserviceError {
     #[error("Join room failed: {0}")]
     JoinRoomFailed(String),
 
+    #[error("Publish data failed: {0}")]
+    PublishDataFailed(String),
+
     #[error("Notify ready failed: {0}")]
     NotifyReadyFailed(String),
 
+    #[error("Event stream error: {0}")]
+    EventStream(String),
+
+    #[error("Session {0} ended before becoming ready")]
+    SessionEnded(String),
+
     #[error("Configuration error: {0}")]
     ConfigurationError(String),
 
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+
+    #[error("Request {0} got no response in time")]
+    RpcTimeout(String),
+
+    #[error("Request {id} failed: {message}")]
+    RpcFailed { id: String, message: String },
+
+    #[error("Session manager circuit is open, retry after {retry_after:?}")]
+    CircuitOpen { retry_after: std::time::Duration },
+
     #[error("Timeout waiting for response")]
     Timeout,
 
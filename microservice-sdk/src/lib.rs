@@ -2,15 +2,39 @@
 //!
 //! This SDK provides a simple API for microservices to:
 //! - Register themselves with the session manager
-//! - Join LiveKit rooms when requested
+//! - Join LiveKit rooms when requested, optionally handing only data messages to the service
+//!   and reconnecting dropped rooms ([`ReconnectPolicy`])
 //! - Notify the session manager when ready
+//! - Receive notifications as JSON or msgpack ([`payload`])
+//! - Fail fast while the session manager is unavailable ([`circuit_breaker`])
+//! - Exchange typed request/response/event messages over the data channel ([`protocol`])
+//! - Make correlated request/response calls to other participants ([`rpc`])
+//! - Cap how fast a room session publishes data messages ([`rate_limit`])
+//!
+//! Applications can use [`SessionClient`] to create sessions and wait for them to become ready.
 
+pub mod circuit_breaker;
 pub mod client;
 pub mod errors;
 pub mod models;
+pub mod payload;
+pub mod protocol;
+pub mod rate_limit;
+pub mod room_session;
+pub mod rpc;
+pub mod session_client;
 pub mod traits;
 
+pub use circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitState};
 pub use client::{MicroserviceRunner, SessionManagerClient};
 pub use errors::*;
 pub use models::*;
+pub use payload::Negotiated;
+pub use protocol::Envelope;
+pub use rate_limit::{ExcessPolicy, PublishLimit, PublishRateLimiter};
+pub use room_session::{
+    data_packet, DataContext, ReconnectPolicy, RoomConnectionState, RoomConnections, RoomSession,
+};
+pub use rpc::{PendingRequests, RpcSession};
+pub use session_client::SessionClient;
 pub use traits::*;
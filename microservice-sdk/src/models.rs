@@ -1,6 +1,17 @@
+use crate::circuit_breaker::CircuitBreakerConfig;
+use crate::errors::{MicroserviceError, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+// Types exchanged with the session manager, shared so both sides serialize them the same way
+pub use session_protocol::{
+    Capabilities, ConnectionQuality, JoinRoomRequest, JoinRoomResponse, LeaveReason,
+    LeaveRoomRequest, ParticipantStats, PayloadFormat, RegisterMicroserviceRequest,
+    RegisterMicroserviceResponse, RoomStats, SequencedEvent, ServiceReadyRequest,
+    ServiceReadyResponse, SessionEvent, SessionStatus, JSON_CONTENT_TYPE, MSGPACK_CONTENT_TYPE,
+    NOTIFICATION_FORMAT_METADATA_KEY, SERVICE_TYPE_METADATA_KEY, SESSION_SNAPSHOT_EVENT,
+};
+
 /// Configuration for the microservice SDK
 #[derive(Debug, Clone)]
 pub struct MicroserviceConfig {
@@ -14,6 +25,10 @@ pub struct MicroserviceConfig {
     pub metadata: HashMap<String, String>,
     /// Timeout for HTTP requests (in seconds)
     pub request_timeout_secs: u64,
+    /// Listen backlog of the runner's HTTP server
+    pub listen_backlog: u32,
+    /// When to stop calling an unavailable session manager
+    pub circuit_breaker: CircuitBreakerConfig,
 }
 
 impl MicroserviceConfig {
@@ -24,6 +39,8 @@ impl MicroserviceConfig {
             service_endpoint,
             metadata: HashMap::new(),
             request_timeout_secs: 30,
+            listen_backlog: 1024,
+            circuit_breaker: CircuitBreakerConfig::default(),
         }
     }
 
@@ -32,59 +49,204 @@ impl MicroserviceConfig {
         self
     }
 
+    /// Register as an instance of `service_type`, so sessions requiring the type can be
+    /// assigned this instance
+    pub fn with_service_type(mut self, service_type: impl Into<String>) -> Self {
+        self.metadata
+            .insert(SERVICE_TYPE_METADATA_KEY.to_string(), service_type.into());
+        self
+    }
+
+    /// Ask the session manager to send `/join-room` and `/leave-room` notifications in
+    /// `format`; the runner accepts every format regardless
+    pub fn with_notification_format(mut self, format: PayloadFormat) -> Self {
+        self.metadata.insert(
+            NOTIFICATION_FORMAT_METADATA_KEY.to_string(),
+            format.as_str().to_string(),
+        );
+        self
+    }
+
     pub fn with_timeout(mut self, timeout_secs: u64) -> Self {
         self.request_timeout_secs = timeout_secs;
         self
     }
+
+    pub fn with_listen_backlog(mut self, backlog: u32) -> Self {
+        self.listen_backlog = backlog;
+        self
+    }
+
+    pub fn with_circuit_breaker(mut self, circuit_breaker: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker = circuit_breaker;
+        self
+    }
 }
 
-/// Request to register a microservice with the session manager
-#[derive(Debug, Serialize)]
-pub struct RegisterMicroserviceRequest {
-    pub service_id: String,
-    pub endpoint: String,
+/// Request to create a session (sent by applications to the session manager)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CreateSessionRequest {
+    pub user_identity: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub room_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<HashMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required_services: Option<Vec<String>>,
+    /// Service types to fill with any available registered instance, one per entry
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required_service_types: Option<Vec<String>>,
+    /// Return the user's existing `Ready`/`Active` session instead of creating a new one
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub reuse_existing: bool,
+    /// Set to `Some(false)` to skip the session manager's room observer; services then
+    /// become ready by acknowledging the join request or calling `notify_ready`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub observe: Option<bool>,
+    /// Terminate the session this many seconds after creation, overriding the server's
+    /// default maximum lifetime
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_lifetime_secs: Option<u64>,
+    /// Consider the session ready once this many services are ready rather than all
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_ready_services: Option<usize>,
 }
 
-/// Response from registering a microservice
-#[derive(Debug, Deserialize)]
-pub struct RegisterMicroserviceResponse {
-    pub success: bool,
-    pub service_id: String,
-    pub message: String,
+impl CreateSessionRequest {
+    pub fn new(user_identity: impl Into<String>) -> Self {
+        Self {
+            user_identity: user_identity.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Start building a request field by field
+    pub fn builder() -> CreateSessionRequestBuilder {
+        CreateSessionRequestBuilder::default()
+    }
+}
+
+/// Fluent builder for [`CreateSessionRequest`]
+///
+/// [`build`](Self::build) rejects requests the session manager would refuse anyway: a
+/// missing or blank user identity, and zero lifetimes or quorums.
+#[derive(Debug, Clone, Default)]
+pub struct CreateSessionRequestBuilder {
+    request: CreateSessionRequest,
+}
+
+impl CreateSessionRequestBuilder {
+    pub fn user_identity(mut self, user_identity: impl Into<String>) -> Self {
+        self.request.user_identity = user_identity.into();
+        self
+    }
+
+    pub fn user_name(mut self, user_name: impl Into<String>) -> Self {
+        self.request.user_name = Some(user_name.into());
+        self
+    }
+
+    pub fn room_name(mut self, room_name: impl Into<String>) -> Self {
+        self.request.room_name = Some(room_name.into());
+        self
+    }
+
+    /// Require the service with this id; may be called repeatedly
+    pub fn required_service(mut self, service_id: impl Into<String>) -> Self {
+        self.request
+            .required_services
+            .get_or_insert_with(Vec::new)
+            .push(service_id.into());
+        self
+    }
+
+    /// Require one instance of this service type; may be called repeatedly
+    pub fn required_service_type(mut self, service_type: impl Into<String>) -> Self {
+        self.request
+            .required_service_types
+            .get_or_insert_with(Vec::new)
+            .push(service_type.into());
+        self
+    }
+
+    /// Add one session metadata entry, replacing an earlier value for the same key
+    pub fn metadata_entry(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.request
+            .metadata
+            .get_or_insert_with(HashMap::new)
+            .insert(key.into(), value.into());
+        self
+    }
+
+    pub fn reuse_existing(mut self, reuse_existing: bool) -> Self {
+        self.request.reuse_existing = reuse_existing;
+        self
+    }
+
+    pub fn observe(mut self, observe: bool) -> Self {
+        self.request.observe = Some(observe);
+        self
+    }
+
+    pub fn max_lifetime_secs(mut self, secs: u64) -> Self {
+        self.request.max_lifetime_secs = Some(secs);
+        self
+    }
+
+    pub fn min_ready_services(mut self, count: usize) -> Self {
+        self.request.min_ready_services = Some(count);
+        self
+    }
+
+    pub fn build(self) -> Result<CreateSessionRequest> {
+        if self.request.user_identity.trim().is_empty() {
+            return Err(MicroserviceError::InvalidRequest(
+                "user_identity is required".to_string(),
+            ));
+        }
+        if self.request.max_lifetime_secs == Some(0) {
+            return Err(MicroserviceError::InvalidRequest(
+                "max_lifetime_secs must be positive".to_string(),
+            ));
+        }
+        if self.request.min_ready_services == Some(0) {
+            return Err(MicroserviceError::InvalidRequest(
+                "min_ready_services must be positive".to_string(),
+            ));
+        }
+        Ok(self.request)
+    }
 }
 
-/// Request to join a LiveKit room (sent by session manager to microservice)
+/// Response from creating a session
 #[derive(Debug, Clone, Deserialize)]
-pub struct JoinRoomRequest {
-    pub room_name: String,
+pub struct CreateSessionResponse {
     pub session_id: String,
-    pub service_identity: String,
+    pub room_name: String,
     pub access_token: String,
     pub livekit_url: String,
+    pub status: SessionStatus,
 }
 
-/// Response when joining a room
-#[derive(Debug, Serialize)]
-pub struct JoinRoomResponse {
-    pub success: bool,
-    pub message: String,
+/// Current state of a session
+#[derive(Debug, Clone, Deserialize)]
+pub struct SessionStatusResponse {
     pub session_id: String,
-    pub service_id: String,
-}
-
-/// Request to notify that the service is ready
-#[derive(Debug, Serialize)]
-pub struct ServiceReadyRequest {
-    pub service_id: String,
-}
-
-/// Response from notifying service ready
-#[derive(Debug, Deserialize)]
-pub struct ServiceReadyResponse {
-    pub success: bool,
-    pub message: String,
-    pub all_services_ready: bool,
+    pub room_name: String,
+    pub status: SessionStatus,
+    pub ready_services: Vec<String>,
+    pub pending_services: Vec<String>,
+    /// Capabilities reported by each service when joining, keyed by service id
+    #[serde(default)]
+    pub service_capabilities: HashMap<String, Capabilities>,
+    pub metadata: HashMap<String, String>,
+    pub created_at: String,
+    pub updated_at: String,
+    /// Latest room stats taken by the session manager, if it observes the room
+    #[serde(default)]
+    pub room_stats: Option<RoomStats>,
 }
 
 /// Error response from session manager
@@ -0,0 +1,85 @@
+//! Content negotiation for the notifications the runner receives from the session manager
+//!
+//! Services choose the format with [`MicroserviceConfig::with_notification_format`]; the
+//! runner decodes whatever format a request arrives in, so it keeps accepting JSON from
+//! session managers that do not know about msgpack.
+//!
+//! [`MicroserviceConfig::with_notification_format`]: crate::MicroserviceConfig::with_notification_format
+
+use axum::{
+    body::Bytes,
+    extract::{FromRequest, Request},
+    http::{header, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::models::PayloadFormat;
+
+/// A body in a negotiated [`PayloadFormat`]
+///
+/// As an extractor, decodes the request body according to its `Content-Type`: msgpack
+/// when the header says so, JSON otherwise, including when it is missing. As a response,
+/// encodes `value` in `format`, so handlers answer in the format they were called with.
+#[derive(Debug, Clone)]
+pub struct Negotiated<T> {
+    pub format: PayloadFormat,
+    pub value: T,
+}
+
+impl<T> Negotiated<T> {
+    /// Answer in the same format as this request
+    pub fn reply<U>(&self, value: U) -> Negotiated<U> {
+        Negotiated {
+            format: self.format,
+            value,
+        }
+    }
+}
+
+impl<S, T> FromRequest<S> for Negotiated<T>
+where
+    S: Send + Sync,
+    T: DeserializeOwned,
+{
+    type Rejection = (StatusCode, String);
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let format = request
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(PayloadFormat::from_content_type)
+            .unwrap_or_default();
+        let body = Bytes::from_request(request, state)
+            .await
+            .map_err(|e| (e.status(), e.body_text()))?;
+        let value = format.decode(&body).map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                format!("Failed to decode {} body: {}", format, e),
+            )
+        })?;
+        Ok(Self { format, value })
+    }
+}
+
+impl<T: Serialize> IntoResponse for Negotiated<T> {
+    fn into_response(self) -> Response {
+        match self.format.encode(&self.value) {
+            Ok(body) => (
+                [(
+                    header::CONTENT_TYPE,
+                    HeaderValue::from_static(self.format.content_type()),
+                )],
+                body,
+            )
+                .into_response(),
+            Err(e) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to encode {} response: {}", self.format, e),
+            )
+                .into_response(),
+        }
+    }
+}
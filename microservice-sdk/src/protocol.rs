@@ -0,0 +1,155 @@
+//! Message envelopes exchanged between clients and services over LiveKit data packets
+//!
+//! Every message is a JSON object tagged with a `kind` field and published on the topic
+//! matching its kind, so services and clients can tell requests, responses and events
+//! apart without inspecting payload text:
+//!
+//! ```json
+//! { "kind": "request", "id": "1", "method": "ping", "params": null }
+//! ```
+
+use livekit::prelude::DataPacket;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{errors::Result, room_session::data_packet};
+
+/// Data packet topics used for each message kind
+pub mod topics {
+    pub const REQUEST: &str = "request";
+    pub const RESPONSE: &str = "response";
+    pub const EVENT: &str = "event";
+}
+
+/// A message sent over the data channel
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Envelope {
+    Request(Request),
+    Response(Response),
+    Event(Event),
+}
+
+/// Asks the receiver to perform `method`; answered by a [`Response`] with the same `id`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Request {
+    pub id: String,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+/// Outcome of a [`Request`], carrying either `result` or `error`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Response {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// A one-way notification that expects no answer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Event {
+    pub name: String,
+    #[serde(default)]
+    pub data: Value,
+}
+
+impl Request {
+    pub fn new(id: impl Into<String>, method: impl Into<String>, params: Value) -> Self {
+        Self {
+            id: id.into(),
+            method: method.into(),
+            params,
+        }
+    }
+}
+
+impl Response {
+    /// Successful response to the request with `id`
+    pub fn ok(id: impl Into<String>, result: Value) -> Self {
+        Self {
+            id: id.into(),
+            result: Some(result),
+            error: None,
+        }
+    }
+
+    /// Failed response to the request with `id`
+    pub fn err(id: impl Into<String>, error: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            result: None,
+            error: Some(error.into()),
+        }
+    }
+
+    /// The result, or the error message if the request failed
+    pub fn into_result(self) -> std::result::Result<Value, String> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.result.unwrap_or(Value::Null)),
+        }
+    }
+}
+
+impl Event {
+    pub fn new(name: impl Into<String>, data: Value) -> Self {
+        Self {
+            name: name.into(),
+            data,
+        }
+    }
+}
+
+impl Envelope {
+    /// The data packet topic messages of this kind are published on
+    pub fn topic(&self) -> &'static str {
+        match self {
+            Envelope::Request(_) => topics::REQUEST,
+            Envelope::Response(_) => topics::RESPONSE,
+            Envelope::Event(_) => topics::EVENT,
+        }
+    }
+
+    /// Serialize into a data packet payload
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec(self)?)
+    }
+
+    /// Parse a data packet payload
+    pub fn decode(payload: &[u8]) -> Result<Self> {
+        Ok(serde_json::from_slice(payload)?)
+    }
+
+    /// Build a `DataPacket` on this message's topic; an empty `destinations` list
+    /// addresses every participant of the room
+    pub fn to_packet(&self, reliable: bool, destinations: &[String]) -> Result<DataPacket> {
+        Ok(data_packet(
+            Some(self.topic()),
+            self.encode()?,
+            reliable,
+            destinations,
+        ))
+    }
+}
+
+impl From<Request> for Envelope {
+    fn from(request: Request) -> Self {
+        Envelope::Request(request)
+    }
+}
+
+impl From<Response> for Envelope {
+    fn from(response: Response) -> Self {
+        Envelope::Response(response)
+    }
+}
+
+impl From<Event> for Envelope {
+    fn from(event: Event) -> Self {
+        Envelope::Event(event)
+    }
+}
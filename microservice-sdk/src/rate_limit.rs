@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// What happens to messages published over the limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExcessPolicy {
+    /// Discard the message; the publish call still succeeds
+    Drop,
+    /// Hold the publish call until the topic has capacity again
+    Delay,
+}
+
+/// Cap on data-channel messages per second, applied to each topic separately
+///
+/// Up to `messages_per_second` messages may go out in a burst before the limit applies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublishLimit {
+    pub messages_per_second: u32,
+    pub on_excess: ExcessPolicy,
+}
+
+impl PublishLimit {
+    pub fn new(messages_per_second: u32, on_excess: ExcessPolicy) -> Self {
+        Self {
+            messages_per_second,
+            on_excess,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+#[derive(Debug, Default)]
+struct LimiterState {
+    limit: Option<PublishLimit>,
+    buckets: HashMap<Option<String>, Bucket>,
+}
+
+/// Per-topic token bucket guarding a room's publish helpers
+///
+/// Unlimited until a [`PublishLimit`] is set. Shared between a
+/// [`RoomSession`](crate::RoomSession) and the [`DataContext`](crate::DataContext)s of its
+/// event loop, so replies count against the same limit.
+#[derive(Debug, Default)]
+pub struct PublishRateLimiter {
+    state: Mutex<LimiterState>,
+    dropped: AtomicU64,
+}
+
+impl PublishRateLimiter {
+    pub fn new(limit: Option<PublishLimit>) -> Self {
+        Self {
+            state: Mutex::new(LimiterState {
+                limit,
+                buckets: HashMap::new(),
+            }),
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    /// Replace the limit; `None` lifts it
+    pub fn set_limit(&self, limit: Option<PublishLimit>) {
+        let mut state = self.state.lock().unwrap();
+        state.limit = limit;
+        state.buckets.clear();
+    }
+
+    pub fn limit(&self) -> Option<PublishLimit> {
+        self.state.lock().unwrap().limit
+    }
+
+    /// Messages discarded under [`ExcessPolicy::Drop`] so far
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Take a slot for one message on `topic`, waiting for one under
+    /// [`ExcessPolicy::Delay`]. Returns `false` if the message must be dropped.
+    pub async fn acquire(&self, topic: Option<&str>) -> bool {
+        loop {
+            let wait = match self.try_acquire(topic) {
+                Ok(()) => return true,
+                Err(None) => {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                    return false;
+                }
+                Err(Some(wait)) => wait,
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// `Err(Some(wait))` when the caller should retry after `wait`, `Err(None)` to drop
+    fn try_acquire(&self, topic: Option<&str>) -> Result<(), Option<Duration>> {
+        let mut state = self.state.lock().unwrap();
+        let Some(limit) = state.limit else {
+            return Ok(());
+        };
+        let rate = f64::from(limit.messages_per_second.max(1));
+
+        let now = Instant::now();
+        let bucket = state
+            .buckets
+            .entry(topic.map(str::to_string))
+            .or_insert(Bucket {
+                tokens: rate,
+                refilled_at: now,
+            });
+        let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(rate);
+        bucket.refilled_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return Ok(());
+        }
+        match limit.on_excess {
+            ExcessPolicy::Drop => Err(None),
+            ExcessPolicy::Delay => Err(Some(Duration::from_secs_f64((1.0 - bucket.tokens) / rate))),
+        }
+    }
+}
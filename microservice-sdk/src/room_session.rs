@@ -0,0 +1,592 @@
+use futures::future::OptionFuture;
+use livekit::prelude::*;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use tokio::{
+    sync::{mpsc::UnboundedReceiver, watch},
+    task::JoinHandle,
+};
+use tracing::{debug, error, info, warn};
+
+use crate::{
+    errors::{MicroserviceError, Result},
+    models::JoinRoomRequest,
+    protocol::Envelope,
+    rate_limit::{PublishLimit, PublishRateLimiter},
+    traits::MicroserviceHandler,
+};
+
+/// A data message received in a session's room, passed to
+/// [`MicroserviceHandler::on_data_received`]
+#[derive(Debug, Clone)]
+pub struct DataContext {
+    pub session_id: String,
+    pub room_name: String,
+    /// Identity of the sender, `None` if the server sent the message
+    pub participant_identity: Option<String>,
+    pub topic: Option<String>,
+    pub payload: Arc<Vec<u8>>,
+    room: Arc<Room>,
+    limiter: Arc<PublishRateLimiter>,
+}
+
+impl DataContext {
+    /// The payload decoded as UTF-8, replacing invalid sequences
+    pub fn text(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(&self.payload)
+    }
+
+    /// The room the message was received in, e.g. to publish tracks
+    pub fn room(&self) -> &Room {
+        &self.room
+    }
+
+    /// The payload decoded as a protocol [`Envelope`]
+    pub fn envelope(&self) -> Result<Envelope> {
+        Envelope::decode(&self.payload)
+    }
+
+    /// Publish a reliable protocol message back to the sender only, e.g. a `Response`
+    pub async fn reply_envelope(&self, envelope: &Envelope) -> Result<()> {
+        let destinations: Vec<String> = self.participant_identity.iter().cloned().collect();
+        publish_packet(
+            &self.room,
+            &self.limiter,
+            envelope.to_packet(true, &destinations)?,
+        )
+        .await
+    }
+
+    /// Publish a reliable data message to every participant of the room
+    pub async fn send(&self, payload: impl Into<Vec<u8>>, topic: Option<String>) -> Result<()> {
+        let packet = data_packet(topic.as_deref(), payload.into(), true, &[]);
+        publish_packet(&self.room, &self.limiter, packet).await
+    }
+
+    /// Publish a reliable data message back to the sender only
+    pub async fn reply(&self, payload: impl Into<Vec<u8>>, topic: Option<String>) -> Result<()> {
+        let destinations: Vec<String> = self.participant_identity.iter().cloned().collect();
+        let packet = data_packet(topic.as_deref(), payload.into(), true, &destinations);
+        publish_packet(&self.room, &self.limiter, packet).await
+    }
+}
+
+/// Build the `DataPacket` the publish helpers send
+///
+/// An empty `destinations` list addresses every participant of the room; lossy packets
+/// (`reliable == false`) may be dropped or reordered but have lower latency.
+pub fn data_packet(
+    topic: Option<&str>,
+    payload: Vec<u8>,
+    reliable: bool,
+    destinations: &[String],
+) -> DataPacket {
+    DataPacket {
+        payload,
+        topic: topic.map(str::to_string),
+        reliable,
+        destination_identities: destinations
+            .iter()
+            .map(|identity| ParticipantIdentity::from(identity.clone()))
+            .collect(),
+    }
+}
+
+async fn publish_packet(
+    room: &Room,
+    limiter: &PublishRateLimiter,
+    packet: DataPacket,
+) -> Result<()> {
+    if !limiter.acquire(packet.topic.as_deref()).await {
+        debug!(
+            "Dropped data message on topic {:?}: publish rate limit reached",
+            packet.topic
+        );
+        return Ok(());
+    }
+
+    room.local_participant()
+        .publish_data(packet)
+        .await
+        .map_err(|e| MicroserviceError::PublishDataFailed(e.to_string()))
+}
+
+/// Connection state of a room the microservice has joined
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoomConnectionState {
+    Connected,
+    Reconnecting,
+    /// The connection dropped for a reason other than the session ending
+    Disconnected,
+}
+
+/// Tracks the connection state of every joined room, keyed by session id
+///
+/// The runner's `/health` endpoint reports unhealthy while any room is
+/// [`RoomConnectionState::Disconnected`]. Rooms that end normally (deleted, closed, or
+/// left on purpose) are removed rather than marked disconnected.
+#[derive(Debug, Clone, Default)]
+pub struct RoomConnections {
+    rooms: Arc<Mutex<HashMap<String, RoomConnectionState>>>,
+}
+
+impl RoomConnections {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&self, session_id: &str, state: RoomConnectionState) {
+        self.rooms
+            .lock()
+            .unwrap()
+            .insert(session_id.to_string(), state);
+    }
+
+    /// Stop tracking a session's room, e.g. after leaving it on purpose
+    pub fn remove(&self, session_id: &str) {
+        self.rooms.lock().unwrap().remove(session_id);
+    }
+
+    pub fn get(&self, session_id: &str) -> Option<RoomConnectionState> {
+        self.rooms.lock().unwrap().get(session_id).copied()
+    }
+
+    /// Sessions whose room connection was lost and has not recovered, sorted
+    pub fn disconnected(&self) -> Vec<String> {
+        let mut sessions: Vec<String> = self
+            .rooms
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, state)| **state == RoomConnectionState::Disconnected)
+            .map(|(session_id, _)| session_id.clone())
+            .collect();
+        sessions.sort();
+        sessions
+    }
+
+    /// Update a session's state from one of its room events
+    ///
+    /// Services running their own event loop call this for every event to keep the
+    /// runner's health check accurate.
+    pub fn handle_event(&self, session_id: &str, event: &RoomEvent) {
+        match event {
+            RoomEvent::Reconnecting => self.set(session_id, RoomConnectionState::Reconnecting),
+            RoomEvent::Reconnected => self.set(session_id, RoomConnectionState::Connected),
+            RoomEvent::Disconnected { reason } if is_expected_disconnect(*reason) => {
+                self.remove(session_id)
+            }
+            RoomEvent::Disconnected { .. } => {
+                self.set(session_id, RoomConnectionState::Disconnected)
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Whether a disconnect reason means the session ended rather than the connection failing
+fn is_expected_disconnect(reason: DisconnectReason) -> bool {
+    matches!(
+        reason,
+        DisconnectReason::ClientInitiated
+            | DisconnectReason::ParticipantRemoved
+            | DisconnectReason::RoomDeleted
+            | DisconnectReason::RoomClosed
+    )
+}
+
+/// How a room session reconnects after its connection drops unexpectedly
+///
+/// Reconnects reuse the access token from the join request, which the session manager
+/// issues for the lifetime of a session. Disconnects that end the session (room deleted,
+/// participant removed, closed on purpose) are never retried.
+#[derive(Debug, Clone)]
+pub struct ReconnectPolicy {
+    /// Attempts before giving up and leaving the room disconnected
+    pub max_attempts: u32,
+    /// Delay before the first attempt, doubled for each following one
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(10),
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// Delay before attempt `attempt`, counting from 1
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.base_delay.saturating_mul(factor).min(self.max_delay)
+    }
+
+    /// Run `connect` with backoff until it succeeds, returning the last error once
+    /// `max_attempts` attempts have failed
+    ///
+    /// `connect` is passed the attempt number, counting from 1.
+    pub async fn retry<T, F, Fut>(&self, mut connect: F) -> Result<T>
+    where
+        F: FnMut(u32) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut attempt = 1;
+        loop {
+            tokio::time::sleep(self.delay(attempt)).await;
+            match connect(attempt).await {
+                Ok(connected) => return Ok(connected),
+                Err(e) if attempt >= self.max_attempts => return Err(e),
+                Err(e) => warn!("Reconnect attempt {} failed: {}", attempt, e),
+            }
+            attempt += 1;
+        }
+    }
+}
+
+/// The room a session is currently connected to, replaced when it reconnects
+type SharedRoom = Arc<RwLock<Arc<Room>>>;
+
+fn current_room(room: &SharedRoom) -> Arc<Room> {
+    room.read().unwrap().clone()
+}
+
+/// A microservice's connection to a session's LiveKit room
+///
+/// Wraps the connected `Room` and provides helpers for publishing data. Sessions from
+/// [`RoomSession::connect`] run an event loop that dispatches `DataReceived` events to
+/// [`MicroserviceHandler::on_data_received`] until the room disconnects, or until
+/// reconnecting fails if a [`ReconnectPolicy`] is set.
+#[derive(Debug)]
+pub struct RoomSession {
+    session_id: String,
+    room_name: String,
+    room: SharedRoom,
+    event_loop: Option<JoinHandle<()>>,
+    connections: RoomConnections,
+    limiter: Arc<PublishRateLimiter>,
+    closing: watch::Sender<bool>,
+}
+
+impl RoomSession {
+    /// Connect to the room in `request` and start dispatching its events to `handler`
+    pub async fn connect(
+        request: &JoinRoomRequest,
+        handler: Arc<dyn MicroserviceHandler>,
+    ) -> Result<Self> {
+        Self::connect_tracked(request, handler, RoomConnections::new()).await
+    }
+
+    /// Like [`RoomSession::connect`], recording the room's connection state in `connections`
+    pub async fn connect_tracked(
+        request: &JoinRoomRequest,
+        handler: Arc<dyn MicroserviceHandler>,
+        connections: RoomConnections,
+    ) -> Result<Self> {
+        Self::connect_reconnecting(request, handler, connections, None).await
+    }
+
+    /// Like [`RoomSession::connect_tracked`], reconnecting per `reconnect` when the
+    /// connection drops and resuming the event loop on the new connection
+    pub async fn connect_reconnecting(
+        request: &JoinRoomRequest,
+        handler: Arc<dyn MicroserviceHandler>,
+        connections: RoomConnections,
+        reconnect: Option<ReconnectPolicy>,
+    ) -> Result<Self> {
+        let (mut session, event_rx) = Self::connect_with_events(request).await?;
+        connections.set(&request.session_id, RoomConnectionState::Connected);
+        session.connections = connections.clone();
+
+        let event_loop = EventLoop {
+            session_id: request.session_id.clone(),
+            room_name: request.room_name.clone(),
+            room: session.room.clone(),
+            limiter: session.limiter.clone(),
+            handler,
+            connections,
+        };
+        let reconnect = reconnect.map(|policy| Reconnect {
+            request: request.clone(),
+            policy,
+            closing: session.closing.subscribe(),
+        });
+        session.event_loop = Some(tokio::spawn(event_loop.run(event_rx, reconnect)));
+
+        Ok(session)
+    }
+
+    /// Connect to the room in `request`, leaving its events to the caller
+    ///
+    /// For services that run their own event loop but still want the publish helpers.
+    pub async fn connect_with_events(
+        request: &JoinRoomRequest,
+    ) -> Result<(Self, UnboundedReceiver<RoomEvent>)> {
+        let (room, event_rx) = Room::connect(
+            &request.livekit_url,
+            &request.access_token,
+            RoomOptions::default(),
+        )
+        .await
+        .map_err(|e| {
+            MicroserviceError::JoinRoomFailed(format!("LiveKit connection failed: {}", e))
+        })?;
+
+        info!(
+            "{} connected to room {} for session {}",
+            request.service_identity, request.room_name, request.session_id
+        );
+
+        let session = Self {
+            session_id: request.session_id.clone(),
+            room_name: request.room_name.clone(),
+            room: Arc::new(RwLock::new(Arc::new(room))),
+            event_loop: None,
+            connections: RoomConnections::new(),
+            limiter: Arc::new(PublishRateLimiter::default()),
+            closing: watch::channel(false).0,
+        };
+        Ok((session, event_rx))
+    }
+
+    pub fn session_id(&self) -> &str {
+        &self.session_id
+    }
+
+    pub fn room_name(&self) -> &str {
+        &self.room_name
+    }
+
+    /// The room currently connected, which changes if the session reconnects
+    pub fn room(&self) -> Arc<Room> {
+        current_room(&self.room)
+    }
+
+    /// Cap how fast this session publishes data messages, per topic; `None` lifts the cap
+    ///
+    /// Applies to the publish helpers here and to replies sent through the
+    /// [`DataContext`]s of this session's event loop.
+    pub fn set_publish_limit(&self, limit: Option<PublishLimit>) {
+        self.limiter.set_limit(limit);
+    }
+
+    /// The limiter guarding this session's publishes, e.g. to read how many were dropped
+    pub fn publish_limiter(&self) -> &PublishRateLimiter {
+        &self.limiter
+    }
+
+    /// Whether the dispatching event loop has stopped, i.e. the room disconnected
+    ///
+    /// Always `false` for sessions from [`RoomSession::connect_with_events`].
+    pub fn is_finished(&self) -> bool {
+        self.event_loop
+            .as_ref()
+            .is_some_and(|event_loop| event_loop.is_finished())
+    }
+
+    /// Publish a reliable UTF-8 message to every participant of the room
+    pub async fn publish_text(&self, topic: Option<&str>, message: &str) -> Result<()> {
+        self.publish_bytes(topic, message.as_bytes().to_vec(), true)
+            .await
+    }
+
+    /// Publish a message to every participant of the room
+    pub async fn publish_bytes(
+        &self,
+        topic: Option<&str>,
+        payload: impl Into<Vec<u8>>,
+        reliable: bool,
+    ) -> Result<()> {
+        self.publish_to(&[], topic, payload, reliable).await
+    }
+
+    /// Publish a reliable protocol message to every participant of the room
+    pub async fn publish_envelope(&self, envelope: &Envelope) -> Result<()> {
+        publish_packet(&self.room(), &self.limiter, envelope.to_packet(true, &[])?).await
+    }
+
+    /// Publish a message to the given participants only; an empty list addresses everyone
+    pub async fn publish_to(
+        &self,
+        identities: &[String],
+        topic: Option<&str>,
+        payload: impl Into<Vec<u8>>,
+        reliable: bool,
+    ) -> Result<()> {
+        let packet = data_packet(topic, payload.into(), reliable, identities);
+        publish_packet(&self.room(), &self.limiter, packet).await
+    }
+
+    /// Disconnect from the room and wait for the event loop to finish
+    pub async fn close(self) -> Result<()> {
+        self.connections.remove(&self.session_id);
+        // Stop a reconnect in progress before closing the room it may have replaced
+        self.closing.send_replace(true);
+        self.room().close().await.map_err(|e| {
+            MicroserviceError::JoinRoomFailed(format!("Failed to close room: {}", e))
+        })?;
+
+        if let Some(Err(e)) = OptionFuture::from(self.event_loop).await {
+            warn!(
+                "Event loop for room {} ended abnormally: {}",
+                self.room_name, e
+            );
+        }
+        Ok(())
+    }
+}
+
+/// What the event loop needs to reconnect a dropped room
+struct Reconnect {
+    request: JoinRoomRequest,
+    policy: ReconnectPolicy,
+    closing: watch::Receiver<bool>,
+}
+
+struct EventLoop {
+    session_id: String,
+    room_name: String,
+    room: SharedRoom,
+    limiter: Arc<PublishRateLimiter>,
+    handler: Arc<dyn MicroserviceHandler>,
+    connections: RoomConnections,
+}
+
+impl EventLoop {
+    async fn run(
+        self,
+        mut event_rx: UnboundedReceiver<RoomEvent>,
+        mut reconnect: Option<Reconnect>,
+    ) {
+        debug!("Starting event loop for room {}", self.room_name);
+
+        loop {
+            let reason = self.dispatch(&mut event_rx).await;
+            let (Some(reconnect), Some(reason)) = (reconnect.as_mut(), reason) else {
+                break;
+            };
+            if is_expected_disconnect(reason) || *reconnect.closing.borrow() {
+                break;
+            }
+            match self.reconnect(reconnect).await {
+                Some(new_rx) => event_rx = new_rx,
+                None => break,
+            }
+        }
+
+        info!("Event loop ended for room {}", self.room_name);
+    }
+
+    /// Connect the room again, returning its events once connected
+    async fn reconnect(&self, reconnect: &mut Reconnect) -> Option<UnboundedReceiver<RoomEvent>> {
+        self.connections
+            .set(&self.session_id, RoomConnectionState::Reconnecting);
+        let request = &reconnect.request;
+        let connect = reconnect.policy.retry(|attempt| async move {
+            info!(
+                "Reconnecting to room {} (attempt {})",
+                request.room_name, attempt
+            );
+            Room::connect(
+                &request.livekit_url,
+                &request.access_token,
+                RoomOptions::default(),
+            )
+            .await
+            .map_err(|e| {
+                MicroserviceError::JoinRoomFailed(format!("LiveKit connection failed: {}", e))
+            })
+        });
+
+        let (room, event_rx) = tokio::select! {
+            result = connect => match result {
+                Ok(connected) => connected,
+                Err(e) => {
+                    error!("Giving up reconnecting to room {}: {}", self.room_name, e);
+                    self.connections
+                        .set(&self.session_id, RoomConnectionState::Disconnected);
+                    return None;
+                }
+            },
+            _ = reconnect.closing.wait_for(|closing| *closing) => return None,
+        };
+
+        // The session was closed while the connection was being made
+        if *reconnect.closing.borrow() {
+            let _ = room.close().await;
+            return None;
+        }
+
+        *self.room.write().unwrap() = Arc::new(room);
+        self.connections
+            .set(&self.session_id, RoomConnectionState::Connected);
+        info!("Reconnected to room {}", self.room_name);
+        Some(event_rx)
+    }
+
+    /// Dispatch events until the room disconnects, returning why, or `None` if the
+    /// event stream ended without a disconnect event
+    async fn dispatch(
+        &self,
+        event_rx: &mut UnboundedReceiver<RoomEvent>,
+    ) -> Option<DisconnectReason> {
+        let room_name = &self.room_name;
+
+        while let Some(event) = event_rx.recv().await {
+            self.connections.handle_event(&self.session_id, &event);
+
+            match event {
+                RoomEvent::DataReceived {
+                    payload,
+                    topic,
+                    participant,
+                    ..
+                } => {
+                    let ctx = DataContext {
+                        session_id: self.session_id.clone(),
+                        room_name: room_name.clone(),
+                        participant_identity: participant.map(|p| p.identity().to_string()),
+                        topic,
+                        payload,
+                        room: current_room(&self.room),
+                        limiter: self.limiter.clone(),
+                    };
+
+                    if let Err(e) = self.handler.on_data_received(ctx).await {
+                        error!("Failed to handle data message in room {}: {}", room_name, e);
+                    }
+                }
+                RoomEvent::ParticipantConnected(participant) => {
+                    info!(
+                        "Participant {} joined room {}",
+                        participant.identity(),
+                        room_name
+                    );
+                }
+                RoomEvent::ParticipantDisconnected(participant) => {
+                    info!(
+                        "Participant {} left room {}",
+                        participant.identity(),
+                        room_name
+                    );
+                }
+                RoomEvent::Disconnected { reason } => {
+                    warn!("Disconnected from room {}: {:?}", room_name, reason);
+                    return Some(reason);
+                }
+                _ => {
+                    debug!("Room {} event: {:?}", room_name, event);
+                }
+            }
+        }
+
+        None
+    }
+}
@@ -0,0 +1,228 @@
+//! Correlated request/response calls over a room's data channel
+//!
+//! [`RpcSession`] publishes protocol [`Request`]s and waits for the [`Response`] carrying
+//! the same id, so services no longer have to match answers to questions by hand the way
+//! the ping/pong example does.
+
+use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::oneshot;
+use tracing::debug;
+
+use crate::{
+    errors::{MicroserviceError, Result},
+    models::{Capabilities, JoinRoomRequest, LeaveRoomRequest},
+    protocol::{topics, Envelope, Request, Response},
+    room_session::{DataContext, RoomSession},
+    traits::MicroserviceHandler,
+};
+
+/// How long [`RpcSession::call`] waits for a response unless configured otherwise
+pub const DEFAULT_RPC_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Requests awaiting their response, keyed by request id
+#[derive(Debug, Default)]
+pub struct PendingRequests {
+    pending: Mutex<HashMap<String, oneshot::Sender<Response>>>,
+}
+
+impl PendingRequests {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a request with a fresh id and start tracking it
+    ///
+    /// The returned receiver completes once [`PendingRequests::resolve`] is called with
+    /// the matching response; pass it to [`PendingRequests::wait`].
+    pub fn start(
+        &self,
+        method: impl Into<String>,
+        params: Value,
+    ) -> (Request, oneshot::Receiver<Response>) {
+        let request = Request::new(uuid::Uuid::new_v4().to_string(), method, params);
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().unwrap().insert(request.id.clone(), tx);
+        (request, rx)
+    }
+
+    /// Hand a response to the request waiting for it
+    ///
+    /// Returns `false` if no request with that id is pending, e.g. it already timed out.
+    pub fn resolve(&self, response: Response) -> bool {
+        let waiter = self.pending.lock().unwrap().remove(&response.id);
+        match waiter {
+            Some(tx) => tx.send(response).is_ok(),
+            None => false,
+        }
+    }
+
+    /// Stop tracking a request, e.g. because publishing it failed
+    pub fn cancel(&self, id: &str) {
+        self.pending.lock().unwrap().remove(id);
+    }
+
+    /// Wait up to `timeout` for the response to request `id`
+    ///
+    /// An unanswered request is dropped from the map, so a late response is ignored.
+    pub async fn wait(
+        &self,
+        id: &str,
+        response: oneshot::Receiver<Response>,
+        timeout: Duration,
+    ) -> Result<Response> {
+        match tokio::time::timeout(timeout, response).await {
+            Ok(Ok(response)) => Ok(response),
+            Ok(Err(_)) => Err(MicroserviceError::RpcFailed {
+                id: id.to_string(),
+                message: "request was cancelled".to_string(),
+            }),
+            Err(_) => {
+                self.cancel(id);
+                Err(MicroserviceError::RpcTimeout(id.to_string()))
+            }
+        }
+    }
+
+    /// Number of requests still waiting for a response
+    pub fn len(&self) -> usize {
+        self.pending.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A [`RoomSession`] that can make request/response calls to other participants
+///
+/// Responses are picked out of the room's data messages before they reach the wrapped
+/// handler; every other message is dispatched to it as usual.
+#[derive(Debug)]
+pub struct RpcSession {
+    session: RoomSession,
+    pending: Arc<PendingRequests>,
+    timeout: Duration,
+}
+
+impl RpcSession {
+    /// Connect to the room in `request`, dispatching messages that are not responses to
+    /// `handler`
+    pub async fn connect(
+        request: &JoinRoomRequest,
+        handler: Arc<dyn MicroserviceHandler>,
+    ) -> Result<Self> {
+        let pending = Arc::new(PendingRequests::new());
+        let handler = Arc::new(ResponseRouter {
+            pending: pending.clone(),
+            inner: handler,
+        });
+        let session = RoomSession::connect(request, handler).await?;
+
+        Ok(Self {
+            session,
+            pending,
+            timeout: DEFAULT_RPC_TIMEOUT,
+        })
+    }
+
+    /// Change how long calls wait for their response
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// The underlying room session, e.g. to publish events
+    pub fn session(&self) -> &RoomSession {
+        &self.session
+    }
+
+    /// Requests still waiting for a response
+    pub fn pending(&self) -> &PendingRequests {
+        &self.pending
+    }
+
+    /// Send a request to every participant and return the result of the first response
+    pub async fn call(&self, method: &str, params: Value) -> Result<Value> {
+        self.call_to(&[], method, params).await
+    }
+
+    /// Send a request to the given participants and return the result of the first response
+    ///
+    /// Fails with [`MicroserviceError::RpcTimeout`] if nobody answers in time and with
+    /// [`MicroserviceError::RpcFailed`] if the response carries an error.
+    pub async fn call_to(
+        &self,
+        identities: &[String],
+        method: &str,
+        params: Value,
+    ) -> Result<Value> {
+        let (request, response) = self.pending.start(method, params);
+        let id = request.id.clone();
+        let payload = Envelope::from(request).encode()?;
+
+        if let Err(e) = self
+            .session
+            .publish_to(identities, Some(topics::REQUEST), payload, true)
+            .await
+        {
+            self.pending.cancel(&id);
+            return Err(e);
+        }
+
+        let response = self.pending.wait(&id, response, self.timeout).await?;
+        response
+            .into_result()
+            .map_err(|message| MicroserviceError::RpcFailed { id, message })
+    }
+
+    /// Disconnect from the room, failing calls still waiting for a response
+    pub async fn close(self) -> Result<()> {
+        self.pending.pending.lock().unwrap().clear();
+        self.session.close().await
+    }
+}
+
+/// Resolves pending requests from response messages and forwards everything else
+struct ResponseRouter {
+    pending: Arc<PendingRequests>,
+    inner: Arc<dyn MicroserviceHandler>,
+}
+
+#[async_trait]
+impl MicroserviceHandler for ResponseRouter {
+    async fn handle_join_room(&self, request: JoinRoomRequest) -> Result<()> {
+        self.inner.handle_join_room(request).await
+    }
+
+    async fn on_data_received(&self, ctx: DataContext) -> Result<()> {
+        if ctx.topic.as_deref() == Some(topics::RESPONSE) {
+            if let Ok(Envelope::Response(response)) = ctx.envelope() {
+                let id = response.id.clone();
+                if self.pending.resolve(response) {
+                    return Ok(());
+                }
+                debug!(
+                    "Response {} in room {} matches no pending request",
+                    id, ctx.room_name
+                );
+            }
+        }
+        self.inner.on_data_received(ctx).await
+    }
+
+    async fn handle_leave_room(&self, request: LeaveRoomRequest) -> Result<()> {
+        self.inner.handle_leave_room(request).await
+    }
+
+    async fn capabilities(&self, request: &JoinRoomRequest) -> Capabilities {
+        self.inner.capabilities(request).await
+    }
+
+    async fn health_check(&self) -> Result<()> {
+        self.inner.health_check().await
+    }
+}
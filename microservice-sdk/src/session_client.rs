@@ -0,0 +1,197 @@
+use futures::{stream, Stream, StreamExt};
+use reqwest::Client;
+use reqwest_eventsource::{Event, EventSource};
+use std::time::Duration;
+use tokio::time::Instant;
+use tracing::{debug, info, warn};
+
+use crate::{
+    client::error_from_response,
+    errors::{MicroserviceError, Result},
+    models::*,
+};
+
+/// Default interval between status polls in [`SessionClient::wait_until_ready`]
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Client for applications that create sessions on the Session Manager
+#[derive(Debug, Clone)]
+pub struct SessionClient {
+    session_manager_url: String,
+    http_client: Client,
+    poll_interval: Duration,
+}
+
+impl SessionClient {
+    /// Create a new session client with its own HTTP client
+    pub fn new(session_manager_url: String, request_timeout_secs: u64) -> Result<Self> {
+        let http_client = Client::builder()
+            .timeout(Duration::from_secs(request_timeout_secs))
+            .build()
+            .map_err(MicroserviceError::HttpError)?;
+
+        Ok(Self::with_http_client(session_manager_url, http_client))
+    }
+
+    /// Create a session client that shares an existing HTTP client
+    pub fn with_http_client(session_manager_url: String, http_client: Client) -> Self {
+        Self {
+            session_manager_url,
+            http_client,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+        }
+    }
+
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Create a new session
+    pub async fn create_session(
+        &self,
+        request: &CreateSessionRequest,
+    ) -> Result<CreateSessionResponse> {
+        let url = format!("{}/api/v1/create-session", self.session_manager_url);
+
+        info!("Creating session for user {}", request.user_identity);
+
+        let response = self.http_client.post(&url).json(request).send().await?;
+
+        if response.status().is_success() {
+            let create_response: CreateSessionResponse = response.json().await?;
+            info!(
+                "Created session {} ({:?})",
+                create_response.session_id, create_response.status
+            );
+            Ok(create_response)
+        } else {
+            Err(error_from_response(response).await)
+        }
+    }
+
+    /// Fetch the current status of a session
+    pub async fn get_session(&self, session_id: &str) -> Result<SessionStatusResponse> {
+        let url = format!(
+            "{}/api/v1/sessions/{}",
+            self.session_manager_url, session_id
+        );
+
+        let response = self.http_client.get(&url).send().await?;
+
+        if response.status().is_success() {
+            Ok(response.json().await?)
+        } else {
+            Err(error_from_response(response).await)
+        }
+    }
+
+    /// Poll the session until it is `Ready` (or already `Active`)
+    ///
+    /// Fails with [`MicroserviceError::Timeout`] if the session is not ready within `timeout`,
+    /// and with [`MicroserviceError::SessionEnded`] if it terminates first.
+    pub async fn wait_until_ready(
+        &self,
+        session_id: &str,
+        timeout: Duration,
+    ) -> Result<SessionStatusResponse> {
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            let session = self.get_session(session_id).await?;
+            match session.status {
+                SessionStatus::Ready | SessionStatus::Active => return Ok(session),
+                SessionStatus::Terminating | SessionStatus::Terminated => {
+                    return Err(MicroserviceError::SessionEnded(session_id.to_string()))
+                }
+                _ => {
+                    debug!(
+                        "Session {} is {:?}, pending services: {:?}",
+                        session_id, session.status, session.pending_services
+                    );
+                }
+            }
+
+            if Instant::now() + self.poll_interval > deadline {
+                return Err(MicroserviceError::Timeout);
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+
+    /// Subscribe to a session's events over SSE
+    ///
+    /// Dropped connections are re-established automatically, sending `Last-Event-ID` when
+    /// the server provides event ids. The stream ends when the session manager closes the
+    /// session's event stream (including its final `close` event on shutdown or when the
+    /// subscriber falls too far behind), or after yielding an error if the subscription is rejected
+    /// (e.g. 404 for an unknown session). The HTTP client's request timeout also bounds each
+    /// connection, after which the stream reconnects.
+    pub fn subscribe_events(
+        &self,
+        session_id: &str,
+    ) -> Result<impl Stream<Item = Result<SessionEvent>> + Send + 'static> {
+        let url = format!(
+            "{}/sessions/{}/events",
+            self.session_manager_url, session_id
+        );
+
+        let source = EventSource::new(self.http_client.get(&url)).map_err(|e| {
+            MicroserviceError::EventStream(format!("Failed to create event source: {}", e))
+        })?;
+
+        Ok(stream::unfold(source, |mut source| async move {
+            loop {
+                let item = match source.next().await? {
+                    Ok(Event::Open) => {
+                        debug!("Event stream connected");
+                        continue;
+                    }
+                    Ok(Event::Message(message)) if message.event == SESSION_SNAPSHOT_EVENT => {
+                        debug!("Event stream sent session snapshot");
+                        continue;
+                    }
+                    Ok(Event::Message(message)) if message.event == "lagged" => {
+                        warn!(
+                            "Event stream lagged, some events were skipped: {}",
+                            message.data
+                        );
+                        continue;
+                    }
+                    Ok(Event::Message(message)) if message.event == "close" => {
+                        info!(
+                            "Event stream closed by the session manager: {}",
+                            message.data
+                        );
+                        source.close();
+                        return None;
+                    }
+                    Ok(Event::Message(message)) => {
+                        serde_json::from_str(&message.data).map_err(MicroserviceError::JsonError)
+                    }
+                    Err(reqwest_eventsource::Error::StreamEnded) => {
+                        source.close();
+                        return None;
+                    }
+                    Err(reqwest_eventsource::Error::InvalidStatusCode(_, response)) => {
+                        Err(error_from_response(response).await)
+                    }
+                    Err(
+                        e @ (reqwest_eventsource::Error::Transport(_)
+                        | reqwest_eventsource::Error::Utf8(_)
+                        | reqwest_eventsource::Error::Parser(_)),
+                    ) => {
+                        // The event source reconnects by itself
+                        warn!("Event stream interrupted, reconnecting: {}", e);
+                        continue;
+                    }
+                    Err(e) => {
+                        source.close();
+                        Err(MicroserviceError::EventStream(e.to_string()))
+                    }
+                };
+                return Some((item, source));
+            }
+        }))
+    }
+}
@@ -1,4 +1,8 @@
-use crate::{errors::Result, models::JoinRoomRequest};
+use crate::{
+    errors::{MicroserviceError, Result},
+    models::{Capabilities, JoinRoomRequest, LeaveRoomRequest},
+    room_session::DataContext,
+};
 use async_trait::async_trait;
 
 /// Trait that microservices must implement to handle session manager requests
@@ -10,16 +14,60 @@ pub trait MicroserviceHandler: Send + Sync {
     /// 1. Connect to the LiveKit room using the provided access token
     /// 2. Set up any necessary resources
     /// 3. Return Ok(()) when ready, or Err() if failed
-    async fn handle_join_room(&self, request: JoinRoomRequest) -> Result<()>;
+    ///
+    /// Return only once connected: the runner answers the join request as soon as this
+    /// returns `Ok`, and the session manager takes that answer as proof the service joined,
+    /// in case its room observer misses the service connecting.
+    ///
+    /// If readiness takes longer than joining (e.g. loading a model), call
+    /// [`SessionManagerClient::notify_ready`](crate::SessionManagerClient::notify_ready)
+    /// once the service can actually handle the session.
+    ///
+    /// Services that only need to react to data messages can leave this unimplemented,
+    /// implement [`on_data_received`](Self::on_data_received) instead and start their runner
+    /// with [`MicroserviceRunner::with_room_sessions`](crate::MicroserviceRunner::with_room_sessions).
+    async fn handle_join_room(&self, request: JoinRoomRequest) -> Result<()> {
+        Err(MicroserviceError::JoinRoomFailed(format!(
+            "handle_join_room is not implemented (session {})",
+            request.session_id
+        )))
+    }
+
+    /// Called for every data message received in a room joined through a
+    /// [`RoomSession`](crate::RoomSession)
+    ///
+    /// This is optional - errors are logged and do not end the room's event loop
+    async fn on_data_received(&self, ctx: DataContext) -> Result<()> {
+        tracing::debug!(
+            "Ignoring data message in room {} from {:?}",
+            ctx.room_name,
+            ctx.participant_identity
+        );
+        Ok(())
+    }
 
     /// Called when the microservice should clean up and leave the room
     ///
-    /// This is optional - microservices can implement cleanup logic here
-    async fn handle_leave_room(&self, session_id: &str, room_name: &str) -> Result<()> {
-        tracing::info!("Leaving room {} for session {}", room_name, session_id);
+    /// This is optional - microservices can implement cleanup logic here, e.g. flushing
+    /// state only when `request.reason` is [`LeaveReason::ClientLeft`](crate::LeaveReason::ClientLeft).
+    async fn handle_leave_room(&self, request: LeaveRoomRequest) -> Result<()> {
+        tracing::info!(
+            "Leaving room {} for session {} ({:?})",
+            request.room_name,
+            request.session_id,
+            request.reason
+        );
         Ok(())
     }
 
+    /// Capabilities to report to the session manager after joining a room
+    ///
+    /// This is optional - e.g. supported data topics, codecs or the model version. The
+    /// session manager exposes them in the session status so clients can adapt.
+    async fn capabilities(&self, _request: &JoinRoomRequest) -> Capabilities {
+        Capabilities::new()
+    }
+
     /// Called to check if the microservice is healthy
     ///
     /// This is optional - microservices can implement health check logic here
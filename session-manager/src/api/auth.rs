@@ -0,0 +1,124 @@
+use crate::{
+    api::{handlers::handle_error, handlers::AppState, models::ErrorResponse},
+    domain::Session,
+    utils::errors::{Result as AppResult, SessionManagerError},
+};
+use axum::{
+    extract::FromRequestParts,
+    http::{header, request::Parts, StatusCode},
+    response::Json,
+};
+
+/// 管理员认证提取器：要求 `Authorization: Bearer <auth.admin_token>`
+///
+/// 未配置 `admin_token` 时所有管理接口均拒绝访问。
+#[derive(Debug, Clone, Copy)]
+pub struct AdminAuth;
+
+impl FromRequestParts<AppState> for AdminAuth {
+    type Rejection = (StatusCode, Json<ErrorResponse>);
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let Some(expected) = state.config.auth.admin_token.as_deref() else {
+            return Err(handle_error(SessionManagerError::Unauthorized(
+                "Admin API is disabled".to_string(),
+            )));
+        };
+
+        match bearer_token(parts) {
+            Some(token) if constant_time_eq(token.as_bytes(), expected.as_bytes()) => Ok(AdminAuth),
+            _ => Err(handle_error(SessionManagerError::Unauthorized(
+                "Missing or invalid admin token".to_string(),
+            ))),
+        }
+    }
+}
+
+/// 会话接口的调用方身份
+///
+/// 提供管理员令牌时为 `Admin`；配置了 `auth.api_tokens` 时必须提供其中之一，
+/// 否则返回 401；未配置时调用方认证关闭，一律为 `Anonymous`。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Caller {
+    Admin,
+    Principal(String),
+    Anonymous,
+}
+
+impl Caller {
+    /// 创建会话时记录为所有者的主体名称
+    pub fn principal(&self) -> Option<&str> {
+        match self {
+            Caller::Principal(principal) => Some(principal),
+            Caller::Admin | Caller::Anonymous => None,
+        }
+    }
+
+    /// 校验调用方能否访问 `session`：管理员及未记录所有者的会话不受限制
+    pub fn authorize(&self, session: &Session) -> AppResult<()> {
+        self.authorize_owner(&session.id, session.owner.as_deref())
+    }
+
+    /// 按会话所有者校验访问权限，用于会话已从存储删除的场景
+    pub fn authorize_owner(&self, session_id: &str, owner: Option<&str>) -> AppResult<()> {
+        match (self, owner) {
+            (Caller::Admin, _) | (_, None) => Ok(()),
+            (Caller::Principal(principal), Some(owner)) if principal == owner => Ok(()),
+            _ => Err(SessionManagerError::Forbidden(format!(
+                "Session {} belongs to another caller",
+                session_id
+            ))),
+        }
+    }
+}
+
+impl FromRequestParts<AppState> for Caller {
+    type Rejection = (StatusCode, Json<ErrorResponse>);
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let auth = &state.config.auth;
+        let provided = bearer_token(parts);
+
+        if let (Some(token), Some(admin_token)) = (provided, auth.admin_token.as_deref()) {
+            if constant_time_eq(token.as_bytes(), admin_token.as_bytes()) {
+                return Ok(Caller::Admin);
+            }
+        }
+
+        if auth.api_tokens.is_empty() {
+            return Ok(Caller::Anonymous);
+        }
+
+        provided
+            .and_then(|token| {
+                auth.api_tokens
+                    .iter()
+                    .find(|(expected, _)| constant_time_eq(token.as_bytes(), expected.as_bytes()))
+            })
+            .map(|(_, principal)| Caller::Principal(principal.clone()))
+            .ok_or_else(|| {
+                handle_error(SessionManagerError::Unauthorized(
+                    "Missing or invalid API token".to_string(),
+                ))
+            })
+    }
+}
+
+fn bearer_token(parts: &Parts) -> Option<&str> {
+    parts
+        .headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+}
+
+// 避免通过比较耗时泄露令牌内容
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
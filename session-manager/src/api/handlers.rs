@@ -1,12 +1,28 @@
 use crate::{
-    api::models::*,
-    domain::MicroserviceInfo,
-    services::{MicroserviceRegistry, SessionService},
-    utils::errors::SessionManagerError,
+    api::{
+        auth::{AdminAuth, Caller},
+        models::*,
+    },
+    domain::{LeaveReason, MicroserviceInfo},
+    services::{
+        MicroserviceRegistry, RateLimiter, SessionService, HEALTH_PROBE_CONCURRENCY,
+        HEALTH_PROBE_TIMEOUT,
+    },
+    storage::{jsonl, SessionStorage},
+    utils::{errors::SessionManagerError, logging::LogLevelHandle, validation},
+};
+use axum::{
+    body::{Body, Bytes},
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
 };
-use axum::{extract::State, http::StatusCode, response::Json};
 use chrono::Utc;
-use std::sync::Arc;
+use futures::TryStreamExt;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
 
 #[derive(Clone)]
 pub struct AppState {
@@ -14,6 +30,13 @@ pub struct AppState {
     pub microservice_registry: Arc<MicroserviceRegistry>,
     pub config: crate::config::AppConfig,
     pub event_bus: crate::events::EventBus,
+    pub rate_limiter: Arc<RateLimiter>,
+    pub storage: Arc<dyn SessionStorage>,
+    pub log_level: Option<LogLevelHandle>,
+    /// 服务器关闭信号，事件流据此提前结束
+    pub shutdown: tokio::sync::watch::Receiver<bool>,
+    /// 排空模式：为 true 时拒绝创建新会话，已有会话及其事件流不受影响
+    pub draining: Arc<AtomicBool>,
 }
 
 // 健康检查
@@ -25,16 +48,58 @@ pub async fn health_check() -> Json<HealthCheckResponse> {
     })
 }
 
+// 就绪检查 - 主动探测依赖，任一依赖不可用时返回 503
+pub async fn readiness_check(
+    State(state): State<AppState>,
+) -> (StatusCode, Json<ReadinessResponse>) {
+    let dependencies = state.session_service.check_readiness().await;
+    let ready = dependencies.iter().all(|dependency| dependency.healthy);
+
+    // 排空中的实例报告未就绪，负载均衡器据此不再转发新会话
+    let (status_code, status) = if state.draining.load(Ordering::Relaxed) {
+        (StatusCode::SERVICE_UNAVAILABLE, "draining")
+    } else if ready {
+        (StatusCode::OK, "ready")
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, "not_ready")
+    };
+
+    (
+        status_code,
+        Json(ReadinessResponse {
+            status: status.to_string(),
+            timestamp: Utc::now(),
+            dependencies,
+        }),
+    )
+}
+
+// 微服务健康汇总 - 并发探测每个已注册微服务的 /health 端点
+pub async fn microservices_health(
+    State(state): State<AppState>,
+) -> Json<MicroserviceHealthResponse> {
+    let services = state
+        .microservice_registry
+        .check_health(HEALTH_PROBE_TIMEOUT, HEALTH_PROBE_CONCURRENCY)
+        .await;
+
+    Json(MicroserviceHealthResponse {
+        timestamp: Utc::now(),
+        services,
+    })
+}
+
 // 注册微服务
 pub async fn register_microservice(
     State(state): State<AppState>,
     Json(request): Json<RegisterMicroserviceRequest>,
 ) -> Result<Json<RegisterMicroserviceResponse>, (StatusCode, Json<ErrorResponse>)> {
-    let microservice = MicroserviceInfo::new(
-        request.service_id.clone(),
-        request.endpoint,
-        request.metadata.unwrap_or_default(),
-    );
+    let metadata = request.metadata.unwrap_or_default();
+    validation::validate_metadata("metadata", &metadata, &state.config.metadata_limits)
+        .map_err(handle_error)?;
+
+    let microservice =
+        MicroserviceInfo::new(request.service_id.clone(), request.endpoint, metadata);
 
     match state
         .microservice_registry
@@ -52,17 +117,35 @@ pub async fn register_microservice(
 
 // 创建会话 - 简单同步创建，返回会话信息
 pub async fn create_session(
+    caller: Caller,
     State(state): State<AppState>,
     Json(request): Json<CreateSessionRequest>,
-) -> Result<Json<CreateSessionResponse>, (StatusCode, Json<ErrorResponse>)> {
+) -> Result<Json<CreateSessionResponse>, Response> {
+    // 排空中不接受新会话，返回 503 让负载均衡器转发到其他实例
+    if state.draining.load(Ordering::Relaxed) {
+        return Err(error_response(SessionManagerError::Draining));
+    }
+
+    // 按用户身份限流
+    if let Err(retry_after) = state.rate_limiter.check(&request.user_identity) {
+        tracing::warn!(
+            "Rate limit exceeded for user {}, retry after {:?}",
+            request.user_identity,
+            retry_after
+        );
+        return Err(error_response(SessionManagerError::RateLimited {
+            retry_after_secs: retry_after.as_secs_f64().ceil().max(1.0) as u64,
+        }));
+    }
+
+    // 限制 metadata 大小，避免超大数据写入存储与令牌
+    if let Some(metadata) = &request.metadata {
+        validation::validate_metadata("metadata", metadata, &state.config.metadata_limits)
+            .map_err(error_response)?;
+    }
+
     // 转换请求类型
-    let session_request = crate::services::session_service::CreateSessionRequest {
-        user_identity: request.user_identity.clone(),
-        user_name: request.user_name,
-        room_name: request.room_name,
-        metadata: request.metadata,
-        required_services: request.required_services,
-    };
+    let session_request = service_request(request, &caller);
 
     // 创建会话
     match state.session_service.create_session(session_request).await {
@@ -80,23 +163,415 @@ pub async fn create_session(
         }
         Err(e) => {
             tracing::error!("Failed to create session: {}", e);
-            Err(handle_error(e))
+            Err(error_response(e))
         }
     }
 }
 
+// 将 API 请求转换为会话服务请求，记录调用方为会话所有者
+fn service_request(
+    request: CreateSessionRequest,
+    caller: &Caller,
+) -> crate::services::session_service::CreateSessionRequest {
+    crate::services::session_service::CreateSessionRequest {
+        user_identity: request.user_identity,
+        user_name: request.user_name,
+        room_name: request.room_name,
+        metadata: request.metadata,
+        required_services: request.required_services,
+        required_service_types: request.required_service_types,
+        reuse_existing: request.reuse_existing,
+        observe: request.observe,
+        max_lifetime_secs: request.max_lifetime_secs,
+        min_ready_services: request.min_ready_services,
+        owner: caller.principal().map(str::to_string),
+    }
+}
+
+// 试运行会话创建：校验输入并检查所需微服务是否可用，不创建房间、不签发令牌、不保存会话
+pub async fn validate_session(
+    caller: Caller,
+    State(state): State<AppState>,
+    Json(request): Json<CreateSessionRequest>,
+) -> Result<Json<ValidateSessionResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let validation = state
+        .session_service
+        .validate_session(&service_request(request, &caller))
+        .await
+        .map_err(|e| {
+            tracing::debug!("Session validation rejected: {}", e);
+            handle_error(e)
+        })?;
+
+    Ok(Json(ValidateSessionResponse {
+        valid: validation.missing_services.is_empty(),
+        available_services: validation.available_services,
+        missing_services: validation.missing_services,
+    }))
+}
+
+// 读取会话并校验调用方是否有权访问
+pub(crate) async fn authorized_session(
+    state: &AppState,
+    caller: &Caller,
+    session_id: String,
+) -> Result<crate::domain::Session, (StatusCode, Json<ErrorResponse>)> {
+    let session = state
+        .session_service
+        .get_session(&session_id)
+        .await
+        .map_err(handle_error)?
+        .ok_or_else(|| handle_error(SessionManagerError::SessionNotFound { session_id }))?;
+
+    caller.authorize(&session).map_err(|e| {
+        tracing::warn!("Denied access to session {}: {}", session.id, e);
+        handle_error(e)
+    })?;
+    Ok(session)
+}
+
+// 查询会话状态
+pub async fn get_session_status(
+    caller: Caller,
+    State(state): State<AppState>,
+    Path(session_id): Path<String>,
+) -> Result<Json<SessionStatusResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let session = authorized_session(&state, &caller, session_id).await?;
+
+    Ok(Json(SessionStatusResponse::from(session)))
+}
+
+// 按发布顺序查询会话的事件历史；会话从存储删除后，历史在 `streams.history_retention_secs` 内仍可查询
+pub async fn get_session_event_history(
+    caller: Caller,
+    State(state): State<AppState>,
+    Path(session_id): Path<String>,
+) -> Result<Json<SessionEventHistoryResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let owner = match state
+        .session_service
+        .get_session(&session_id)
+        .await
+        .map_err(handle_error)?
+    {
+        Some(session) => session.owner,
+        None => {
+            state
+                .storage
+                .get_ended_session(&session_id)
+                .await
+                .map_err(handle_error)?
+                .ok_or_else(|| {
+                    handle_error(SessionManagerError::SessionNotFound {
+                        session_id: session_id.clone(),
+                    })
+                })?
+                .owner
+        }
+    };
+    caller
+        .authorize_owner(&session_id, owner.as_deref())
+        .map_err(|e| {
+            tracing::warn!("Denied access to history of session {}: {}", session_id, e);
+            handle_error(e)
+        })?;
+
+    let events = state.storage.get_events(&session_id).await.map_err(|e| {
+        tracing::error!(
+            "Failed to read event history of session {}: {}",
+            session_id,
+            e
+        );
+        handle_error(e)
+    })?;
+
+    Ok(Json(SessionEventHistoryResponse { session_id, events }))
+}
+
+// 合并更新会话元数据
+pub async fn update_session_metadata(
+    caller: Caller,
+    State(state): State<AppState>,
+    Path(session_id): Path<String>,
+    Json(patch): Json<UpdateSessionMetadataRequest>,
+) -> Result<Json<UpdateSessionMetadataResponse>, (StatusCode, Json<ErrorResponse>)> {
+    authorized_session(&state, &caller, session_id.clone()).await?;
+
+    let session = state
+        .session_service
+        .update_metadata(&session_id, patch)
+        .await
+        .map_err(|e| {
+            tracing::warn!("Failed to update metadata of session {}: {}", session_id, e);
+            handle_error(e)
+        })?;
+
+    Ok(Json(UpdateSessionMetadataResponse {
+        session_id: session.id,
+        metadata: session.metadata,
+        updated_at: session.updated_at,
+    }))
+}
+
+// 为会话签发新的客户端令牌，供会话超过令牌有效期后重连
+pub async fn refresh_token(
+    caller: Caller,
+    State(state): State<AppState>,
+    Path(session_id): Path<String>,
+) -> Result<Json<RefreshTokenResponse>, (StatusCode, Json<ErrorResponse>)> {
+    authorized_session(&state, &caller, session_id.clone()).await?;
+
+    let (session, access_token) = state
+        .session_service
+        .refresh_client_token(&session_id)
+        .await
+        .map_err(|e| {
+            tracing::warn!("Failed to refresh token of session {}: {}", session_id, e);
+            handle_error(e)
+        })?;
+
+    Ok(Json(RefreshTokenResponse {
+        session_id: session.id,
+        access_token,
+        livekit_url: state.config.livekit.server_url.clone(),
+    }))
+}
+
+// 删除会话时告知微服务的离开原因：管理员删除与会话所有者删除区分开
+fn leave_reason(caller: &Caller) -> LeaveReason {
+    match caller {
+        Caller::Admin => LeaveReason::AdminTerminate,
+        _ => LeaveReason::SessionDeleted,
+    }
+}
+
+// 删除会话：立即拆除房间并终止会话
+pub async fn delete_session(
+    caller: Caller,
+    State(state): State<AppState>,
+    Path(session_id): Path<String>,
+) -> Result<Json<DeleteSessionResponse>, (StatusCode, Json<ErrorResponse>)> {
+    authorized_session(&state, &caller, session_id.clone()).await?;
+
+    let session = state
+        .session_service
+        .terminate_session(&session_id, leave_reason(&caller))
+        .await
+        .map_err(|e| {
+            tracing::warn!("Failed to delete session {}: {}", session_id, e);
+            handle_error(e)
+        })?;
+
+    tracing::info!("Session {} deleted", session_id);
+    Ok(Json(DeleteSessionResponse {
+        session_id: session.id,
+        status: session.status,
+    }))
+}
+
+// 客户端主动离开会话；若无其他参与者则开始拆除会话
+pub async fn leave_session(
+    caller: Caller,
+    State(state): State<AppState>,
+    Path(session_id): Path<String>,
+    Json(request): Json<LeaveSessionRequest>,
+) -> Result<Json<LeaveSessionResponse>, (StatusCode, Json<ErrorResponse>)> {
+    authorized_session(&state, &caller, session_id.clone()).await?;
+
+    let session = state
+        .session_service
+        .leave_session(&session_id, &request.user_identity)
+        .await
+        .map_err(|e| {
+            tracing::warn!("Failed to leave session {}: {}", session_id, e);
+            handle_error(e)
+        })?;
+
+    Ok(Json(LeaveSessionResponse {
+        session_id: session.id,
+        status: session.status,
+    }))
+}
+
+// 微服务加载完成后通知就绪，达到就绪法定数量时会话进入 Ready
+pub async fn service_ready(
+    State(state): State<AppState>,
+    Path(session_id): Path<String>,
+    Json(request): Json<ServiceReadyRequest>,
+) -> Result<Json<ServiceReadyResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let session = state
+        .session_service
+        .mark_service_ready(&session_id, &request.service_id)
+        .await
+        .map_err(|e| {
+            tracing::warn!(
+                "Failed to mark service {} ready in session {}: {}",
+                request.service_id,
+                session_id,
+                e
+            );
+            handle_error(e)
+        })?;
+
+    Ok(Json(ServiceReadyResponse {
+        success: true,
+        message: format!("Service {} is ready", request.service_id),
+        all_services_ready: session.get_pending_services().is_empty(),
+    }))
+}
+
+const DEFAULT_SESSION_PAGE_SIZE: usize = 50;
+const MAX_SESSION_PAGE_SIZE: usize = 200;
+
+// 分页列出会话，支持按状态过滤；认证主体只能看到自己创建的会话
+pub async fn list_sessions(
+    caller: Caller,
+    State(state): State<AppState>,
+    Query(query): Query<ListSessionsQuery>,
+) -> Result<Json<ListSessionsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_SESSION_PAGE_SIZE)
+        .clamp(1, MAX_SESSION_PAGE_SIZE);
+
+    let page = state
+        .session_service
+        .list_sessions(
+            query.status,
+            caller.principal(),
+            limit,
+            query.cursor.as_deref(),
+        )
+        .await
+        .map_err(handle_error)?;
+
+    let sessions = page
+        .sessions
+        .into_iter()
+        .map(|session| SessionSummary {
+            ready_services: session.get_ready_services().len(),
+            pending_services: session.get_pending_services().len(),
+            session_id: session.id,
+            room_name: session.room_name,
+            status: session.status,
+            created_at: session.created_at,
+        })
+        .collect();
+
+    Ok(Json(ListSessionsResponse {
+        sessions,
+        next_cursor: page.next_cursor,
+    }))
+}
+
+// 调试：以 JSON Lines 流式导出所有会话（需管理员令牌）
+pub async fn export_sessions(_admin: AdminAuth, State(state): State<AppState>) -> Response {
+    tracing::info!("Exporting sessions for debugging");
+
+    let stream = jsonl::export_sessions(state.storage.clone())
+        .map_ok(Bytes::from)
+        .inspect_err(|e| tracing::error!("Session export aborted: {}", e));
+
+    (
+        [
+            (header::CONTENT_TYPE, "application/x-ndjson"),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"sessions.jsonl\"",
+            ),
+        ],
+        Body::from_stream(stream),
+    )
+        .into_response()
+}
+
+// 管理：运行时切换日志过滤指令（需管理员令牌）
+pub async fn set_log_level(
+    _admin: AdminAuth,
+    State(state): State<AppState>,
+    Json(request): Json<SetLogLevelRequest>,
+) -> Result<Json<SetLogLevelResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let log_level = state.log_level.as_ref().ok_or_else(|| {
+        handle_error(SessionManagerError::Configuration(
+            "Log level reload is not enabled".to_string(),
+        ))
+    })?;
+
+    let previous = log_level.set(&request.directive).map_err(handle_error)?;
+    tracing::warn!(
+        "Log filter changed from '{}' to '{}'",
+        previous,
+        request.directive
+    );
+
+    Ok(Json(SetLogLevelResponse {
+        previous,
+        current: request.directive,
+    }))
+}
+
+// 管理：维护前终止所有会话并删除对应房间（需管理员令牌），可重复调用
+pub async fn terminate_all_sessions(
+    _admin: AdminAuth,
+    State(state): State<AppState>,
+) -> Result<Json<TerminateAllResponse>, (StatusCode, Json<ErrorResponse>)> {
+    tracing::warn!("Terminating all sessions");
+
+    let terminated = state
+        .session_service
+        .terminate_all_sessions()
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to terminate all sessions: {}", e);
+            handle_error(e)
+        })?;
+
+    Ok(Json(TerminateAllResponse { terminated }))
+}
+
+// 管理：进入排空模式，拒绝新会话，已有会话照常运行直至结束（需管理员令牌）
+pub async fn drain(_admin: AdminAuth, State(state): State<AppState>) -> Json<DrainResponse> {
+    if !state.draining.swap(true, Ordering::Relaxed) {
+        tracing::warn!("Draining: new sessions are rejected until undrained");
+    }
+    Json(DrainResponse { draining: true })
+}
+
+// 管理：退出排空模式，恢复接受新会话（需管理员令牌）
+pub async fn undrain(_admin: AdminAuth, State(state): State<AppState>) -> Json<DrainResponse> {
+    if state.draining.swap(false, Ordering::Relaxed) {
+        tracing::warn!("Undrained: accepting new sessions again");
+    }
+    Json(DrainResponse { draining: false })
+}
+
 // 错误处理辅助函数
-fn handle_error(error: SessionManagerError) -> (StatusCode, Json<ErrorResponse>) {
+pub(crate) fn handle_error(error: SessionManagerError) -> (StatusCode, Json<ErrorResponse>) {
     let (status_code, error_type) = match &error {
         SessionManagerError::SessionNotFound { .. } => (StatusCode::NOT_FOUND, "SessionNotFound"),
         SessionManagerError::InvalidRequest(_) => (StatusCode::BAD_REQUEST, "InvalidRequest"),
         SessionManagerError::MicroserviceJoinTimeout => (StatusCode::REQUEST_TIMEOUT, "Timeout"),
+        SessionManagerError::Unauthorized(_) => (StatusCode::UNAUTHORIZED, "Unauthorized"),
+        SessionManagerError::Forbidden(_) => (StatusCode::FORBIDDEN, "Forbidden"),
+        SessionManagerError::RoomFull { .. } => (StatusCode::CONFLICT, "RoomFull"),
+        SessionManagerError::InvalidStatusTransition { .. } => {
+            (StatusCode::CONFLICT, "InvalidStatusTransition")
+        }
+        SessionManagerError::RateLimited { .. } => (StatusCode::TOO_MANY_REQUESTS, "RateLimited"),
+        SessionManagerError::AtCapacity { .. } => (StatusCode::SERVICE_UNAVAILABLE, "AtCapacity"),
+        SessionManagerError::Draining => (StatusCode::SERVICE_UNAVAILABLE, "Draining"),
         SessionManagerError::Configuration(_) => {
             (StatusCode::INTERNAL_SERVER_ERROR, "Configuration")
         }
+        SessionManagerError::LiveKitConnect { .. } => (StatusCode::BAD_GATEWAY, "LiveKitConnect"),
         _ => (StatusCode::INTERNAL_SERVER_ERROR, "InternalError"),
     };
 
+    // 服务端错误记录完整的原因链，便于定位底层故障
+    if status_code.is_server_error() {
+        let causes: Vec<String> = error.causes().map(ToString::to_string).collect();
+        tracing::error!(error = %error, causes = ?causes, "Request failed");
+    }
+
     (
         status_code,
         Json(ErrorResponse {
@@ -106,3 +581,19 @@ fn handle_error(error: SessionManagerError) -> (StatusCode, Json<ErrorResponse>)
         }),
     )
 }
+
+// 构建完整错误响应，附带需要的响应头（如 Retry-After）
+pub(crate) fn error_response(error: SessionManagerError) -> Response {
+    let retry_after = match &error {
+        SessionManagerError::RateLimited { retry_after_secs } => Some(*retry_after_secs),
+        _ => None,
+    };
+
+    let mut response = handle_error(error).into_response();
+    if let Some(secs) = retry_after {
+        response
+            .headers_mut()
+            .insert(header::RETRY_AFTER, secs.into());
+    }
+    response
+}
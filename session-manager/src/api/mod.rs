@@ -1,5 +1,7 @@
+pub mod auth;
 pub mod handlers;
 pub mod models;
+pub mod streams;
 
 pub use handlers::*;
 pub use models::*;
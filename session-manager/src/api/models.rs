@@ -1,22 +1,13 @@
-use crate::domain::SessionStatus;
+use crate::domain::{Capabilities, Session, SessionStatus};
+use crate::events::RecordedEvent;
+use crate::services::{DependencyStatus, ServiceHealth};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use session_protocol::RoomStats;
 use std::collections::HashMap;
 
-// 微服务注册 API
-#[derive(Debug, Deserialize)]
-pub struct RegisterMicroserviceRequest {
-    pub service_id: String,
-    pub endpoint: String,
-    pub metadata: Option<HashMap<String, String>>,
-}
-
-#[derive(Debug, Serialize)]
-pub struct RegisterMicroserviceResponse {
-    pub success: bool,
-    pub service_id: String,
-    pub message: String,
-}
+// 微服务注册 API（与 SDK 共享）
+pub use session_protocol::{RegisterMicroserviceRequest, RegisterMicroserviceResponse};
 
 // 会话创建 API
 #[derive(Debug, Deserialize)]
@@ -26,6 +17,25 @@ pub struct CreateSessionRequest {
     pub room_name: Option<String>,
     pub metadata: Option<HashMap<String, String>>,
     pub required_services: Option<Vec<String>>,
+    /// 需要的微服务类型，每项由注册了该类型（metadata 中的 `type`）的任一可用实例承担
+    #[serde(default)]
+    pub required_service_types: Option<Vec<String>>,
+    /// 若该用户已有 Ready/Active 会话则直接返回（附新令牌），不再新建
+    #[serde(default)]
+    pub reuse_existing: bool,
+    /// 会话管理器是否连接房间观察微服务加入（默认 true）；为 false 时仅依据加入通知确认和 service-ready 调用判定就绪
+    #[serde(default = "default_observe")]
+    pub observe: bool,
+    /// 会话最长存活时间（秒），覆盖服务端默认值；到期后无论是否活跃都会被终止
+    #[serde(default)]
+    pub max_lifetime_secs: Option<u64>,
+    /// 就绪的微服务达到该数量即视为会话就绪，默认要求全部就绪
+    #[serde(default)]
+    pub min_ready_services: Option<usize>,
+}
+
+fn default_observe() -> bool {
+    true
 }
 
 #[derive(Debug, Serialize)]
@@ -37,6 +47,15 @@ pub struct CreateSessionResponse {
     pub status: SessionStatus,
 }
 
+// 会话创建试运行 API：请求体与创建会话相同
+#[derive(Debug, Serialize)]
+pub struct ValidateSessionResponse {
+    /// 所需微服务是否全部可用
+    pub valid: bool,
+    pub available_services: Vec<String>,
+    pub missing_services: Vec<String>,
+}
+
 // 会话状态查询 API
 #[derive(Debug, Serialize)]
 pub struct SessionStatusResponse {
@@ -45,20 +64,128 @@ pub struct SessionStatusResponse {
     pub status: SessionStatus,
     pub ready_services: Vec<String>,
     pub pending_services: Vec<String>,
+    /// 各微服务加入房间时声明的能力，按服务 ID 索引
+    pub service_capabilities: HashMap<String, Capabilities>,
+    pub metadata: HashMap<String, String>,
     pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// 观察者最近一次采集的房间统计（参与者、轨道、连接质量）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub room_stats: Option<RoomStats>,
 }
 
-// 服务就绪通知 API
+impl From<Session> for SessionStatusResponse {
+    fn from(session: Session) -> Self {
+        Self {
+            session_id: session.id.clone(),
+            room_name: session.room_name.clone(),
+            ready_services: session.get_ready_services(),
+            pending_services: session.get_pending_services(),
+            service_capabilities: session.service_capabilities(),
+            status: session.status,
+            metadata: session.metadata,
+            created_at: session.created_at,
+            updated_at: session.updated_at,
+            room_stats: session.room_stats,
+        }
+    }
+}
+
+// 会话事件历史查询 API
+#[derive(Debug, Serialize)]
+pub struct SessionEventHistoryResponse {
+    pub session_id: String,
+    /// 按发布顺序排列的事件
+    pub events: Vec<RecordedEvent>,
+}
+
+// 会话元数据更新 API：值为 null 表示删除该键
+pub type UpdateSessionMetadataRequest = HashMap<String, Option<String>>;
+
+#[derive(Debug, Serialize)]
+pub struct UpdateSessionMetadataResponse {
+    pub session_id: String,
+    pub metadata: HashMap<String, String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// 客户端令牌刷新 API
+#[derive(Debug, Serialize)]
+pub struct RefreshTokenResponse {
+    pub session_id: String,
+    pub access_token: String,
+    pub livekit_url: String,
+}
+
+// 客户端离开会话 API
 #[derive(Debug, Deserialize)]
-pub struct ServiceReadyRequest {
-    pub service_id: String,
+pub struct LeaveSessionRequest {
+    pub user_identity: String,
 }
 
 #[derive(Debug, Serialize)]
-pub struct ServiceReadyResponse {
-    pub success: bool,
-    pub message: String,
-    pub all_services_ready: bool,
+pub struct LeaveSessionResponse {
+    pub session_id: String,
+    pub status: SessionStatus,
+}
+
+// 会话删除 API
+#[derive(Debug, Serialize)]
+pub struct DeleteSessionResponse {
+    pub session_id: String,
+    pub status: SessionStatus,
+}
+
+// 管理：终止所有会话 API
+#[derive(Debug, Serialize)]
+pub struct TerminateAllResponse {
+    pub terminated: usize,
+}
+
+// 管理：排空模式 API
+#[derive(Debug, Serialize)]
+pub struct DrainResponse {
+    pub draining: bool,
+}
+
+// 会话列表 API
+#[derive(Debug, Deserialize)]
+pub struct ListSessionsQuery {
+    pub status: Option<SessionStatus>,
+    pub limit: Option<usize>,
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SessionSummary {
+    pub session_id: String,
+    pub room_name: String,
+    pub status: SessionStatus,
+    pub created_at: DateTime<Utc>,
+    pub ready_services: usize,
+    pub pending_services: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListSessionsResponse {
+    pub sessions: Vec<SessionSummary>,
+    pub next_cursor: Option<String>,
+}
+
+// 服务就绪通知 API（与 SDK 共享）
+pub use session_protocol::{ServiceReadyRequest, ServiceReadyResponse};
+
+// 日志级别热更新 API
+#[derive(Debug, Deserialize)]
+pub struct SetLogLevelRequest {
+    /// `EnvFilter` 指令，如 `debug` 或 `session_manager=debug,livekit=info`
+    pub directive: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SetLogLevelResponse {
+    pub previous: String,
+    pub current: String,
 }
 
 // 健康检查 API
@@ -69,6 +196,22 @@ pub struct HealthCheckResponse {
     pub version: String,
 }
 
+// 就绪检查 API
+#[derive(Debug, Serialize)]
+pub struct ReadinessResponse {
+    pub status: String,
+    pub timestamp: DateTime<Utc>,
+    pub dependencies: Vec<DependencyStatus>,
+}
+
+// 微服务健康汇总 API
+#[derive(Debug, Serialize)]
+pub struct MicroserviceHealthResponse {
+    pub timestamp: DateTime<Utc>,
+    /// 按服务 ID 索引的各微服务健康状况
+    pub services: HashMap<String, ServiceHealth>,
+}
+
 // 错误响应
 #[derive(Debug, Serialize)]
 pub struct ErrorResponse {
@@ -0,0 +1,402 @@
+use crate::{
+    api::{
+        auth::{AdminAuth, Caller},
+        handlers::{authorized_session, handle_error, AppState},
+        models::{ErrorResponse, SessionStatusResponse},
+    },
+    config::StreamConfig,
+    events::{EventBus, EventReceiver, EventSubscription, SequencedEvent},
+    utils::errors::SessionManagerError,
+};
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        Path, State,
+    },
+    http::StatusCode,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Json, Response,
+    },
+};
+use futures::stream::{self, Stream, StreamExt};
+use session_protocol::SESSION_SNAPSHOT_EVENT;
+use std::time::Duration;
+use tokio::{
+    sync::{broadcast::error::RecvError, watch},
+    time::{sleep_until, Instant},
+};
+
+const WS_PING_INTERVAL: Duration = Duration::from_secs(15);
+
+// 会话事件流 (SSE)，先发送会话快照，再推送实时事件；与会话状态接口相同，仅所有者或管理员可订阅
+pub async fn session_events_stream(
+    caller: Caller,
+    State(state): State<AppState>,
+    Path(session_id): Path<String>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, (StatusCode, Json<ErrorResponse>)>
+{
+    authorized_session(&state, &caller, session_id.clone()).await?;
+    let subscription = subscribe_session(&state, &session_id)?;
+    // 先订阅再读取存储，快照之后的变化都会出现在事件流中
+    let snapshot = session_snapshot(&state, &session_id).await?;
+    tracing::debug!("SSE subscriber attached to session {}", session_id);
+
+    let events = sse_event_stream(
+        subscription,
+        state.event_bus,
+        LagPolicy::from(&state.config.streams),
+        state.shutdown,
+        EventIds::Sequence,
+    );
+    Ok(Sse::new(stream::once(async { snapshot }).chain(events))
+        .keep_alive(sse_keep_alive(&state.config.streams)))
+}
+
+/// 从存储读取会话当前状态，构造 `session_snapshot` 事件
+async fn session_snapshot(
+    state: &AppState,
+    session_id: &str,
+) -> Result<Result<Event, axum::Error>, (StatusCode, Json<ErrorResponse>)> {
+    let session = state
+        .storage
+        .get_session(session_id)
+        .await
+        .map_err(handle_error)?
+        .ok_or_else(|| {
+            handle_error(SessionManagerError::SessionNotFound {
+                session_id: session_id.to_string(),
+            })
+        })?;
+
+    Ok(Event::default()
+        .event(SESSION_SNAPSHOT_EVENT)
+        .json_data(SessionStatusResponse::from(session)))
+}
+
+// 全局事件流 (SSE)，包含所有会话的事件，需管理员令牌
+pub async fn global_events_stream(
+    _admin: AdminAuth,
+    State(state): State<AppState>,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+    let receiver = state.event_bus.subscribe_global();
+    tracing::debug!("SSE subscriber attached to global event stream");
+
+    Sse::new(sse_event_stream(
+        receiver.into(),
+        state.event_bus,
+        LagPolicy::from(&state.config.streams),
+        state.shutdown,
+        EventIds::None,
+    ))
+    .keep_alive(sse_keep_alive(&state.config.streams))
+}
+
+/// 按配置构建 SSE 保活设置；配置需已通过 `StreamConfig::validate`
+pub fn sse_keep_alive(config: &StreamConfig) -> KeepAlive {
+    KeepAlive::new()
+        .interval(Duration::from_secs(config.sse_keep_alive_secs))
+        .text(&config.sse_keep_alive_text)
+}
+
+// 会话事件流 (WebSocket) - 供会缓冲 SSE 的代理之后的客户端使用
+pub async fn session_events_ws(
+    caller: Caller,
+    ws: WebSocketUpgrade,
+    State(state): State<AppState>,
+    Path(session_id): Path<String>,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    authorized_session(&state, &caller, session_id.clone()).await?;
+    let subscription = subscribe_session(&state, &session_id)?;
+    tracing::debug!("WebSocket subscriber attached to session {}", session_id);
+
+    Ok(ws.on_upgrade(move |socket| forward_events_ws(socket, subscription, state.event_bus)))
+}
+
+// 全局事件流 (WebSocket)，需管理员令牌
+pub async fn global_events_ws(
+    _admin: AdminAuth,
+    ws: WebSocketUpgrade,
+    State(state): State<AppState>,
+) -> Response {
+    let receiver = state.event_bus.subscribe_global();
+    tracing::debug!("WebSocket subscriber attached to global event stream");
+
+    ws.on_upgrade(move |socket| forward_events_ws(socket, receiver.into(), state.event_bus))
+}
+
+fn subscribe_session(
+    state: &AppState,
+    session_id: &str,
+) -> Result<EventSubscription, (StatusCode, Json<ErrorResponse>)> {
+    state
+        .event_bus
+        .get_session_stream(session_id)
+        .ok_or_else(|| {
+            handle_error(SessionManagerError::SessionNotFound {
+                session_id: session_id.to_string(),
+            })
+        })
+}
+
+/// How an event stream reports events a slow subscriber missed
+#[derive(Debug, Clone, Copy)]
+pub struct LagPolicy {
+    /// Lags within this window after the first one are reported as a single summary
+    pub coalesce_window: Duration,
+    /// Disconnect the subscriber once it has missed more than this many events in
+    /// total; `0` never disconnects
+    pub max_skipped: u64,
+}
+
+impl From<&StreamConfig> for LagPolicy {
+    fn from(config: &StreamConfig) -> Self {
+        Self {
+            coalesce_window: Duration::from_millis(config.lag_coalesce_window_ms),
+            max_skipped: config.max_lagged_events,
+        }
+    }
+}
+
+/// Item of a lag-coalescing event stream
+#[derive(Debug, Clone)]
+pub enum StreamItem {
+    Event(SequencedEvent),
+    /// `skipped` events were missed over `lags` separate lags
+    Lagged {
+        skipped: u64,
+        lags: u64,
+    },
+    /// The subscriber missed more than [`LagPolicy::max_skipped`] events; the stream ends
+    Disconnected {
+        skipped: u64,
+    },
+}
+
+/// Running summary of lags in the current coalescing window
+struct LagWindow {
+    deadline: Instant,
+    skipped: u64,
+    lags: u64,
+}
+
+struct LagState {
+    receiver: EventReceiver,
+    event_bus: EventBus,
+    policy: LagPolicy,
+    window: Option<LagWindow>,
+    total_skipped: u64,
+}
+
+/// Receive events, folding every lag within `policy.coalesce_window` of the first one
+/// into a single [`StreamItem::Lagged`] emitted when the window ends.
+///
+/// Events received meanwhile are passed through, so a subscriber that keeps lagging sees
+/// one summary per window instead of a notice per missed batch.
+pub fn coalesce_lag(
+    receiver: EventReceiver,
+    event_bus: EventBus,
+    policy: LagPolicy,
+) -> impl Stream<Item = StreamItem> {
+    let state = LagState {
+        receiver,
+        event_bus,
+        policy,
+        window: None,
+        total_skipped: 0,
+    };
+
+    stream::unfold(Some(state), |state| async move {
+        let mut state = state?;
+        loop {
+            let deadline = state.window.as_ref().map(|window| window.deadline);
+            tokio::select! {
+                biased;
+                _ = sleep_until(deadline.unwrap_or_else(Instant::now)), if deadline.is_some() => {
+                    let window = state.window.take()?;
+                    let summary = StreamItem::Lagged {
+                        skipped: window.skipped,
+                        lags: window.lags,
+                    };
+                    return Some((summary, Some(state)));
+                }
+                event = state.receiver.recv() => match event {
+                    Ok(event) => return Some((StreamItem::Event(event), Some(state))),
+                    Err(RecvError::Lagged(skipped)) => {
+                        state.event_bus.record_dropped(skipped);
+                        state.total_skipped += skipped;
+                        if state.policy.max_skipped > 0
+                            && state.total_skipped > state.policy.max_skipped
+                        {
+                            tracing::warn!(
+                                "Disconnecting subscriber after it skipped {} events",
+                                state.total_skipped
+                            );
+                            let skipped = state.total_skipped;
+                            return Some((StreamItem::Disconnected { skipped }, None));
+                        }
+
+                        tracing::debug!("Subscriber lagged, skipped {} events", skipped);
+                        let window = state.window.get_or_insert_with(|| LagWindow {
+                            deadline: Instant::now() + state.policy.coalesce_window,
+                            skipped: 0,
+                            lags: 0,
+                        });
+                        window.skipped += skipped;
+                        window.lags += 1;
+                    }
+                    // Report lags that were still being collected before ending
+                    Err(RecvError::Closed) => {
+                        let window = state.window.take()?;
+                        let summary = StreamItem::Lagged {
+                            skipped: window.skipped,
+                            lags: window.lags,
+                        };
+                        return Some((summary, None));
+                    }
+                },
+            }
+        }
+    })
+}
+
+/// Whether SSE events get an `id:` field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EventIds {
+    /// The event's sequence number, for streams of a single session
+    Sequence,
+    /// None; sequence numbers of different sessions would collide on the global stream
+    None,
+}
+
+/// Convert a subscription into SSE events: pending events first, then live events until
+/// the channel closes. Lags are coalesced according to `policy`; a subscriber that falls
+/// too far behind, or a server shutdown, gets a final `close` event before the stream ends.
+fn sse_event_stream(
+    subscription: EventSubscription,
+    event_bus: EventBus,
+    policy: LagPolicy,
+    shutdown: watch::Receiver<bool>,
+    ids: EventIds,
+) -> impl Stream<Item = Result<Event, axum::Error>> {
+    let pending = stream::iter(subscription.pending).map(move |event| sse_event(&event, ids));
+    let events = Box::pin(coalesce_lag(subscription.receiver, event_bus, policy));
+
+    let live = stream::unfold(Some((events, shutdown)), move |state| async move {
+        let (mut events, mut shutdown) = state?;
+        let event = tokio::select! {
+            item = events.next() => match item? {
+                StreamItem::Event(event) => sse_event(&event, ids),
+                StreamItem::Lagged { skipped, lags } => {
+                    tracing::warn!(
+                        "SSE subscriber lagged {} times, skipped {} events",
+                        lags,
+                        skipped
+                    );
+                    Event::default()
+                        .event("lagged")
+                        .json_data(serde_json::json!({ "skipped": skipped, "lags": lags }))
+                }
+                StreamItem::Disconnected { skipped } => {
+                    let close = Event::default()
+                        .event("close")
+                        .data(format!("subscriber too slow, skipped {} events", skipped));
+                    return Some((Ok(close), None));
+                }
+            },
+            // A dropped sender means the server is gone as well
+            _ = shutdown.wait_for(|stopping| *stopping) => {
+                tracing::debug!("Closing SSE stream for server shutdown");
+                let close = Event::default().event("close").data("server shutting down");
+                return Some((Ok(close), None));
+            }
+        };
+        Some((event, Some((events, shutdown))))
+    });
+
+    pending.chain(live)
+}
+
+fn sse_event(event: &SequencedEvent, ids: EventIds) -> Result<Event, axum::Error> {
+    let sse = Event::default().event(event.event.event_name());
+    let sse = match (ids, event.seq) {
+        (EventIds::Sequence, Some(seq)) => sse.id(seq.to_string()),
+        _ => sse,
+    };
+    sse.json_data(event)
+}
+
+/// Push events to a WebSocket until either side closes, starting with pending events.
+///
+/// The broadcast subscription is dropped together with `subscription` when this returns.
+async fn forward_events_ws(
+    mut socket: WebSocket,
+    subscription: EventSubscription,
+    event_bus: EventBus,
+) {
+    let EventSubscription {
+        pending,
+        mut receiver,
+    } = subscription;
+
+    for event in &pending {
+        if let Some(frame) = ws_text_frame(event) {
+            if socket.send(Message::Text(frame.into())).await.is_err() {
+                return;
+            }
+        }
+    }
+
+    let mut ping_interval = tokio::time::interval(WS_PING_INTERVAL);
+
+    loop {
+        tokio::select! {
+            event = receiver.recv() => {
+                let frame = match event {
+                    Ok(event) => ws_text_frame(&event),
+                    Err(RecvError::Lagged(skipped)) => {
+                        tracing::warn!("WebSocket subscriber lagged, skipped {} events", skipped);
+                        event_bus.record_dropped(skipped);
+                        Some(serde_json::json!({ "type": "Lagged", "skipped": skipped }).to_string())
+                    }
+                    Err(RecvError::Closed) => {
+                        let _ = socket.send(Message::Close(None)).await;
+                        break;
+                    }
+                };
+
+                if let Some(frame) = frame {
+                    if socket.send(Message::Text(frame.into())).await.is_err() {
+                        break;
+                    }
+                }
+            }
+
+            message = socket.recv() => {
+                match message {
+                    // Pongs are answered by the WebSocket implementation; client payloads are ignored
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    Some(Ok(_)) => {}
+                }
+            }
+
+            _ = ping_interval.tick() => {
+                if socket.send(Message::Ping(Default::default())).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+
+    tracing::debug!("WebSocket event subscriber disconnected");
+}
+
+fn ws_text_frame(event: &SequencedEvent) -> Option<String> {
+    match serde_json::to_string(event) {
+        Ok(text) => Some(text),
+        Err(e) => {
+            tracing::error!("Failed to serialize event for WebSocket: {}", e);
+            None
+        }
+    }
+}
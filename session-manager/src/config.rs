@@ -1,5 +1,12 @@
-use crate::utils::errors::{Result, SessionManagerError};
+use crate::{
+    domain::{IdentityScheme, ParticipantTimeouts, RoomNameTemplate, TokenAudit, TokenIssuer},
+    utils::{
+        errors::{Result, SessionManagerError},
+        logging::LogFormat,
+    },
+};
 use serde::Deserialize;
+use std::collections::HashMap;
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct AppConfig {
@@ -8,6 +15,20 @@ pub struct AppConfig {
     pub microservices: MicroserviceConfig,
     pub logging: LoggingConfig,
     pub vector_log: VectorLogConfig,
+    #[serde(default)]
+    pub rate_limit: RateLimitConfig,
+    #[serde(default)]
+    pub auth: AuthConfig,
+    #[serde(default)]
+    pub sweeper: SweeperConfig,
+    #[serde(default)]
+    pub streams: StreamConfig,
+    #[serde(default)]
+    pub metadata_limits: MetadataLimitConfig,
+    #[serde(default)]
+    pub discovery: DiscoveryConfig,
+    #[serde(default)]
+    pub nats: NatsSinkConfig,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -15,6 +36,16 @@ pub struct ServerConfig {
     pub host: String,
     pub port: u16,
     pub workers: Option<usize>,
+    /// 监听套接字的 backlog（等待 accept 的连接队列长度）；套接字始终开启 SO_REUSEADDR，重启后可立即重新绑定端口
+    #[serde(default = "default_listen_backlog")]
+    pub backlog: u32,
+    /// 本实例未结束会话数的上限，达到后创建会话返回 503，直至有会话结束；0 表示不限
+    #[serde(default)]
+    pub max_sessions: usize,
+}
+
+fn default_listen_backlog() -> u32 {
+    1024
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -22,12 +53,106 @@ pub struct LiveKitConfig {
     pub server_url: String,
     pub api_key: String,
     pub api_secret: String,
+    /// 单次房间 API 调用（创建/删除房间）的超时时间（毫秒）
+    #[serde(default = "default_livekit_api_timeout_ms")]
+    pub api_timeout_ms: u64,
+    /// 房间 API 调用遇到瞬时错误时的最大尝试次数（含首次）
+    #[serde(default = "default_livekit_api_max_attempts")]
+    pub api_max_attempts: u32,
+    /// 会话管理器签发的参与者身份前缀；多个实例共用同一 LiveKit 时应各自配置不同前缀
+    #[serde(default)]
+    pub identity: IdentityScheme,
+    /// 未指定 room_name 时自动生成房间名的模板，支持 {prefix}、{session_id}、{short_id}、{user_identity} 占位符
+    #[serde(default)]
+    pub room_name: RoomNameTemplate,
+    /// 写入 LiveKit 房间元数据（JSON 对象）的会话 metadata 键；为空时不公开任何元数据
+    #[serde(default)]
+    pub room_metadata_keys: Vec<String>,
+    /// 观察者判定客户端、微服务无活动超时的时间；客户端超时会终止会话
+    #[serde(default)]
+    pub participant_timeouts: ParticipantTimeouts,
+    /// 会话管理器同时保持的观察者房间连接上限，超出后新会话延迟观察
+    #[serde(default = "default_max_observer_connections")]
+    pub max_observer_connections: usize,
+    /// 房间参与者上限（含微服务与会话管理器自身），0 表示不限制；房间已满时拒绝签发客户端令牌
+    #[serde(default = "default_max_participants")]
+    pub max_participants: u32,
+    /// 观察者只承认会话已通知的微服务身份；以其他身份自称微服务的参与者不计入就绪，只记录告警
+    #[serde(default = "default_verify_service_identities")]
+    pub verify_service_identities: bool,
+    /// 会话管理器的观察者以隐藏参与者身份加入房间；关闭后客户端可以看到 `session-manager-*` 参与者（便于调试）
+    #[serde(default = "default_hidden_observer")]
+    pub hidden_observer: bool,
+    /// 观察者采集房间统计（参与者、轨道、连接质量）的间隔（秒），结果写入会话状态并发布 `stats` 事件；0 表示关闭
+    #[serde(default = "default_room_stats_interval_secs")]
+    pub room_stats_interval_secs: u64,
+    /// 令牌签发审计去向，默认输出 `token_audit` 结构化日志；不从配置文件读取，可在代码中替换
+    #[serde(skip)]
+    pub token_audit: TokenAudit,
+    /// 令牌签发方，默认使用 api_key/api_secret 本地签名；不从配置文件读取，可在代码中替换为外部认证服务
+    #[serde(skip)]
+    pub token_provider: TokenIssuer,
+}
+
+impl LiveKitConfig {
+    /// 房间 API（Twirp）使用的 HTTP 地址：`ws://`/`wss://` 换成 `http://`/`https://`，其余原样返回
+    pub fn api_url(&self) -> String {
+        if let Some(rest) = self.server_url.strip_prefix("ws://") {
+            format!("http://{}", rest)
+        } else if let Some(rest) = self.server_url.strip_prefix("wss://") {
+            format!("https://{}", rest)
+        } else {
+            self.server_url.clone()
+        }
+    }
+}
+
+fn default_livekit_api_timeout_ms() -> u64 {
+    10_000
+}
+
+fn default_livekit_api_max_attempts() -> u32 {
+    3
+}
+
+fn default_max_observer_connections() -> usize {
+    256
+}
+
+fn default_max_participants() -> u32 {
+    50
+}
+
+fn default_verify_service_identities() -> bool {
+    true
+}
+
+fn default_hidden_observer() -> bool {
+    true
+}
+
+fn default_room_stats_interval_secs() -> u64 {
+    30
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct MicroserviceConfig {
     pub registration_timeout: u64,
     pub join_timeout: u64,
+    /// 加入通知的最大尝试次数（含首次）
+    #[serde(default = "default_notify_max_attempts")]
+    pub notify_max_attempts: u32,
+    /// 加入通知首次重试前的等待时间（毫秒），之后指数退避
+    #[serde(default = "default_notify_retry_base_delay_ms")]
+    pub notify_retry_base_delay_ms: u64,
+}
+
+fn default_notify_max_attempts() -> u32 {
+    3
+}
+
+fn default_notify_retry_base_delay_ms() -> u64 {
+    500
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -43,6 +168,187 @@ pub struct VectorLogConfig {
     pub source_name: String,
 }
 
+/// 会话创建限流配置（按 user_identity 的令牌桶）
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct RateLimitConfig {
+    pub enabled: bool,
+    /// 令牌补充速率（每秒请求数），必须为正数
+    pub requests_per_second: f64,
+    /// 桶容量，即允许的突发请求数
+    pub burst: u32,
+    /// 空闲多久后清理桶（秒）
+    pub idle_timeout_secs: u64,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            requests_per_second: 1.0,
+            burst: 5,
+            idle_timeout_secs: 300,
+        }
+    }
+}
+
+/// 废弃会话清理配置：长时间停留在 Creating/WaitingForServices 且无人加入的会话会被拆除
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct SweeperConfig {
+    pub enabled: bool,
+    /// 扫描间隔（秒）
+    pub interval_secs: u64,
+    /// 会话创建后超过该时长（秒）仍无人加入即视为废弃
+    pub max_age_secs: u64,
+    /// 会话最长存活时间（秒），超过后无论是否活跃都会被终止；0 表示不限制
+    /// 创建会话时可通过 `max_lifetime_secs` 按会话覆盖
+    pub max_lifetime_secs: u64,
+    /// 已终止的会话保留该时长（秒）后从存储删除，期间仍可查询其最终状态；最多 7 天
+    pub terminated_grace_secs: u64,
+}
+
+impl Default for SweeperConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_secs: 60,
+            max_age_secs: 600,
+            max_lifetime_secs: 0,
+            terminated_grace_secs: 300,
+        }
+    }
+}
+
+/// 事件流配置
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct StreamConfig {
+    /// SSE 保活消息间隔（秒），必须为正数；应小于代理的空闲连接超时
+    pub sse_keep_alive_secs: u64,
+    /// SSE 保活注释内容，不得包含换行
+    pub sse_keep_alive_text: String,
+    /// 全局事件广播通道容量；订阅者落后超过该数量的事件会被跳过
+    pub global_channel_capacity: usize,
+    /// 每个会话事件广播通道的容量
+    pub session_channel_capacity: usize,
+    /// SSE 订阅者落后时，在该时间窗口（毫秒）内的多次落后合并为一条 `lagged` 事件
+    pub lag_coalesce_window_ms: u64,
+    /// SSE 订阅者累计跳过的事件超过该数量时断开连接；0 表示不断开
+    pub max_lagged_events: u64,
+    /// 每个会话保存的事件历史条数上限，超出后丢弃最旧的记录；0 表示不保存事件历史
+    pub history_capacity: usize,
+    /// 会话从存储删除后，其事件历史继续保留的时间（秒）；0 表示随会话一起删除
+    pub history_retention_secs: u64,
+}
+
+impl Default for StreamConfig {
+    fn default() -> Self {
+        Self {
+            sse_keep_alive_secs: 15,
+            sse_keep_alive_text: "keep-alive".to_string(),
+            global_channel_capacity: 1000,
+            session_channel_capacity: 100,
+            lag_coalesce_window_ms: 1000,
+            max_lagged_events: 0,
+            history_capacity: 1000,
+            history_retention_secs: 3600,
+        }
+    }
+}
+
+impl StreamConfig {
+    pub fn validate(&self) -> Result<()> {
+        if self.sse_keep_alive_secs == 0 {
+            return Err(SessionManagerError::Configuration(
+                "streams.sse_keep_alive_secs must be greater than 0".to_string(),
+            ));
+        }
+        if self.sse_keep_alive_text.contains(['\n', '\r']) {
+            return Err(SessionManagerError::Configuration(
+                "streams.sse_keep_alive_text must not contain line breaks".to_string(),
+            ));
+        }
+        if self.global_channel_capacity == 0 || self.session_channel_capacity == 0 {
+            return Err(SessionManagerError::Configuration(
+                "streams channel capacities must be greater than 0".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// 创建会话与注册微服务时 metadata 的大小限制，超出时返回 InvalidRequest
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct MetadataLimitConfig {
+    /// 最多允许的键数量
+    pub max_keys: usize,
+    /// 序列化为 JSON 后的最大字节数
+    pub max_bytes: usize,
+}
+
+impl Default for MetadataLimitConfig {
+    fn default() -> Self {
+        Self {
+            max_keys: 64,
+            max_bytes: 16 * 1024,
+        }
+    }
+}
+
+/// 微服务发现配置：除自注册外，额外从这里列出的来源获取可用微服务
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct DiscoveryConfig {
+    /// 静态微服务列表，未注册时也视为可用；同 ID 的服务注册后以注册信息为准
+    pub static_services: Vec<StaticServiceConfig>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct StaticServiceConfig {
+    pub service_id: String,
+    pub endpoint: String,
+    /// 与注册时的 metadata 相同，例如 `type` 用于按类型分配
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+}
+
+/// NATS 事件转发配置：启用后所有会话事件以 JSON 发布到 NATS，供后端服务消费
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct NatsSinkConfig {
+    pub enabled: bool,
+    /// NATS 服务器地址，例如 `nats://localhost:4222`
+    pub url: String,
+    /// 主题前缀，事件发布到 `{subject_prefix}.{session_id}.{event_name}`
+    pub subject_prefix: String,
+    /// 待转发事件队列容量；NATS 处理不过来、队列已满时丢弃事件
+    pub buffer: usize,
+}
+
+impl Default for NatsSinkConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: "nats://localhost:4222".to_string(),
+            subject_prefix: "session-manager.events".to_string(),
+            buffer: 1024,
+        }
+    }
+}
+
+/// 管理接口认证配置
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct AuthConfig {
+    /// 管理/调试接口的 Bearer 令牌；未配置时这些接口一律拒绝访问
+    pub admin_token: Option<String>,
+    /// 调用方 API 令牌到主体名称的映射；配置后会话接口要求 Bearer 令牌，
+    /// 且调用方只能访问自己创建的会话（管理员令牌不受限制）。为空时不校验调用方
+    pub api_tokens: HashMap<String, String>,
+}
+
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
@@ -50,16 +356,33 @@ impl Default for AppConfig {
                 host: "0.0.0.0".to_string(),
                 port: 8080,
                 workers: Some(4),
+                backlog: default_listen_backlog(),
+                max_sessions: 0,
             },
             livekit: LiveKitConfig {
                 server_url: "ws://localhost:7880".to_string(),
                 api_key: std::env::var("LIVEKIT_API_KEY").unwrap_or_else(|_| "devkey".to_string()),
                 api_secret: std::env::var("LIVEKIT_API_SECRET")
                     .unwrap_or_else(|_| "secret".to_string()),
+                api_timeout_ms: default_livekit_api_timeout_ms(),
+                api_max_attempts: default_livekit_api_max_attempts(),
+                identity: IdentityScheme::default(),
+                room_name: RoomNameTemplate::default(),
+                room_metadata_keys: Vec::new(),
+                participant_timeouts: ParticipantTimeouts::default(),
+                max_observer_connections: default_max_observer_connections(),
+                max_participants: default_max_participants(),
+                verify_service_identities: default_verify_service_identities(),
+                hidden_observer: default_hidden_observer(),
+                room_stats_interval_secs: default_room_stats_interval_secs(),
+                token_audit: TokenAudit::default(),
+                token_provider: TokenIssuer::default(),
             },
             microservices: MicroserviceConfig {
                 registration_timeout: 30,
                 join_timeout: 60,
+                notify_max_attempts: default_notify_max_attempts(),
+                notify_retry_base_delay_ms: default_notify_retry_base_delay_ms(),
             },
             logging: LoggingConfig {
                 level: "debug".to_string(),
@@ -72,26 +395,50 @@ impl Default for AppConfig {
                     .unwrap_or_else(|_| "localhost:9000".to_string()),
                 source_name: "session-manager".to_string(),
             },
+            rate_limit: RateLimitConfig::default(),
+            auth: AuthConfig::default(),
+            sweeper: SweeperConfig::default(),
+            streams: StreamConfig::default(),
+            metadata_limits: MetadataLimitConfig::default(),
+            discovery: DiscoveryConfig::default(),
+            nats: NatsSinkConfig::default(),
+        }
+    }
+}
+
+/// 未设置 `CONFIG_PATH` 时读取的配置文件
+const DEFAULT_CONFIG_PATH: &str = "/etc/session-manager/config.toml";
+
+/// 把 `overlay` 逐键合并进 `base`：两边都是表时递归合并，否则以 `overlay` 的值为准
+fn merge_toml_tables(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base_table)), toml::Value::Table(overlay_table)) => {
+                merge_toml_tables(base_table, overlay_table);
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
         }
     }
 }
 
 impl AppConfig {
+    /// 加载配置：依次合并 `CONFIG_PATH` 中以冒号分隔的 TOML 文件（如 `base.toml:production.toml`），再应用环境变量覆盖
     pub fn load() -> Result<Self> {
-        // 首先尝试从 TOML 配置文件加载
-        let config_path = std::env::var("CONFIG_PATH").unwrap_or_else(|_| "/etc/session-manager/config.toml".to_string());
-        
-        let mut config = if std::path::Path::new(&config_path).exists() {
-            // 从 TOML 文件加载配置
-            let config_str = std::fs::read_to_string(&config_path)
-                .map_err(|e| SessionManagerError::Configuration(format!("Failed to read config file {}: {}", config_path, e)))?;
-            
-            toml::from_str::<AppConfig>(&config_str)
-                .map_err(|e| SessionManagerError::Configuration(format!("Failed to parse config file: {}", e)))?
-        } else {
-            // 如果配置文件不存在，使用默认配置
-            AppConfig::default()
+        // 显式指定的文件必须存在；未指定时默认配置文件可以不存在
+        let config_path = match std::env::var("CONFIG_PATH") {
+            Ok(config_path) => config_path,
+            Err(_) if std::path::Path::new(DEFAULT_CONFIG_PATH).exists() => {
+                DEFAULT_CONFIG_PATH.to_string()
+            }
+            Err(_) => String::new(),
         };
+        let paths: Vec<&str> = config_path
+            .split(':')
+            .filter(|path| !path.is_empty())
+            .collect();
+        let mut config = Self::from_files(&paths)?;
 
         // 环境变量覆盖配置文件设置
         if let Ok(url) = std::env::var("LIVEKIT_SERVER_URL") {
@@ -122,6 +469,243 @@ impl AppConfig {
             config.vector_log.endpoint = endpoint;
         }
 
+        // 覆盖管理接口令牌
+        if let Ok(token) = std::env::var("ADMIN_TOKEN") {
+            config.auth.admin_token = Some(token);
+        }
+
         Ok(config)
     }
+
+    /// 按顺序合并多个 TOML 配置文件：后面的文件逐键覆盖前面的，嵌套的表（如 `[livekit]`）递归合并，其余值（含数组）整体替换
+    ///
+    /// 任一文件不存在时返回 `Configuration` 错误。
+    pub fn from_files<P: AsRef<std::path::Path>>(paths: &[P]) -> Result<Self> {
+        let mut merged: Option<toml::Table> = None;
+        for path in paths {
+            let path = path.as_ref();
+            // 路径写错时直接报错，避免静默使用错误的配置运行
+            if !path.exists() {
+                return Err(SessionManagerError::Configuration(format!(
+                    "Config file {} does not exist",
+                    path.display()
+                )));
+            }
+            let config_str = std::fs::read_to_string(path).map_err(|e| {
+                SessionManagerError::Configuration(format!(
+                    "Failed to read config file {}: {}",
+                    path.display(),
+                    e
+                ))
+            })?;
+            let layer = config_str.parse::<toml::Table>().map_err(|e| {
+                SessionManagerError::Configuration(format!(
+                    "Failed to parse config file {}: {}",
+                    path.display(),
+                    e
+                ))
+            })?;
+            merged = Some(match merged {
+                Some(mut base) => {
+                    merge_toml_tables(&mut base, layer);
+                    base
+                }
+                None => layer,
+            });
+        }
+
+        match merged {
+            Some(table) => toml::Value::Table(table)
+                .try_into::<AppConfig>()
+                .map_err(|e| {
+                    SessionManagerError::Configuration(format!(
+                        "Failed to parse config file: {}",
+                        e
+                    ))
+                }),
+            // 未指定配置文件时使用默认配置
+            None => Ok(AppConfig::default()),
+        }
+    }
+
+    /// 检查配置的语义正确性；发现问题时一次性列出全部问题，而不是在运行时才暴露
+    pub fn validate(&self) -> Result<()> {
+        let mut problems = Vec::new();
+        let mut require = |ok: bool, problem: &str| {
+            if !ok {
+                problems.push(problem.to_string());
+            }
+        };
+
+        // 服务器
+        require(
+            !self.server.host.trim().is_empty(),
+            "server.host must not be empty",
+        );
+        require(self.server.port != 0, "server.port must be greater than 0");
+        require(
+            self.server.workers != Some(0),
+            "server.workers must be greater than 0",
+        );
+        require(
+            self.server.backlog != 0,
+            "server.backlog must be greater than 0",
+        );
+
+        // LiveKit
+        require(
+            !self.livekit.api_key.trim().is_empty(),
+            "livekit.api_key must not be empty",
+        );
+        require(
+            !self.livekit.api_secret.trim().is_empty(),
+            "livekit.api_secret must not be empty",
+        );
+        require(
+            self.livekit.api_timeout_ms > 0,
+            "livekit.api_timeout_ms must be greater than 0",
+        );
+        require(
+            self.livekit.api_max_attempts > 0,
+            "livekit.api_max_attempts must be greater than 0",
+        );
+
+        // 微服务
+        require(
+            self.microservices.registration_timeout > 0,
+            "microservices.registration_timeout must be greater than 0",
+        );
+        require(
+            self.microservices.join_timeout > 0,
+            "microservices.join_timeout must be greater than 0",
+        );
+        require(
+            self.microservices.notify_max_attempts > 0,
+            "microservices.notify_max_attempts must be greater than 0",
+        );
+
+        // 限流与清理任务仅在启用时检查
+        if self.rate_limit.enabled {
+            let rate = self.rate_limit.requests_per_second;
+            require(
+                rate.is_finite() && rate > 0.0,
+                "rate_limit.requests_per_second must be greater than 0",
+            );
+            require(
+                self.rate_limit.burst > 0,
+                "rate_limit.burst must be greater than 0",
+            );
+        }
+        if self.sweeper.enabled {
+            require(
+                self.sweeper.interval_secs > 0,
+                "sweeper.interval_secs must be greater than 0",
+            );
+            require(
+                self.sweeper.max_age_secs > 0,
+                "sweeper.max_age_secs must be greater than 0",
+            );
+            require(
+                self.sweeper.terminated_grace_secs <= MAX_TERMINATED_GRACE_SECS,
+                "sweeper.terminated_grace_secs must not exceed 604800 (7 days)",
+            );
+        }
+
+        if self.vector_log.enabled {
+            require(
+                !self.vector_log.source_name.trim().is_empty(),
+                "vector_log.source_name must not be empty",
+            );
+        }
+
+        for service in &self.discovery.static_services {
+            require(
+                !service.service_id.trim().is_empty() && !service.endpoint.trim().is_empty(),
+                "discovery.static_services entries need a service_id and an endpoint",
+            );
+        }
+
+        if self.nats.enabled {
+            require(
+                !self.nats.url.trim().is_empty(),
+                "nats.url must not be empty",
+            );
+            require(
+                !self.nats.subject_prefix.trim().is_empty(),
+                "nats.subject_prefix must not be empty",
+            );
+            require(self.nats.buffer > 0, "nats.buffer must be greater than 0");
+        }
+
+        if let Some(problem) = livekit_url_problem(&self.livekit.server_url) {
+            problems.push(problem);
+        }
+        if self.vector_log.enabled {
+            if let Some(problem) = vector_endpoint_problem(&self.vector_log.endpoint) {
+                problems.push(problem);
+            }
+        }
+
+        // 各子配置自带的校验
+        let nested = [
+            self.logging.format.parse::<LogFormat>().map(|_| ()),
+            self.streams.validate(),
+            self.livekit.room_name.validate(),
+        ];
+        problems.extend(nested.into_iter().filter_map(|result| match result {
+            Ok(()) => None,
+            Err(SessionManagerError::Configuration(problem)) => Some(problem),
+            Err(e) => Some(e.to_string()),
+        }));
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(SessionManagerError::Configuration(format!(
+                "Invalid configuration: {}",
+                problems.join("; ")
+            )))
+        }
+    }
+}
+
+/// 已终止会话在存储中保留时长的上限（秒），7 天
+const MAX_TERMINATED_GRACE_SECS: u64 = 7 * 24 * 3600;
+
+/// LiveKit 服务器地址支持的协议
+const LIVEKIT_URL_SCHEMES: [&str; 4] = ["ws", "wss", "http", "https"];
+
+fn livekit_url_problem(server_url: &str) -> Option<String> {
+    match reqwest::Url::parse(server_url) {
+        Ok(url) if !LIVEKIT_URL_SCHEMES.contains(&url.scheme()) => Some(format!(
+            "livekit.server_url '{}' uses unsupported scheme '{}', expected one of {}",
+            server_url,
+            url.scheme(),
+            LIVEKIT_URL_SCHEMES.join(", ")
+        )),
+        Ok(url) if url.host_str().is_none() => {
+            Some(format!("livekit.server_url '{}' has no host", server_url))
+        }
+        Ok(_) => None,
+        Err(e) => Some(format!(
+            "livekit.server_url '{}' is not a valid URL: {}",
+            server_url, e
+        )),
+    }
+}
+
+/// Vector 地址须为 host:port，允许带 `http://` 前缀（与启动时的处理一致）
+fn vector_endpoint_problem(endpoint: &str) -> Option<String> {
+    let addr = endpoint.strip_prefix("http://").unwrap_or(endpoint);
+    let valid = addr.rsplit_once(':').is_some_and(|(host, port)| {
+        !host.is_empty() && !host.contains('/') && port.parse::<u16>().is_ok_and(|port| port > 0)
+    });
+    if valid {
+        None
+    } else {
+        Some(format!(
+            "vector_log.endpoint '{}' must be host:port",
+            endpoint
+        ))
+    }
 }
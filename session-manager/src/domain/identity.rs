@@ -0,0 +1,41 @@
+use serde::Deserialize;
+
+/// Prefixes of the participant identities the session manager issues
+///
+/// Session-manager instances sharing a LiveKit deployment should use distinct prefixes so
+/// neither mistakes the other's observer or clients for its own.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct IdentityScheme {
+    pub manager_prefix: String,
+    pub client_prefix: String,
+}
+
+impl Default for IdentityScheme {
+    fn default() -> Self {
+        Self {
+            manager_prefix: "session-manager-".to_string(),
+            client_prefix: "client-".to_string(),
+        }
+    }
+}
+
+impl IdentityScheme {
+    /// Identity the session manager's observer uses in a session's room
+    pub fn manager(&self, session_id: &str) -> String {
+        format!("{}{}", self.manager_prefix, session_id)
+    }
+
+    /// Identity issued in a session's client token
+    pub fn client(&self, session_id: &str) -> String {
+        format!("{}{}", self.client_prefix, session_id)
+    }
+
+    /// The prefix `identity` would impersonate, if any; users may not pick such identities
+    pub fn reserved_prefix(&self, identity: &str) -> Option<&str> {
+        [&self.manager_prefix, &self.client_prefix]
+            .into_iter()
+            .find(|prefix| !prefix.is_empty() && identity.starts_with(prefix.as_str()))
+            .map(String::as_str)
+    }
+}
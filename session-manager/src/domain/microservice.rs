@@ -2,6 +2,12 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+// 与微服务之间的请求/响应类型由 SDK 共享
+pub use session_protocol::{
+    Capabilities, JoinRoomRequest, JoinRoomResponse, LeaveReason, LeaveRoomRequest, PayloadFormat,
+    NOTIFICATION_FORMAT_METADATA_KEY, SERVICE_TYPE_METADATA_KEY,
+};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MicroserviceInfo {
     pub service_id: String,
@@ -9,6 +15,9 @@ pub struct MicroserviceInfo {
     pub status: ServiceStatus,
     pub registered_at: DateTime<Utc>,
     pub metadata: HashMap<String, String>,
+    /// 微服务加入房间时声明的能力（数据主题、编解码器、模型版本等）
+    #[serde(default)]
+    pub capabilities: Capabilities,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -27,6 +36,7 @@ impl MicroserviceInfo {
             status: ServiceStatus::Registered,
             registered_at: Utc::now(),
             metadata,
+            capabilities: Capabilities::new(),
         }
     }
 
@@ -34,6 +44,22 @@ impl MicroserviceInfo {
         self.status = status;
     }
 
+    /// 微服务注册时声明的类型（metadata 中的 `type`）
+    pub fn service_type(&self) -> Option<&str> {
+        self.metadata
+            .get(SERVICE_TYPE_METADATA_KEY)
+            .map(String::as_str)
+    }
+
+    /// 微服务注册时要求的通知编码（metadata 中的 `notification_format`），未声明或无法识别时为 JSON
+    pub fn notification_format(&self) -> PayloadFormat {
+        PayloadFormat::from_metadata(
+            self.metadata
+                .get(NOTIFICATION_FORMAT_METADATA_KEY)
+                .map(String::as_str),
+        )
+    }
+
     pub fn is_available(&self) -> bool {
         matches!(
             self.status,
@@ -41,18 +67,3 @@ impl MicroserviceInfo {
         )
     }
 }
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct JoinRoomRequest {
-    pub room_name: String,
-    pub session_id: String,
-    pub service_identity: String,
-    pub access_token: String,
-    pub livekit_url: String,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct JoinRoomResponse {
-    pub success: bool,
-    pub message: String,
-}
@@ -1,5 +1,17 @@
+pub mod identity;
 pub mod microservice;
+pub mod participant;
+pub mod room_name;
 pub mod session;
+pub mod session_observer;
+pub mod token_audit;
+pub mod token_provider;
 
+pub use identity::*;
 pub use microservice::*;
+pub use participant::*;
+pub use room_name::*;
 pub use session::*;
+pub use session_observer::*;
+pub use token_audit::*;
+pub use token_provider::*;
@@ -0,0 +1,78 @@
+use crate::domain::IdentityScheme;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Role of a participant in a session's LiveKit room
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ParticipantRole {
+    Client,  // 终端用户
+    Service, // 微服务
+    Manager, // 会话管理器自身的观察者连接
+}
+
+/// Metadata embedded in every token the session manager mints, so the observer can read
+/// a participant's role from `participant.metadata()` instead of inferring it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParticipantMetadata {
+    pub role: ParticipantRole,
+    pub session_id: String,
+}
+
+impl ParticipantMetadata {
+    pub fn new(role: ParticipantRole, session_id: &str) -> Self {
+        Self {
+            role,
+            session_id: session_id.to_string(),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("participant metadata serializes")
+    }
+
+    /// Parse participant metadata; `None` if it was not set by the session manager
+    pub fn parse(metadata: &str) -> Option<Self> {
+        serde_json::from_str(metadata).ok()
+    }
+}
+
+impl ParticipantRole {
+    /// Classify a participant, preferring the role carried in its token metadata.
+    ///
+    /// Metadata is only trusted when it belongs to this session; otherwise (e.g. tokens
+    /// minted before roles were embedded) this falls back to [`ParticipantRole::classify`].
+    pub fn from_participant(
+        identity: &str,
+        metadata: &str,
+        session_id: &str,
+        service_ids: &HashSet<String>,
+        identities: &IdentityScheme,
+    ) -> Self {
+        match ParticipantMetadata::parse(metadata) {
+            Some(metadata) if metadata.session_id == session_id => metadata.role,
+            _ => Self::classify(identity, session_id, service_ids, identities),
+        }
+    }
+
+    /// Classify a participant of the session's room by exact identity.
+    ///
+    /// The manager identity is derived from the session id using the configured prefix and
+    /// services are the session's registered service ids; every other participant is a
+    /// client. No substring matching is involved, so ids like `gpu-worker-7` or
+    /// `customer-service-rep` classify correctly.
+    pub fn classify(
+        identity: &str,
+        session_id: &str,
+        service_ids: &HashSet<String>,
+        identities: &IdentityScheme,
+    ) -> Self {
+        if identity == identities.manager(session_id) {
+            ParticipantRole::Manager
+        } else if service_ids.contains(identity) {
+            ParticipantRole::Service
+        } else {
+            ParticipantRole::Client
+        }
+    }
+}
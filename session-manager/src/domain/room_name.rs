@@ -0,0 +1,88 @@
+use crate::utils::{
+    errors::{Result, SessionManagerError},
+    validation::{self, MAX_ROOM_NAME_LEN},
+};
+use serde::Deserialize;
+
+/// Placeholders a room name template may contain
+const PLACEHOLDERS: [&str; 4] = ["prefix", "session_id", "short_id", "user_identity"];
+
+/// Length of the `{short_id}` placeholder, taken from the start of the session id
+const SHORT_ID_LEN: usize = 8;
+
+/// Template for the room names of sessions created without an explicit `room_name`
+///
+/// Supported placeholders are `{prefix}`, `{session_id}`, `{short_id}` (first eight
+/// characters of the session id) and `{user_identity}`. Characters not allowed in room
+/// names are replaced with `-` and the result is cut to the maximum room name length.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct RoomNameTemplate {
+    pub template: String,
+    pub prefix: String,
+}
+
+impl Default for RoomNameTemplate {
+    fn default() -> Self {
+        Self {
+            template: "{prefix}-{session_id}".to_string(),
+            prefix: "room".to_string(),
+        }
+    }
+}
+
+impl RoomNameTemplate {
+    /// Check that the template only uses known placeholders and renders a legal room name
+    pub fn validate(&self) -> Result<()> {
+        let mut rest = self.template.as_str();
+        while let Some(start) = rest.find('{') {
+            let Some(len) = rest[start..].find('}') else {
+                return Err(invalid_template("unclosed '{'"));
+            };
+            let placeholder = &rest[start + 1..start + len];
+            if !PLACEHOLDERS.contains(&placeholder) {
+                return Err(invalid_template(&format!(
+                    "unknown placeholder {{{}}}",
+                    placeholder
+                )));
+            }
+            rest = &rest[start + len + 1..];
+        }
+
+        // Placeholder values are sanitized, so only the literal parts can be illegal
+        let sample = self.render("00000000-0000-0000-0000-000000000000", "user");
+        validation::validate_room_name(&sample)
+            .map_err(|e| invalid_template(&format!("renders an illegal room name: {}", e)))
+    }
+
+    /// Room name for a session; callers still need to ensure it is unique
+    pub fn render(&self, session_id: &str, user_identity: &str) -> String {
+        let short_id: String = session_id.chars().take(SHORT_ID_LEN).collect();
+        let name = self
+            .template
+            .replace("{prefix}", &sanitize(&self.prefix))
+            .replace("{session_id}", &sanitize(session_id))
+            .replace("{short_id}", &sanitize(&short_id))
+            .replace("{user_identity}", &sanitize(user_identity));
+
+        name.chars().take(MAX_ROOM_NAME_LEN).collect()
+    }
+}
+
+/// Replace characters that are not allowed in room names
+fn sanitize(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+fn invalid_template(reason: &str) -> SessionManagerError {
+    SessionManagerError::Configuration(format!("Invalid room name template: {}", reason))
+}
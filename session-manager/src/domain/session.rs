@@ -1,14 +1,43 @@
-use crate::config::LiveKitConfig;
-use crate::domain::microservice::MicroserviceInfo;
-use crate::events::{EventBus, SessionEvent};
+use crate::config::{LiveKitConfig, MicroserviceConfig};
+use crate::domain::microservice::{
+    Capabilities, JoinRoomRequest, LeaveReason, LeaveRoomRequest, MicroserviceInfo,
+};
+use crate::domain::participant::{ParticipantMetadata, ParticipantRole};
+use crate::domain::session_observer::{
+    run_session_observer, DisconnectAction, LifecycleObserver, ObservedEvent, ObserverExit,
+    OBSERVER_TICK_INTERVAL,
+};
+use crate::domain::token_audit::TokenAuditRecord;
+use crate::domain::token_provider::TokenRequest;
+use crate::events::EventBus;
+use crate::services::livekit_service::{
+    call_room_api, check_room_capacity, is_room_already_exists,
+};
+use crate::services::observer_pool::ObserverPermit;
 use crate::utils::errors::{Result, SessionManagerError};
 use chrono::{DateTime, Utc};
 use livekit::prelude::*;
-use livekit_api::access_token::{AccessToken, VideoGrants};
-use serde::{Deserialize, Serialize};
+use livekit_api::access_token::VideoGrants;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use session_protocol::{PayloadError, PayloadFormat};
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
+use tokio_stream::{wrappers::UnboundedReceiverStream, StreamExt};
+
+// Shared with the SDK; the serialized names are persisted and exposed through the API
+use session_protocol::RoomStats;
+pub use session_protocol::SessionStatus;
+
+/// Lifetime of every token minted for a session's room
+const TOKEN_TTL: Duration = Duration::from_secs(3600 * 6);
+
+/// How many times the session manager tries to rejoin a room after a transient disconnect
+const ROOM_RECONNECT_ATTEMPTS: u32 = 3;
+
+/// Delay before the first rejoin attempt, doubled after every failure
+const ROOM_RECONNECT_DELAY: Duration = Duration::from_secs(1);
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Session {
@@ -21,6 +50,27 @@ pub struct Session {
     pub registered_microservices: Vec<MicroserviceInfo>,
     pub ready_microservices: HashSet<String>,
     pub metadata: HashMap<String, String>,
+    /// Identity of the user the session was created for
+    #[serde(default)]
+    pub user_identity: Option<String>,
+    /// Authenticated principal that created the session; only it (or an admin) may
+    /// read, modify or delete the session. `None` when caller authentication is off.
+    #[serde(default)]
+    pub owner: Option<String>,
+    /// Whether the session manager observes the room; observer-less sessions become ready
+    /// through join acknowledgements and `service-ready` calls only
+    #[serde(default = "default_observe")]
+    pub observe: bool,
+    /// Maximum lifetime in seconds, overriding the sweeper's default; the session is
+    /// terminated once it is this old, whatever its activity
+    #[serde(default)]
+    pub max_lifetime_secs: Option<u64>,
+    /// How many services must be ready for the session to become `Ready`; `None` for all
+    #[serde(default)]
+    pub min_ready_services: Option<usize>,
+    /// Latest snapshot of the room taken by the observer; `None` until the first poll
+    #[serde(default)]
+    pub room_stats: Option<RoomStats>,
 
     // Non-serialized fields for runtime state
     #[serde(skip)]
@@ -33,16 +83,45 @@ pub struct SessionRoomConnection {
     pub room: Room,
     pub event_handle: Option<tokio::task::JoinHandle<()>>,
     pub livekit_config: LiveKitConfig,
+    /// Slot in the observer pool, held while connected
+    pub permit: Option<ObserverPermit>,
+}
+
+/// Retry policy for join notifications sent to microservices
+#[derive(Debug, Clone)]
+pub struct NotifyRetryPolicy {
+    /// Total number of attempts, including the first one
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled for every further retry
+    pub base_delay: Duration,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub enum SessionStatus {
-    Creating,           // 正在创建房间
-    WaitingForServices, // 等待微服务加入
-    Ready,              // 准备就绪，可以返回令牌
-    Active,             // 客户端已连接
-    Terminating,        // 正在终止
-    Terminated,         // 已终止
+impl NotifyRetryPolicy {
+    const MAX_DELAY: Duration = Duration::from_secs(10);
+
+    pub fn delay_for_attempt(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.base_delay.saturating_mul(factor).min(Self::MAX_DELAY)
+    }
+}
+
+impl From<&MicroserviceConfig> for NotifyRetryPolicy {
+    fn from(config: &MicroserviceConfig) -> Self {
+        Self {
+            max_attempts: config.notify_max_attempts.max(1),
+            base_delay: Duration::from_millis(config.notify_retry_base_delay_ms),
+        }
+    }
+}
+
+/// Outcome of a single failed notification attempt
+enum NotifyAttemptError {
+    Retryable(SessionManagerError),
+    Fatal(SessionManagerError),
+}
+
+fn default_observe() -> bool {
+    true
 }
 
 impl Session {
@@ -58,13 +137,36 @@ impl Session {
             registered_microservices: Vec::new(),
             ready_microservices: HashSet::new(),
             metadata,
+            user_identity: None,
+            owner: None,
+            observe: true,
+            max_lifetime_secs: None,
+            min_ready_services: None,
+            room_stats: None,
             room_connection: None,
         }
     }
 
-    pub fn update_status(&mut self, status: SessionStatus) {
+    /// Move the session to `status`, rejecting transitions that
+    /// [`SessionStatus::can_transition_to`] does not allow
+    pub fn transition_to(&mut self, status: SessionStatus) -> Result<()> {
+        if !self.status.can_transition_to(&status) {
+            return Err(SessionManagerError::InvalidStatusTransition {
+                session_id: self.id.clone(),
+                from: self.status.clone(),
+                to: status,
+            });
+        }
         self.status = status;
         self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Like [`Self::transition_to`], but logs and ignores an illegal transition
+    pub fn update_status(&mut self, status: SessionStatus) {
+        if let Err(e) = self.transition_to(status) {
+            tracing::warn!("Ignoring status change: {}", e);
+        }
     }
 
     pub fn add_microservice(&mut self, microservice: MicroserviceInfo) {
@@ -77,14 +179,49 @@ impl Session {
         if was_inserted {
             self.updated_at = Utc::now();
 
-            // 检查是否所有微服务都已就绪
-            if self.ready_microservices.len() == self.registered_microservices.len() {
-                self.status = SessionStatus::Ready;
+            // 检查就绪的微服务是否达到法定数量（默认全部）；已在终止的会话保持原状态
+            if self.ready_microservices.len() >= self.ready_quorum() {
+                let _ = self.transition_to(SessionStatus::Ready);
             }
         }
         was_inserted
     }
 
+    /// Merge `patch` into the metadata; a `None` value removes the key
+    pub fn merge_metadata(&mut self, patch: HashMap<String, Option<String>>) {
+        for (key, value) in patch {
+            match value {
+                Some(value) => {
+                    self.metadata.insert(key, value);
+                }
+                None => {
+                    self.metadata.remove(&key);
+                }
+            }
+        }
+        self.updated_at = Utc::now();
+    }
+
+    /// JSON object of the metadata entries listed in `keys`, used as the LiveKit room
+    /// metadata; empty when none of the keys are set
+    pub fn room_metadata(&self, keys: &[String]) -> String {
+        let exposed: std::collections::BTreeMap<&str, &str> = keys
+            .iter()
+            .filter_map(|key| Some((key.as_str(), self.metadata.get(key)?.as_str())))
+            .collect();
+        if exposed.is_empty() {
+            return String::new();
+        }
+        serde_json::to_string(&exposed).unwrap_or_default()
+    }
+
+    /// Number of ready services that makes the session `Ready`: `min_ready_services`,
+    /// capped at the session's service count
+    pub fn ready_quorum(&self) -> usize {
+        let all = self.registered_microservices.len();
+        self.min_ready_services.map_or(all, |min| min.min(all))
+    }
+
     pub fn is_ready(&self) -> bool {
         self.status == SessionStatus::Ready
     }
@@ -97,26 +234,70 @@ impl Session {
             .collect()
     }
 
+    /// Record the capabilities a microservice reported when joining
+    ///
+    /// Returns `false` if the service is not part of this session.
+    pub fn set_service_capabilities(
+        &mut self,
+        service_id: &str,
+        capabilities: Capabilities,
+    ) -> bool {
+        match self
+            .registered_microservices
+            .iter_mut()
+            .find(|service| service.service_id == service_id)
+        {
+            Some(service) => {
+                service.capabilities = capabilities;
+                self.updated_at = Utc::now();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Capabilities reported by each microservice, omitting services that reported none
+    pub fn service_capabilities(&self) -> HashMap<String, Capabilities> {
+        self.registered_microservices
+            .iter()
+            .filter(|service| !service.capabilities.is_empty())
+            .map(|service| (service.service_id.clone(), service.capabilities.clone()))
+            .collect()
+    }
+
     pub fn get_ready_services(&self) -> Vec<String> {
         self.ready_microservices.iter().cloned().collect()
     }
 
+    /// Identities of remote participants currently in the observed room.
+    ///
+    /// Returns an empty list when the session manager is not connected to the room.
+    pub async fn remote_participant_identities(&self) -> Vec<String> {
+        match &self.room_connection {
+            Some(connection) => connection
+                .read()
+                .await
+                .room
+                .remote_participants()
+                .keys()
+                .map(|identity| identity.to_string())
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
     /// Create a LiveKit room for this session
-    pub async fn create_livekit_room(&self, config: &LiveKitConfig) -> Result<()> {
+    ///
+    /// Returns `false` when the room already existed, e.g. because another session
+    /// shares it, so callers know whether the room is theirs to delete.
+    pub async fn create_livekit_room(&self, config: &LiveKitConfig) -> Result<bool> {
         use livekit_api::services::room::{CreateRoomOptions, RoomClient};
 
         tracing::debug!("Creating LiveKit room for session {}", self.id);
         tracing::debug!("  Room name: {}", self.room_name);
         tracing::debug!("  LiveKit server URL: {}", config.server_url);
 
-        // Convert WebSocket URL to HTTP for API calls
-        let api_url = if config.server_url.starts_with("ws://") {
-            config.server_url.replace("ws://", "http://")
-        } else if config.server_url.starts_with("wss://") {
-            config.server_url.replace("wss://", "https://")
-        } else {
-            config.server_url.clone()
-        };
+        let api_url = config.api_url();
 
         tracing::debug!("  Converted API URL: {}", api_url);
 
@@ -124,7 +305,8 @@ impl Session {
 
         let options = CreateRoomOptions {
             empty_timeout: 300, // 5 minutes
-            max_participants: 50,
+            max_participants: config.max_participants,
+            metadata: self.room_metadata(&config.room_metadata_keys),
             ..Default::default()
         };
 
@@ -134,10 +316,21 @@ impl Session {
             options.max_participants
         );
 
-        match room_client.create_room(&self.room_name, options).await {
+        match call_room_api(config, "create_room", || {
+            room_client.create_room(&self.room_name, options.clone())
+        })
+        .await
+        {
             Ok(_) => {
                 tracing::info!("✓ Successfully created LiveKit room: {}", self.room_name);
-                Ok(())
+                Ok(true)
+            }
+            Err(e) if is_room_already_exists(&e) => {
+                tracing::info!(
+                    "✓ LiveKit room '{}' already exists, reusing it",
+                    self.room_name
+                );
+                Ok(false)
             }
             Err(e) => {
                 tracing::error!(
@@ -145,11 +338,28 @@ impl Session {
                     self.room_name,
                     e
                 );
-                Err(SessionManagerError::LiveKit(e))
+                Err(e)
             }
         }
     }
 
+    /// Push the exposed metadata keys to the LiveKit room after they change
+    pub async fn update_livekit_room_metadata(&self, config: &LiveKitConfig) -> Result<()> {
+        use livekit_api::services::room::RoomClient;
+
+        let api_url = config.api_url();
+
+        let room_client = RoomClient::with_api_key(&api_url, &config.api_key, &config.api_secret);
+        let metadata = self.room_metadata(&config.room_metadata_keys);
+
+        call_room_api(config, "update_room_metadata", || {
+            room_client.update_room_metadata(&self.room_name, &metadata)
+        })
+        .await?;
+        tracing::debug!("Updated metadata of LiveKit room {}", self.room_name);
+        Ok(())
+    }
+
     /// Delete the LiveKit room for this session
     pub async fn delete_livekit_room(&self, config: &LiveKitConfig) -> Result<()> {
         use livekit_api::services::room::RoomClient;
@@ -157,20 +367,17 @@ impl Session {
         tracing::debug!("Deleting LiveKit room for session {}", self.id);
         tracing::debug!("  Room name: {}", self.room_name);
 
-        // Convert WebSocket URL to HTTP for API calls
-        let api_url = if config.server_url.starts_with("ws://") {
-            config.server_url.replace("ws://", "http://")
-        } else if config.server_url.starts_with("wss://") {
-            config.server_url.replace("wss://", "https://")
-        } else {
-            config.server_url.clone()
-        };
+        let api_url = config.api_url();
 
         tracing::debug!("  Using API URL: {}", api_url);
 
         let room_client = RoomClient::with_api_key(&api_url, &config.api_key, &config.api_secret);
 
-        match room_client.delete_room(&self.room_name).await {
+        match call_room_api(config, "delete_room", || {
+            room_client.delete_room(&self.room_name)
+        })
+        .await
+        {
             Ok(_) => {
                 tracing::info!("✓ Successfully deleted LiveKit room: {}", self.room_name);
                 Ok(())
@@ -181,16 +388,19 @@ impl Session {
                     self.room_name,
                     e
                 );
-                Err(SessionManagerError::LiveKit(e))
+                Err(e)
             }
         }
     }
 
     /// Connect to LiveKit room and start monitoring for microservice joins
+    ///
+    /// The observer `permit` is held for as long as the connection stays open.
     pub async fn connect_to_livekit(
         &mut self,
         livekit_config: LiveKitConfig,
         event_bus: Arc<EventBus>,
+        permit: ObserverPermit,
     ) -> Result<()> {
         tracing::debug!("Connecting session {} to LiveKit room", self.id);
         tracing::debug!("  Room name: {}", self.room_name);
@@ -205,7 +415,7 @@ impl Session {
 
         // Generate room token for session manager
         tracing::debug!("Generating room token for session manager");
-        let room_token = self.generate_room_token(&livekit_config)?;
+        let room_token = self.generate_room_token(&livekit_config).await?;
         tracing::debug!("✓ Room token generated successfully");
 
         // Convert server URL to WebSocket format for Room::connect
@@ -225,10 +435,10 @@ impl Session {
             .await
             .map_err(|e| {
                 tracing::error!("✗ Failed to connect to LiveKit room: {}", e);
-                SessionManagerError::Internal(anyhow::anyhow!(
-                    "Failed to connect to LiveKit room: {}",
-                    e
-                ))
+                SessionManagerError::LiveKitConnect {
+                    room_name: self.room_name.clone(),
+                    source: e,
+                }
             })?;
 
         tracing::info!(
@@ -249,209 +459,133 @@ impl Session {
             expected_services.len()
         );
 
-        let event_bus_clone = event_bus.clone();
-        let event_handle = tokio::spawn(async move {
-            Self::monitor_session_lifecycle(
-                session_id,
-                event_rx,
-                expected_services,
-                event_bus_clone,
-            )
-            .await;
-        });
-
-        // Store connection
-        let connection = SessionRoomConnection {
+        let mut observer = LifecycleObserver::new(
+            session_id.clone(),
+            expected_services,
+            event_bus,
+            livekit_config.identity.clone(),
+            livekit_config.participant_timeouts.clone(),
+            livekit_config.max_participants,
+        )
+        .with_identity_verification(livekit_config.verify_service_identities)
+        .with_ready_quorum(self.ready_quorum());
+
+        // Store connection; the monitoring task swaps in the new room when it rejoins
+        let connection = Arc::new(RwLock::new(SessionRoomConnection {
             room,
-            event_handle: Some(event_handle),
+            event_handle: None,
             livekit_config,
-        };
-
-        self.room_connection = Some(Arc::new(RwLock::new(connection)));
-        self.update_status(SessionStatus::WaitingForServices);
-
-        tracing::info!(
-            "✓ Session {} connected to LiveKit and monitoring started",
-            self.id
-        );
-        Ok(())
-    }
-
-    /// Monitor session lifecycle - handles microservices and client connections throughout session lifetime
-    async fn monitor_session_lifecycle(
-        session_id: String,
-        mut event_rx: tokio::sync::mpsc::UnboundedReceiver<RoomEvent>,
-        expected_services: HashSet<String>,
-        event_bus: Arc<EventBus>,
-    ) {
-        let mut joined_services = HashSet::new();
-        let mut client_connected = false;
-        let mut client_last_seen = std::time::Instant::now();
-        let mut service_last_seen: std::collections::HashMap<String, std::time::Instant> =
-            std::collections::HashMap::new();
-
-        const CLIENT_TIMEOUT_SECS: u64 = 60; // 5 minutes
-        const SERVICE_TIMEOUT_SECS: u64 = 60; // 1 minute
-        const SERVICE_RETRY_INTERVAL_SECS: u64 = 30; // 30 seconds
-
-        let mut retry_timer =
-            tokio::time::interval(std::time::Duration::from_secs(SERVICE_RETRY_INTERVAL_SECS));
-
-        tracing::info!(
-            "Starting session lifecycle monitoring for session {}",
-            session_id
-        );
-
-        loop {
-            tokio::select! {
-                // Handle room events
-                event = event_rx.recv() => {
-                    match event {
-                        Some(RoomEvent::ParticipantConnected(participant)) => {
-                            let identity = participant.identity().to_string();
-
-                            if expected_services.contains(&identity) {
-                                // Microservice joined
-                                if !joined_services.contains(&identity) {
-                                    joined_services.insert(identity.clone());
-                                    service_last_seen.insert(identity.clone(), std::time::Instant::now());
-
-                                    tracing::info!("Microservice {} joined session {}", identity, session_id);
-
-                                    // Publish microservice joined event
-                                    event_bus.publish_to_session(&session_id, SessionEvent::MicroserviceJoined {
-                                        session_id: session_id.clone(),
-                                        service_id: identity,
-                                    });
-
-                                    // Check if all services have joined
-                                    if joined_services.len() == expected_services.len() {
-                                        event_bus.publish_to_session(&session_id, SessionEvent::SessionReady {
-                                            session_id: session_id.clone(),
-                                            all_participants_joined: true,
-                                        });
-                                    }
-                                } else {
-                                    // Service reconnected
-                                    service_last_seen.insert(identity.clone(), std::time::Instant::now());
-                                    tracing::info!("Microservice {} reconnected to session {}", identity, session_id);
-                                }
-                            } else if identity.starts_with("client-") || (!identity.starts_with("session-manager-") && !expected_services.contains(&identity)) {
-                                // Client joined
-                                client_connected = true;
-                                client_last_seen = std::time::Instant::now();
-                                tracing::info!("Client {} joined session {}", identity, session_id);
-
-                                event_bus.publish_to_session(&session_id, SessionEvent::ClientJoined {
-                                    session_id: session_id.clone(),
-                                    user_identity: identity,
-                                });
-                            }
-                        }
-
-                        Some(RoomEvent::ParticipantDisconnected(participant)) => {
-                            let identity = participant.identity().to_string();
-
-                            if joined_services.contains(&identity) {
-                                // Microservice disconnected
-                                tracing::warn!("Microservice {} disconnected from session {}", identity, session_id);
-                                // Don't remove from joined_services immediately - wait for timeout
-                            } else if identity.starts_with("client-") || (!identity.starts_with("session-manager-") && !expected_services.contains(&identity)) {
-                                // Client disconnected
-                                client_connected = false;
-                                tracing::info!("Client {} disconnected from session {}", identity, session_id);
-                            }
-                        }
-
-                        Some(_) => {
-                            // Other room events - update last seen times for all participants
-                            client_last_seen = std::time::Instant::now();
-                            let now = std::time::Instant::now();
-                            for service in &joined_services {
-                                service_last_seen.insert(service.clone(), now);
-                            }
-                        }
-
-                        None => {
-                            // Event stream closed - session should terminate
-                            tracing::warn!("Room event stream closed for session {}", session_id);
-                            break;
-                        }
-                    }
+            permit: Some(permit),
+        }));
+        let rejoin_connection = connection.clone();
+        let event_handle = tokio::spawn(async move {
+            tracing::info!(
+                "Starting session lifecycle monitoring for session {}",
+                session_id
+            );
+            let mut event_rx = event_rx;
+            loop {
+                let events = UnboundedReceiverStream::new(event_rx).map(ObservedEvent::from);
+                let exit =
+                    run_session_observer(&mut observer, events, OBSERVER_TICK_INTERVAL).await;
+                if exit != ObserverExit::Disconnected(DisconnectAction::Reconnect) {
+                    break;
                 }
-
-                // Periodic checks for timeouts and retries
-                _ = retry_timer.tick() => {
-                    let now = std::time::Instant::now();
-
-                    // Check client timeout
-                    if client_connected && now.duration_since(client_last_seen).as_secs() > CLIENT_TIMEOUT_SECS {
-                        tracing::warn!("Client timeout for session {} - terminating session", session_id);
-                        event_bus.publish_to_session(&session_id, SessionEvent::SessionStatusChanged {
-                            session_id: session_id.clone(),
-                            status: SessionStatus::Terminating,
-                        });
+                match rejoin_room(&ws_url, &room_token, &rejoin_connection).await {
+                    Some(events) => event_rx = events,
+                    None => {
+                        tracing::error!(
+                            "Giving up on rejoining the room of session {}",
+                            session_id
+                        );
                         break;
                     }
-
-                    // Check service timeouts and retry disconnected services
-                    let mut services_to_retry = Vec::new();
-                    for service in &expected_services {
-                        if let Some(last_seen) = service_last_seen.get(service) {
-                            if now.duration_since(*last_seen).as_secs() > SERVICE_TIMEOUT_SECS {
-                                tracing::warn!("Service {} timeout in session {} - will retry", service, session_id);
-                                joined_services.remove(service);
-                                services_to_retry.push(service.clone());
-                            }
-                        } else if !joined_services.contains(service) {
-                            // Service never joined - retry
-                            services_to_retry.push(service.clone());
-                        }
-                    }
-
-                    // Retry failed services
-                    if !services_to_retry.is_empty() {
-                        tracing::info!("Retrying {} services for session {}", services_to_retry.len(), session_id);
-                        // TODO: Implement service retry logic here
-                        // This would involve re-sending join notifications to failed services
-                    }
                 }
             }
+            tracing::info!(
+                "Session lifecycle monitoring ended for session {}",
+                session_id
+            );
+        });
+        connection.write().await.event_handle = Some(event_handle);
+
+        self.room_connection = Some(connection);
+        // A deferred observer may connect after the services already reported ready
+        if self.status == SessionStatus::Creating {
+            self.transition_to(SessionStatus::WaitingForServices)?;
         }
 
         tracing::info!(
-            "Session lifecycle monitoring ended for session {}",
-            session_id
+            "✓ Session {} connected to LiveKit and monitoring started",
+            self.id
         );
+        Ok(())
+    }
+
+    /// Issue a token for `identity` in this session's room through the configured
+    /// [`TokenProvider`](crate::domain::TokenProvider) and record it with the configured
+    /// [`TokenAudit`](crate::domain::TokenAudit)
+    async fn mint_token(
+        &self,
+        config: &LiveKitConfig,
+        identity: &str,
+        role: ParticipantRole,
+        grants: VideoGrants,
+    ) -> anyhow::Result<String> {
+        let audit = TokenAuditRecord::new(&self.id, identity, role, &grants, TOKEN_TTL);
+        let request = TokenRequest {
+            session_id: self.id.clone(),
+            identity: identity.to_string(),
+            role,
+            grants,
+            metadata: ParticipantMetadata::new(role, &self.id).to_json(),
+            ttl: TOKEN_TTL,
+        };
+        let provider = config.token_provider.provider(config);
+        let token = match role {
+            ParticipantRole::Client => provider.client_token(&request).await?,
+            ParticipantRole::Service => provider.service_token(&request).await?,
+            ParticipantRole::Manager => provider.manager_token(&request).await?,
+        };
+        config.token_audit.record(&audit);
+        Ok(token)
     }
 
-    /// Generate a room token for connecting to LiveKit
-    fn generate_room_token(&self, config: &LiveKitConfig) -> Result<String> {
+    /// Generate the token the session manager's observer connects to the room with
+    ///
+    /// The observer joins hidden unless `hidden_observer` is turned off, so clients do not
+    /// see it as a participant.
+    pub async fn generate_room_token(&self, config: &LiveKitConfig) -> Result<String> {
         tracing::debug!("Generating room token for session manager");
         tracing::debug!("  Session ID: {}", self.id);
         tracing::debug!("  Room name: {}", self.room_name);
-        tracing::debug!("  Identity: session-manager-{}", self.id);
+        tracing::debug!("  Identity: {}", config.identity.manager(&self.id));
 
         let grants = VideoGrants {
             room_join: true,
             room: self.room_name.clone(),
             can_publish: false,
             can_subscribe: true,
+            hidden: config.hidden_observer,
             ..Default::default()
         };
 
-        tracing::debug!("  Grants: room_join=true, can_publish=false, can_subscribe=true");
+        tracing::debug!(
+            "  Grants: room_join=true, can_publish=false, can_subscribe=true, hidden={}",
+            config.hidden_observer
+        );
 
-        let token = AccessToken::with_api_key(&config.api_key, &config.api_secret)
-            .with_identity(&format!("session-manager-{}", self.id))
-            .with_grants(grants)
-            .to_jwt()
+        let token = self
+            .mint_token(
+                config,
+                &config.identity.manager(&self.id),
+                ParticipantRole::Manager,
+                grants,
+            )
+            .await
             .map_err(|e| {
                 tracing::error!("✗ Failed to generate room token: {}", e);
-                SessionManagerError::Internal(anyhow::anyhow!(
-                    "Failed to generate room token: {}",
-                    e
-                ))
+                SessionManagerError::Internal(e.context("Failed to generate room token"))
             })?;
 
         tracing::debug!("✓ Room token generated successfully");
@@ -459,10 +593,10 @@ impl Session {
     }
 
     /// Generate a client token for the session
-    pub fn generate_client_token(&self, config: &LiveKitConfig) -> Result<String> {
+    pub async fn generate_client_token(&self, config: &LiveKitConfig) -> Result<String> {
         tracing::debug!("Generating client token for session {}", self.id);
         tracing::debug!("  Room name: {}", self.room_name);
-        tracing::debug!("  Client identity: client-{}", self.id);
+        tracing::debug!("  Client identity: {}", config.identity.client(&self.id));
 
         let grants = VideoGrants {
             room_join: true,
@@ -474,16 +608,17 @@ impl Session {
 
         tracing::debug!("  Grants: room_join=true, can_publish=true, can_subscribe=true");
 
-        let token = AccessToken::with_api_key(&config.api_key, &config.api_secret)
-            .with_identity(&format!("client-{}", self.id))
-            .with_grants(grants)
-            .to_jwt()
+        let token = self
+            .mint_token(
+                config,
+                &config.identity.client(&self.id),
+                ParticipantRole::Client,
+                grants,
+            )
+            .await
             .map_err(|e| {
                 tracing::error!("✗ Failed to generate client token: {}", e);
-                SessionManagerError::Internal(anyhow::anyhow!(
-                    "Failed to generate client token: {}",
-                    e
-                ))
+                SessionManagerError::Internal(e.context("Failed to generate client token"))
             })?;
 
         tracing::info!(
@@ -493,29 +628,34 @@ impl Session {
         Ok(token)
     }
 
-    /// Handle microservice joined event
-    pub fn handle_microservice_joined(&mut self, service_id: &str) {
-        if self.mark_service_ready(service_id) {
-            tracing::info!("Microservice {} joined session {}", service_id, self.id);
-
-            // Check if all services are ready
-            if self.ready_microservices.len() == self.registered_microservices.len() {
-                self.update_status(SessionStatus::Ready);
-                tracing::info!("All microservices joined session {} - now ready", self.id);
-            }
-        }
+    /// Fail with [`SessionManagerError::RoomFull`] if the client could not connect to the
+    /// room because it is at its participant limit
+    ///
+    /// The session's own client is not counted, since reconnecting replaces it.
+    pub async fn ensure_room_capacity(&self, config: &LiveKitConfig) -> Result<()> {
+        check_room_capacity(
+            config,
+            &self.room_name,
+            Some(&config.identity.client(&self.id)),
+        )
+        .await
     }
 
     /// Notify microservices to join this session's room
     /// This sends notifications but doesn't wait - actual join success is detected via RoomEvent
+    ///
+    /// Each returned task resolves to the service id and the capabilities it reported once
+    /// its notification was accepted, or `None` if the notification failed.
     pub async fn notify_microservices_to_join(
         &self,
         livekit_config: &LiveKitConfig,
         livekit_url: &str,
-    ) -> Result<()> {
+        http_client: &reqwest::Client,
+        retry: &NotifyRetryPolicy,
+    ) -> Result<Vec<tokio::task::JoinHandle<Option<(String, Capabilities)>>>> {
         if self.registered_microservices.is_empty() {
             tracing::debug!("No microservices to notify for session {}", self.id);
-            return Ok(());
+            return Ok(Vec::new());
         }
 
         tracing::info!(
@@ -526,15 +666,17 @@ impl Session {
         tracing::debug!("  LiveKit URL: {}", livekit_url);
 
         // Send join requests to all microservices concurrently (fire and forget)
+        let mut notifications = Vec::with_capacity(self.registered_microservices.len());
         for service in &self.registered_microservices {
             tracing::debug!("Preparing notification for service: {}", service.service_id);
             tracing::debug!("  Service endpoint: {}", service.endpoint);
 
             // Generate access token for each microservice
-            let access_token =
-                self.generate_microservice_token(&service.service_id, livekit_config)?;
+            let access_token = self
+                .generate_microservice_token(&service.service_id, livekit_config)
+                .await?;
 
-            let join_request = crate::domain::JoinRoomRequest {
+            let join_request = JoinRoomRequest {
                 room_name: self.room_name.clone(),
                 session_id: self.id.clone(),
                 service_identity: service.service_id.clone(),
@@ -546,26 +688,39 @@ impl Session {
 
             let service_endpoint = service.endpoint.clone();
             let service_id = service.service_id.clone();
+            let format = service.notification_format();
+            let http_client = http_client.clone();
+            let retry = retry.clone();
 
             // Fire and forget - actual join success will be detected via RoomEvent
-            tokio::spawn(async move {
+            notifications.push(tokio::spawn(async move {
                 tracing::debug!(
                     "Sending join notification to service {} at {}",
                     service_id,
                     service_endpoint
                 );
-                match Self::notify_service_join(service_endpoint, join_request).await {
-                    Ok(()) => {
+                match Self::notify_service_join_with_format(
+                    &http_client,
+                    service_endpoint,
+                    join_request,
+                    format,
+                    &retry,
+                )
+                .await
+                {
+                    Ok(capabilities) => {
                         tracing::info!(
                             "✓ Successfully sent join notification to service {}",
                             service_id
                         );
+                        Some((service_id, capabilities))
                     }
                     Err(e) => {
                         tracing::error!("✗ Failed to notify service {} to join: {}", service_id, e);
+                        None
                     }
                 }
-            });
+            }));
         }
 
         tracing::info!(
@@ -574,17 +729,15 @@ impl Session {
             self.id
         );
 
-        Ok(())
+        Ok(notifications)
     }
 
     /// Generate access token for a microservice
-    fn generate_microservice_token(
+    pub async fn generate_microservice_token(
         &self,
         service_id: &str,
         config: &LiveKitConfig,
     ) -> Result<String> {
-        use std::time::Duration;
-
         tracing::debug!("Generating microservice token for service: {}", service_id);
         tracing::debug!("  Room name: {}", self.room_name);
         tracing::debug!("  TTL: 6 hours");
@@ -602,21 +755,16 @@ impl Session {
             "  Grants: room_join=true, can_publish=true, can_subscribe=true, can_publish_data=true"
         );
 
-        let token = AccessToken::with_api_key(&config.api_key, &config.api_secret)
-            .with_identity(service_id)
-            .with_grants(grants)
-            .with_ttl(Duration::from_secs(3600 * 6)) // 6 hours
-            .to_jwt()
+        let token = self
+            .mint_token(config, service_id, ParticipantRole::Service, grants)
+            .await
             .map_err(|e| {
                 tracing::error!(
                     "✗ Failed to generate microservice token for {}: {}",
                     service_id,
                     e
                 );
-                SessionManagerError::Internal(anyhow::anyhow!(
-                    "Failed to generate microservice token: {}",
-                    e
-                ))
+                SessionManagerError::Internal(e.context("Failed to generate microservice token"))
             })?;
 
         tracing::debug!(
@@ -627,11 +775,63 @@ impl Session {
     }
 
     /// Notify a single service to join the room
-    async fn notify_service_join(
+    ///
+    /// `client` is shared across notifications so connections and TLS sessions are reused.
+    /// Connection errors and 5xx responses are retried with exponential backoff according
+    /// to `retry`; 4xx responses mean the service rejected the request and are not retried.
+    ///
+    /// Returns the capabilities the service reported in its response.
+    pub async fn notify_service_join(
+        client: &reqwest::Client,
         endpoint: String,
-        request: crate::domain::JoinRoomRequest,
-    ) -> Result<()> {
-        let client = reqwest::Client::new();
+        request: JoinRoomRequest,
+        retry: &NotifyRetryPolicy,
+    ) -> Result<Capabilities> {
+        Self::notify_service_join_with_format(client, endpoint, request, PayloadFormat::Json, retry)
+            .await
+    }
+
+    /// Like [`Self::notify_service_join`], with the request body encoded in `format`
+    ///
+    /// The service's response is decoded according to its own `Content-Type`.
+    pub async fn notify_service_join_with_format(
+        client: &reqwest::Client,
+        endpoint: String,
+        request: JoinRoomRequest,
+        format: PayloadFormat,
+        retry: &NotifyRetryPolicy,
+    ) -> Result<Capabilities> {
+        let mut attempt = 1;
+        loop {
+            match Self::send_join_request(client, &endpoint, &request, format).await {
+                Ok(capabilities) => return Ok(capabilities),
+                Err(NotifyAttemptError::Retryable(e)) if attempt < retry.max_attempts => {
+                    let delay = retry.delay_for_attempt(attempt);
+                    tracing::warn!(
+                        "⚠ Join notification to {} failed (attempt {}/{}): {} - retrying in {:?}",
+                        endpoint,
+                        attempt,
+                        retry.max_attempts,
+                        e,
+                        delay
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                Err(NotifyAttemptError::Retryable(e)) | Err(NotifyAttemptError::Fatal(e)) => {
+                    return Err(e)
+                }
+            }
+        }
+    }
+
+    /// Send one join notification attempt
+    async fn send_join_request(
+        client: &reqwest::Client,
+        endpoint: &str,
+        request: &JoinRoomRequest,
+        format: PayloadFormat,
+    ) -> std::result::Result<Capabilities, NotifyAttemptError> {
         let url = format!("{}/join-room", endpoint);
 
         tracing::debug!("Sending join notification to service");
@@ -640,17 +840,19 @@ impl Session {
         tracing::debug!("  Session ID: {}", request.session_id);
         tracing::debug!("  Service Identity: {}", request.service_identity);
         tracing::debug!("  LiveKit URL: {}", request.livekit_url);
+        tracing::debug!("  Format: {}", format);
 
-        let response = client
-            .post(&url)
-            .json(&request)
-            .timeout(std::time::Duration::from_secs(10))
-            .send()
-            .await
-            .map_err(|e| {
-                tracing::error!("✗ HTTP request failed to {}: {}", endpoint, e);
-                SessionManagerError::MicroserviceCommunication(e)
-            })?;
+        let request = payload_request(client, &url, format, request).map_err(|e| {
+            NotifyAttemptError::Fatal(SessionManagerError::Internal(anyhow::anyhow!(
+                "Failed to encode join request as {}: {}",
+                format,
+                e
+            )))
+        })?;
+        let response = request.send().await.map_err(|e| {
+            tracing::error!("✗ HTTP request failed to {}: {}", endpoint, e);
+            NotifyAttemptError::Retryable(SessionManagerError::MicroserviceCommunication(e))
+        })?;
 
         let status = response.status();
         tracing::debug!("  Response status: {}", status);
@@ -660,7 +862,19 @@ impl Session {
                 "✓ Successfully notified service at {} to join room",
                 endpoint
             );
-            Ok(())
+
+            // The service accepted the request; a response without capabilities is not an error
+            match decode_response::<crate::domain::JoinRoomResponse>(response).await {
+                Ok(body) => Ok(body.capabilities),
+                Err(e) => {
+                    tracing::warn!(
+                        "⚠ Could not read join response from {}: {} - assuming no capabilities",
+                        endpoint,
+                        e
+                    );
+                    Ok(Capabilities::new())
+                }
+            }
         } else {
             let error_text = response
                 .text()
@@ -672,14 +886,62 @@ impl Session {
                 status,
                 error_text
             );
-            Err(SessionManagerError::Internal(anyhow::anyhow!(
+            let error = SessionManagerError::Internal(anyhow::anyhow!(
                 "Service returned error {}: {}",
                 status,
                 error_text
-            )))
+            ));
+            if status.is_server_error() {
+                Err(NotifyAttemptError::Retryable(error))
+            } else {
+                Err(NotifyAttemptError::Fatal(error))
+            }
         }
     }
 
+    /// Tell every registered service to leave the room, and why
+    ///
+    /// Best effort: each service gets one attempt and failures are only logged, since the
+    /// room is torn down regardless.
+    pub async fn notify_services_to_leave(&self, client: &reqwest::Client, reason: LeaveReason) {
+        let notifications = self.registered_microservices.iter().map(|service| {
+            let url = format!("{}/leave-room", service.endpoint);
+            let request = LeaveRoomRequest {
+                room_name: self.room_name.clone(),
+                session_id: self.id.clone(),
+                service_identity: service.service_id.clone(),
+                reason,
+            };
+            let format = service.notification_format();
+            async move {
+                let sent = match payload_request(client, &url, format, &request) {
+                    Ok(builder) => builder.send().await.map_err(|e| e.to_string()),
+                    Err(e) => Err(e.to_string()),
+                };
+                match sent {
+                    Ok(response) if response.status().is_success() => {
+                        tracing::debug!(
+                            "✓ Notified {} to leave room ({:?})",
+                            request.service_identity,
+                            reason
+                        );
+                    }
+                    Ok(response) => tracing::warn!(
+                        "⚠ Service {} answered leave notification with {}",
+                        request.service_identity,
+                        response.status()
+                    ),
+                    Err(e) => tracing::warn!(
+                        "⚠ Failed to notify {} to leave room: {}",
+                        request.service_identity,
+                        e
+                    ),
+                }
+            }
+        });
+        futures::future::join_all(notifications).await;
+    }
+
     /// Disconnect from LiveKit room
     pub async fn disconnect_from_livekit(&mut self) -> Result<()> {
         tracing::debug!("Disconnecting session {} from LiveKit", self.id);
@@ -700,11 +962,14 @@ impl Session {
                 Ok(_) => tracing::debug!("  ✓ Room connection closed successfully"),
                 Err(e) => tracing::warn!("  ⚠ Error closing room connection: {}", e),
             }
+
+            // Free the observer slot even if other clones still hold the connection
+            connection.permit.take();
         } else {
             tracing::debug!("  No active room connection found");
         }
 
-        self.update_status(SessionStatus::Terminated);
+        self.transition_to(SessionStatus::Terminated)?;
         tracing::info!(
             "✓ Session {} disconnected from LiveKit and terminated",
             self.id
@@ -712,3 +977,61 @@ impl Session {
         Ok(())
     }
 }
+
+/// POST `body` to a service at `url`, encoded in `format`
+fn payload_request<T: Serialize>(
+    client: &reqwest::Client,
+    url: &str,
+    format: PayloadFormat,
+    body: &T,
+) -> std::result::Result<reqwest::RequestBuilder, PayloadError> {
+    Ok(client
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, format.content_type())
+        .header(reqwest::header::ACCEPT, format.content_type())
+        .body(format.encode(body)?))
+}
+
+/// Decode a service's response according to its `Content-Type`, JSON when it names no
+/// known format
+async fn decode_response<T: DeserializeOwned>(response: reqwest::Response) -> anyhow::Result<T> {
+    let format = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(PayloadFormat::from_content_type)
+        .unwrap_or_default();
+    let body = response.bytes().await?;
+    Ok(format.decode(&body)?)
+}
+
+/// Rejoin the room behind `connection` after a transient disconnect, with backoff
+///
+/// On success the new room replaces the dropped one and its events are returned.
+async fn rejoin_room(
+    ws_url: &str,
+    room_token: &str,
+    connection: &RwLock<SessionRoomConnection>,
+) -> Option<tokio::sync::mpsc::UnboundedReceiver<RoomEvent>> {
+    let mut delay = ROOM_RECONNECT_DELAY;
+    for attempt in 1..=ROOM_RECONNECT_ATTEMPTS {
+        tokio::time::sleep(delay).await;
+        match Room::connect(ws_url, room_token, RoomOptions::default()).await {
+            Ok((room, events)) => {
+                tracing::info!("✓ Rejoined LiveKit room after {} attempt(s)", attempt);
+                connection.write().await.room = room;
+                return Some(events);
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Rejoin attempt {}/{} failed: {}",
+                    attempt,
+                    ROOM_RECONNECT_ATTEMPTS,
+                    e
+                );
+                delay *= 2;
+            }
+        }
+    }
+    None
+}
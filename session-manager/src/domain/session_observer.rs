@@ -0,0 +1,465 @@
+use crate::domain::identity::IdentityScheme;
+use crate::domain::microservice::LeaveReason;
+use crate::domain::participant::ParticipantRole;
+use crate::domain::session::SessionStatus;
+use crate::events::{EventBus, SessionEvent};
+use futures::{Stream, StreamExt};
+use livekit::prelude::{DisconnectReason, RoomEvent};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::ops::ControlFlow;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+pub use session_protocol::DisconnectAction;
+
+/// How often [`run_session_observer`] calls [`SessionObserver::on_tick`] for the session room
+pub const OBSERVER_TICK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How long participants may stay silent before [`LifecycleObserver`] gives up on them
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct ParticipantTimeouts {
+    /// A connected client silent for longer than this ends the session
+    pub client_timeout_secs: u64,
+    /// A joined service silent for longer than this is considered gone
+    pub service_timeout_secs: u64,
+}
+
+impl Default for ParticipantTimeouts {
+    fn default() -> Self {
+        Self {
+            client_timeout_secs: 60,
+            service_timeout_secs: 60,
+        }
+    }
+}
+
+impl ParticipantTimeouts {
+    pub fn client_timeout(&self) -> Duration {
+        Duration::from_secs(self.client_timeout_secs)
+    }
+
+    pub fn service_timeout(&self) -> Duration {
+        Duration::from_secs(self.service_timeout_secs)
+    }
+}
+
+/// Room activity relevant to the session, stripped of LiveKit handles so observers can be
+/// fed synthetic events
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ObservedEvent {
+    ParticipantJoined {
+        identity: String,
+        metadata: String,
+    },
+    ParticipantLeft {
+        identity: String,
+        metadata: String,
+    },
+    /// The connection to the room dropped
+    Disconnected {
+        reason: DisconnectReason,
+    },
+    /// Any other room event; proves the participants are still around
+    Activity,
+}
+
+impl From<RoomEvent> for ObservedEvent {
+    fn from(event: RoomEvent) -> Self {
+        match event {
+            RoomEvent::ParticipantConnected(participant) => ObservedEvent::ParticipantJoined {
+                identity: participant.identity().to_string(),
+                metadata: participant.metadata(),
+            },
+            RoomEvent::ParticipantDisconnected(participant) => ObservedEvent::ParticipantLeft {
+                identity: participant.identity().to_string(),
+                metadata: participant.metadata(),
+            },
+            RoomEvent::Disconnected { reason } => ObservedEvent::Disconnected { reason },
+            _ => ObservedEvent::Activity,
+        }
+    }
+}
+
+/// How the session manager handles losing its room connection for `reason`
+///
+/// Reasons that mean the room or our place in it is gone for good terminate the session;
+/// server restarts, migrations and signalling failures are worth rejoining after.
+pub fn disconnect_action(reason: DisconnectReason) -> DisconnectAction {
+    match reason {
+        DisconnectReason::ClientInitiated
+        | DisconnectReason::DuplicateIdentity
+        | DisconnectReason::ParticipantRemoved
+        | DisconnectReason::RoomDeleted
+        | DisconnectReason::RoomClosed
+        | DisconnectReason::UserUnavailable
+        | DisconnectReason::UserRejected
+        | DisconnectReason::SipTrunkFailure => DisconnectAction::Terminate,
+        DisconnectReason::UnknownReason
+        | DisconnectReason::ServerShutdown
+        | DisconnectReason::StateMismatch
+        | DisconnectReason::JoinFailure
+        | DisconnectReason::Migration
+        | DisconnectReason::SignalClose => DisconnectAction::Reconnect,
+    }
+}
+
+/// Why [`run_session_observer`] returned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObserverExit {
+    /// The observer broke out of the loop
+    Stopped,
+    /// The room event stream ended
+    Closed,
+    /// The room connection dropped; the caller should act on the observer's decision
+    Disconnected(DisconnectAction),
+}
+
+/// Monitoring policy for a session's room
+///
+/// Every callback returns `ControlFlow::Break` to stop observing the room.
+pub trait SessionObserver: Send {
+    fn on_participant_joined(&mut self, identity: &str, metadata: &str) -> ControlFlow<()>;
+    fn on_participant_left(&mut self, identity: &str, metadata: &str) -> ControlFlow<()>;
+    fn on_activity(&mut self) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+    /// Called periodically to check timeouts
+    fn on_tick(&mut self, now: Instant) -> ControlFlow<()>;
+    /// The connection to the room dropped; decides whether to rejoin it
+    fn on_disconnected(&mut self, reason: DisconnectReason) -> DisconnectAction {
+        disconnect_action(reason)
+    }
+    /// The room event stream ended
+    fn on_closed(&mut self) {}
+}
+
+/// Feed `events` and periodic ticks to `observer` until it breaks, the room connection
+/// drops or the stream ends
+pub async fn run_session_observer<O, S>(
+    observer: &mut O,
+    mut events: S,
+    tick_interval: Duration,
+) -> ObserverExit
+where
+    O: SessionObserver + ?Sized,
+    S: Stream<Item = ObservedEvent> + Unpin,
+{
+    let mut ticker = tokio::time::interval(tick_interval);
+
+    loop {
+        let flow = tokio::select! {
+            event = events.next() => match event {
+                Some(ObservedEvent::ParticipantJoined { identity, metadata }) => {
+                    observer.on_participant_joined(&identity, &metadata)
+                }
+                Some(ObservedEvent::ParticipantLeft { identity, metadata }) => {
+                    observer.on_participant_left(&identity, &metadata)
+                }
+                Some(ObservedEvent::Disconnected { reason }) => {
+                    return ObserverExit::Disconnected(observer.on_disconnected(reason));
+                }
+                Some(ObservedEvent::Activity) => observer.on_activity(),
+                None => {
+                    observer.on_closed();
+                    return ObserverExit::Closed;
+                }
+            },
+            _ = ticker.tick() => observer.on_tick(Instant::now()),
+        };
+
+        if flow.is_break() {
+            return ObserverExit::Stopped;
+        }
+    }
+}
+
+/// Default policy: tracks services and the client joining, publishes the matching session
+/// events, reports a full room and ends the session when a connected client times out
+pub struct LifecycleObserver {
+    session_id: String,
+    expected_services: HashSet<String>,
+    /// Joined services that make the session ready, see [`Self::with_ready_quorum`]
+    ready_quorum: usize,
+    event_bus: Arc<EventBus>,
+    identities: IdentityScheme,
+    timeouts: ParticipantTimeouts,
+    /// Room participant limit, 0 for none
+    max_participants: u32,
+    /// Everyone else in the room; the observer itself also takes a slot
+    participants: HashSet<String>,
+    room_full: bool,
+    /// Only count services the session notified, see [`Self::with_identity_verification`]
+    verify_identities: bool,
+    /// Participants that claimed the service role under an identity the session never notified
+    unexpected_services: HashSet<String>,
+    joined_services: HashSet<String>,
+    client_connected: bool,
+    client_last_seen: Instant,
+    service_last_seen: HashMap<String, Instant>,
+}
+
+impl LifecycleObserver {
+    pub fn new(
+        session_id: String,
+        expected_services: HashSet<String>,
+        event_bus: Arc<EventBus>,
+        identities: IdentityScheme,
+        timeouts: ParticipantTimeouts,
+        max_participants: u32,
+    ) -> Self {
+        Self {
+            session_id,
+            ready_quorum: expected_services.len(),
+            expected_services,
+            event_bus,
+            identities,
+            timeouts,
+            max_participants,
+            participants: HashSet::new(),
+            room_full: false,
+            verify_identities: true,
+            unexpected_services: HashSet::new(),
+            joined_services: HashSet::new(),
+            client_connected: false,
+            client_last_seen: Instant::now(),
+            service_last_seen: HashMap::new(),
+        }
+    }
+
+    /// Whether to check that a participant classified as a service is one the session
+    /// notified (on by default)
+    ///
+    /// A token's metadata can claim the service role under any identity. With verification
+    /// such participants are logged and never counted towards readiness.
+    pub fn with_identity_verification(mut self, verify: bool) -> Self {
+        self.verify_identities = verify;
+        self
+    }
+
+    /// Announce the session ready once `quorum` services joined instead of all of them
+    pub fn with_ready_quorum(mut self, quorum: usize) -> Self {
+        self.ready_quorum = quorum.min(self.expected_services.len());
+        self
+    }
+
+    /// Participants that joined as a service under an identity the session never notified
+    pub fn unexpected_services(&self) -> &HashSet<String> {
+        &self.unexpected_services
+    }
+
+    /// Services currently in the room
+    pub fn joined_services(&self) -> &HashSet<String> {
+        &self.joined_services
+    }
+
+    pub fn client_connected(&self) -> bool {
+        self.client_connected
+    }
+
+    fn role(&self, identity: &str, metadata: &str) -> ParticipantRole {
+        ParticipantRole::from_participant(
+            identity,
+            metadata,
+            &self.session_id,
+            &self.expected_services,
+            &self.identities,
+        )
+    }
+
+    fn publish(&self, event: SessionEvent) {
+        self.event_bus.publish_to_session(&self.session_id, event);
+    }
+
+    /// Publish `RoomFull` once each time the room reaches its limit
+    fn update_occupancy(&mut self) {
+        let occupied = self.participants.len() + 1;
+        let full = self.max_participants > 0 && occupied >= self.max_participants as usize;
+        if full && !self.room_full {
+            tracing::warn!(
+                "Room of session {} is full ({} participants)",
+                self.session_id,
+                occupied
+            );
+            self.publish(SessionEvent::RoomFull {
+                session_id: self.session_id.clone(),
+                max_participants: self.max_participants,
+            });
+        }
+        self.room_full = full;
+    }
+}
+
+impl SessionObserver for LifecycleObserver {
+    fn on_participant_joined(&mut self, identity: &str, metadata: &str) -> ControlFlow<()> {
+        self.participants.insert(identity.to_string());
+        self.update_occupancy();
+
+        match self.role(identity, metadata) {
+            ParticipantRole::Service
+                if self.verify_identities && !self.expected_services.contains(identity) =>
+            {
+                tracing::warn!(
+                    "Participant {} joined session {} as a service it never notified, ignoring",
+                    identity,
+                    self.session_id
+                );
+                self.unexpected_services.insert(identity.to_string());
+            }
+            ParticipantRole::Service => {
+                self.service_last_seen
+                    .insert(identity.to_string(), Instant::now());
+                if !self.joined_services.insert(identity.to_string()) {
+                    tracing::info!(
+                        "Microservice {} reconnected to session {}",
+                        identity,
+                        self.session_id
+                    );
+                    return ControlFlow::Continue(());
+                }
+
+                tracing::info!(
+                    "Microservice {} joined session {}",
+                    identity,
+                    self.session_id
+                );
+                // Deduplicated against the service's own join acknowledgement
+                self.event_bus.announce_service_joined(
+                    &self.session_id,
+                    identity,
+                    self.ready_quorum,
+                );
+            }
+            ParticipantRole::Client => {
+                self.client_connected = true;
+                self.client_last_seen = Instant::now();
+                tracing::info!("Client {} joined session {}", identity, self.session_id);
+
+                self.publish(SessionEvent::ClientJoined {
+                    session_id: self.session_id.clone(),
+                    user_identity: identity.to_string(),
+                });
+            }
+            ParticipantRole::Manager => {}
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn on_participant_left(&mut self, identity: &str, metadata: &str) -> ControlFlow<()> {
+        self.participants.remove(identity);
+        self.update_occupancy();
+
+        if self.joined_services.contains(identity) {
+            // Keep the service as joined until it times out
+            tracing::warn!(
+                "Microservice {} disconnected from session {}",
+                identity,
+                self.session_id
+            );
+        } else if self.role(identity, metadata) == ParticipantRole::Client {
+            self.client_connected = false;
+            tracing::info!(
+                "Client {} disconnected from session {}",
+                identity,
+                self.session_id
+            );
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn on_activity(&mut self) -> ControlFlow<()> {
+        let now = Instant::now();
+        self.client_last_seen = now;
+        for service in &self.joined_services {
+            self.service_last_seen.insert(service.clone(), now);
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn on_tick(&mut self, now: Instant) -> ControlFlow<()> {
+        if self.client_connected
+            && now.duration_since(self.client_last_seen) > self.timeouts.client_timeout()
+        {
+            tracing::warn!(
+                "Client timeout for session {} - terminating session",
+                self.session_id
+            );
+            self.publish(SessionEvent::ClientTimedOut {
+                session_id: self.session_id.clone(),
+            });
+            self.publish(SessionEvent::SessionStatusChanged {
+                session_id: self.session_id.clone(),
+                status: SessionStatus::Terminating,
+                reason: Some(LeaveReason::Timeout),
+            });
+            return ControlFlow::Break(());
+        }
+
+        let mut services_to_retry = Vec::new();
+        for service in &self.expected_services {
+            match self.service_last_seen.get(service) {
+                Some(last_seen)
+                    if now.duration_since(*last_seen) > self.timeouts.service_timeout() =>
+                {
+                    tracing::warn!(
+                        "Service {} timeout in session {} - will retry",
+                        service,
+                        self.session_id
+                    );
+                    // Forget it entirely so the timeout is reported once
+                    self.service_last_seen.remove(service);
+                    self.joined_services.remove(service);
+                    self.publish(SessionEvent::ServiceTimedOut {
+                        session_id: self.session_id.clone(),
+                        service_id: service.clone(),
+                    });
+                    services_to_retry.push(service.clone());
+                }
+                Some(_) => {}
+                None if !self.joined_services.contains(service) => {
+                    // Service never joined
+                    services_to_retry.push(service.clone());
+                }
+                None => {}
+            }
+        }
+
+        if !services_to_retry.is_empty() {
+            tracing::info!(
+                "Retrying {} services for session {}",
+                services_to_retry.len(),
+                self.session_id
+            );
+            // TODO: Re-send join notifications to the services that timed out
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn on_disconnected(&mut self, reason: DisconnectReason) -> DisconnectAction {
+        let action = disconnect_action(reason);
+        tracing::warn!(
+            "Disconnected from the room of session {} ({:?}), will {:?}",
+            self.session_id,
+            reason,
+            action
+        );
+        self.publish(SessionEvent::RoomDisconnected {
+            session_id: self.session_id.clone(),
+            reason: format!("{:?}", reason),
+            action,
+        });
+        if action == DisconnectAction::Terminate {
+            self.publish(SessionEvent::SessionStatusChanged {
+                session_id: self.session_id.clone(),
+                status: SessionStatus::Terminating,
+                reason: None,
+            });
+        }
+        action
+    }
+
+    fn on_closed(&mut self) {
+        tracing::warn!("Room event stream closed for session {}", self.session_id);
+    }
+}
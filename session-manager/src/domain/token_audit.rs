@@ -0,0 +1,110 @@
+use crate::domain::participant::ParticipantRole;
+use chrono::{DateTime, Utc};
+use livekit_api::access_token::VideoGrants;
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Who a LiveKit token was minted for; never contains the token itself
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TokenAuditRecord {
+    pub session_id: String,
+    pub identity: String,
+    pub role: ParticipantRole,
+    pub room: String,
+    /// Names of the grants the token carries, e.g. `room_join`, `can_publish`
+    pub grants: Vec<&'static str>,
+    pub ttl_secs: u64,
+    pub issued_at: DateTime<Utc>,
+}
+
+impl TokenAuditRecord {
+    pub fn new(
+        session_id: &str,
+        identity: &str,
+        role: ParticipantRole,
+        grants: &VideoGrants,
+        ttl: Duration,
+    ) -> Self {
+        Self {
+            session_id: session_id.to_string(),
+            identity: identity.to_string(),
+            role,
+            room: grants.room.clone(),
+            grants: grant_names(grants),
+            ttl_secs: ttl.as_secs(),
+            issued_at: Utc::now(),
+        }
+    }
+}
+
+fn grant_names(grants: &VideoGrants) -> Vec<&'static str> {
+    [
+        (grants.room_create, "room_create"),
+        (grants.room_list, "room_list"),
+        (grants.room_record, "room_record"),
+        (grants.room_admin, "room_admin"),
+        (grants.room_join, "room_join"),
+        (grants.can_publish, "can_publish"),
+        (grants.can_subscribe, "can_subscribe"),
+        (grants.can_publish_data, "can_publish_data"),
+        (grants.can_update_own_metadata, "can_update_own_metadata"),
+        (grants.ingress_admin, "ingress_admin"),
+        (grants.hidden, "hidden"),
+        (grants.recorder, "recorder"),
+    ]
+    .into_iter()
+    .filter_map(|(granted, name)| granted.then_some(name))
+    .collect()
+}
+
+/// Receives an audit record for every token the session manager mints
+pub trait TokenAuditSink: Send + Sync {
+    fn record(&self, record: &TokenAuditRecord);
+}
+
+/// Default sink: one structured `token_audit` log event per token
+#[derive(Debug, Default)]
+pub struct LogTokenAuditSink;
+
+impl TokenAuditSink for LogTokenAuditSink {
+    fn record(&self, record: &TokenAuditRecord) {
+        tracing::info!(
+            target: "token_audit",
+            session_id = %record.session_id,
+            identity = %record.identity,
+            role = ?record.role,
+            room = %record.room,
+            grants = %record.grants.join(","),
+            ttl_secs = record.ttl_secs,
+            issued_at = %record.issued_at.to_rfc3339(),
+            "LiveKit token issued"
+        );
+    }
+}
+
+/// Shared handle to the configured [`TokenAuditSink`], logging by default
+#[derive(Clone)]
+pub struct TokenAudit(Arc<dyn TokenAuditSink>);
+
+impl TokenAudit {
+    pub fn new(sink: Arc<dyn TokenAuditSink>) -> Self {
+        Self(sink)
+    }
+
+    pub fn record(&self, record: &TokenAuditRecord) {
+        self.0.record(record);
+    }
+}
+
+impl Default for TokenAudit {
+    fn default() -> Self {
+        Self::new(Arc::new(LogTokenAuditSink))
+    }
+}
+
+impl std::fmt::Debug for TokenAudit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("TokenAudit")
+    }
+}
@@ -0,0 +1,106 @@
+use crate::config::LiveKitConfig;
+use crate::domain::participant::ParticipantRole;
+use async_trait::async_trait;
+use livekit_api::access_token::{AccessToken, VideoGrants};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A token to issue for one participant of a session's room
+#[derive(Debug, Clone)]
+pub struct TokenRequest {
+    pub session_id: String,
+    pub identity: String,
+    pub role: ParticipantRole,
+    /// Grants the token must carry; `grants.room` is the session's room
+    pub grants: VideoGrants,
+    /// Participant metadata JSON the token must carry so the observer can tell roles apart
+    pub metadata: String,
+    pub ttl: Duration,
+}
+
+/// Issues the LiveKit access tokens handed out by the session manager
+///
+/// Implement this to obtain tokens from an external auth service instead of signing them
+/// with the API key and secret in [`LiveKitConfig`].
+#[async_trait]
+pub trait TokenProvider: Send + Sync {
+    /// Token for the end user connecting to the session
+    async fn client_token(&self, request: &TokenRequest) -> anyhow::Result<String>;
+    /// Token sent to a microservice with its join request
+    async fn service_token(&self, request: &TokenRequest) -> anyhow::Result<String>;
+    /// Token the session manager's own observer connects with
+    async fn manager_token(&self, request: &TokenRequest) -> anyhow::Result<String>;
+}
+
+/// Default provider: signs every token locally with a LiveKit API key and secret
+#[derive(Debug, Clone)]
+pub struct ApiKeyTokenProvider {
+    api_key: String,
+    api_secret: String,
+}
+
+impl ApiKeyTokenProvider {
+    pub fn new(api_key: &str, api_secret: &str) -> Self {
+        Self {
+            api_key: api_key.to_string(),
+            api_secret: api_secret.to_string(),
+        }
+    }
+
+    fn sign(&self, request: &TokenRequest) -> anyhow::Result<String> {
+        let token = AccessToken::with_api_key(&self.api_key, &self.api_secret)
+            .with_identity(&request.identity)
+            .with_metadata(&request.metadata)
+            .with_grants(request.grants.clone())
+            .with_ttl(request.ttl)
+            .to_jwt()?;
+        Ok(token)
+    }
+}
+
+#[async_trait]
+impl TokenProvider for ApiKeyTokenProvider {
+    async fn client_token(&self, request: &TokenRequest) -> anyhow::Result<String> {
+        self.sign(request)
+    }
+
+    async fn service_token(&self, request: &TokenRequest) -> anyhow::Result<String> {
+        self.sign(request)
+    }
+
+    async fn manager_token(&self, request: &TokenRequest) -> anyhow::Result<String> {
+        self.sign(request)
+    }
+}
+
+/// Shared handle to the configured [`TokenProvider`]
+///
+/// Unset, tokens are signed with the API key and secret of the [`LiveKitConfig`] they
+/// are issued under. The key is still needed for the room service API either way.
+#[derive(Clone, Default)]
+pub struct TokenIssuer(Option<Arc<dyn TokenProvider>>);
+
+impl TokenIssuer {
+    pub fn new(provider: Arc<dyn TokenProvider>) -> Self {
+        Self(Some(provider))
+    }
+
+    pub fn provider(&self, config: &LiveKitConfig) -> Arc<dyn TokenProvider> {
+        match &self.0 {
+            Some(provider) => provider.clone(),
+            None => Arc::new(ApiKeyTokenProvider::new(
+                &config.api_key,
+                &config.api_secret,
+            )),
+        }
+    }
+}
+
+impl std::fmt::Debug for TokenIssuer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            Some(_) => f.write_str("TokenIssuer(custom)"),
+            None => f.write_str("TokenIssuer(api_key)"),
+        }
+    }
+}
@@ -1,108 +1,344 @@
-use crate::domain::SessionStatus;
+use crate::domain::ParticipantRole;
+use crate::services::EventSink;
+use crate::storage::SessionStorage;
+use chrono::{DateTime, Utc};
 use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
-use tokio::sync::broadcast;
+use std::collections::{HashSet, VecDeque};
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+use tokio::sync::{broadcast, mpsc};
 
+/// 每个会话最多暂存的无人接收事件数，超出后丢弃最旧的事件
+const PENDING_EVENTS_CAPACITY: usize = 100;
+
+// 事件类型与 SDK 共享，保证两端的序列化格式一致
+pub use session_protocol::{SequencedEvent, SessionEvent};
+
+pub type EventSender = broadcast::Sender<SequencedEvent>;
+pub type EventReceiver = broadcast::Receiver<SequencedEvent>;
+
+/// 事件历史中的一条记录：会话事件及其发布时间、会话内序号
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(tag = "type")]
-pub enum SessionEvent {
-    SessionCreated {
-        session_id: String,
-        room_name: String,
-        access_token: String,
-        livekit_url: String,
-    },
-    MicroserviceJoined {
-        session_id: String,
-        service_id: String,
-    },
-    ClientJoined {
-        session_id: String,
-        user_identity: String,
-    },
-    SessionReady {
-        session_id: String,
-        all_participants_joined: bool,
-    },
-    SessionStatusChanged {
-        session_id: String,
-        status: SessionStatus,
-    },
-    Error {
-        session_id: String,
-        message: String,
-    },
+pub struct RecordedEvent {
+    pub timestamp: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seq: Option<u64>,
+    #[serde(flatten)]
+    pub event: SessionEvent,
+}
+
+impl RecordedEvent {
+    fn now(event: &SequencedEvent) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            seq: event.seq,
+            event: event.event.clone(),
+        }
+    }
+}
+
+/// 事件订阅：先补发订阅前无人接收而暂存的事件，再接收实时事件
+#[derive(Debug)]
+pub struct EventSubscription {
+    pub pending: Vec<SequencedEvent>,
+    pub receiver: EventReceiver,
+}
+
+impl From<EventReceiver> for EventSubscription {
+    fn from(receiver: EventReceiver) -> Self {
+        Self {
+            pending: Vec::new(),
+            receiver,
+        }
+    }
 }
 
-pub type EventSender = broadcast::Sender<SessionEvent>;
-pub type EventReceiver = broadcast::Receiver<SessionEvent>;
+// 会话事件广播，以及发布时没有任何订阅者的事件
+#[derive(Debug)]
+struct SessionChannel {
+    sender: EventSender,
+    // 下一个发布事件的序号，从 1 开始
+    next_seq: u64,
+    pending: VecDeque<SequencedEvent>,
+    // 已确认加入的微服务，用于对房间事件与显式确认去重
+    joined_services: HashSet<String>,
+    ready_announced: bool,
+}
 
+/// 会话事件总线
+///
+/// 顺序保证：同一会话的事件按发布顺序获得连续递增的序号 `seq`（从 1 开始），并在持有该会话
+/// 通道锁时依次送入事件历史、外部接收端、会话事件流和全局事件流，因此同一会话的事件在任一
+/// 订阅中都按序号递增的顺序出现，序号不连续说明订阅者漏收了事件（如处理过慢被跳过）。不同
+/// 会话的事件之间没有全局顺序；发往未知会话的事件和 `publish_global` 发布的事件没有序号。
 #[derive(Clone, Debug)]
 pub struct EventBus {
     // 全局事件广播
     global_sender: EventSender,
     // 每个会话的事件广播
-    session_senders: Arc<DashMap<String, EventSender>>,
+    session_channels: Arc<DashMap<String, SessionChannel>>,
+    // 未能送达而丢失的事件数（含订阅者处理过慢被跳过的事件）
+    dropped_events: Arc<AtomicU64>,
+    // 每个会话事件广播通道的容量
+    session_capacity: usize,
+    // 事件历史写入队列，由后台任务按发布顺序写入存储；未启用时为 None
+    history: Option<mpsc::UnboundedSender<(String, RecordedEvent)>>,
+    // 外部事件接收端（如 NATS）的转发队列，每个接收端由各自的后台任务按发布顺序投递
+    sinks: Vec<SinkQueue>,
+}
+
+// 发往单个事件接收端的有界队列；队列已满时丢弃事件，不阻塞发布者
+#[derive(Clone, Debug)]
+struct SinkQueue {
+    name: String,
+    sender: mpsc::Sender<RecordedEvent>,
 }
 
 impl EventBus {
     pub fn new() -> Self {
-        let (global_sender, _) = broadcast::channel(1000);
+        Self::with_capacity(1000, 100)
+    }
+
+    /// 指定全局与会话广播通道容量创建事件总线；容量必须大于 0
+    pub fn with_capacity(global_capacity: usize, session_capacity: usize) -> Self {
+        let (global_sender, _) = broadcast::channel(global_capacity);
         Self {
             global_sender,
-            session_senders: Arc::new(DashMap::new()),
+            session_channels: Arc::new(DashMap::new()),
+            dropped_events: Arc::new(AtomicU64::new(0)),
+            session_capacity,
+            history: None,
+            sinks: Vec::new(),
         }
     }
 
+    /// 将发布到会话的事件连同发布时间写入 `storage`，每个会话最多保留 `capacity` 条
+    ///
+    /// 写入在后台任务中按发布顺序进行，必须在 Tokio 运行时内调用。
+    pub fn with_history(mut self, storage: Arc<dyn SessionStorage>, capacity: usize) -> Self {
+        let (sender, mut receiver) = mpsc::unbounded_channel::<(String, RecordedEvent)>();
+        let channels = self.session_channels.clone();
+        tokio::spawn(async move {
+            while let Some((session_id, event)) = receiver.recv().await {
+                // 会话事件流已清理且会话已从存储删除时丢弃，避免为已删除的会话重建历史
+                if !channels.contains_key(&session_id)
+                    && !matches!(storage.get_session(&session_id).await, Ok(Some(_)))
+                {
+                    continue;
+                }
+                if let Err(e) = storage.append_event(&session_id, event, capacity).await {
+                    tracing::warn!("Failed to record event of session {}: {}", session_id, e);
+                }
+            }
+        });
+        self.history = Some(sender);
+        self
+    }
+
+    /// 将发布的所有事件（会话事件与全局事件）转发到 `sink`
+    ///
+    /// 转发不阻塞发布者：事件先进入容量为 `buffer` 的队列，由后台任务逐条投递；接收端处理不过来、
+    /// 队列已满时事件被丢弃并计入丢弃数。投递失败只记录告警。必须在 Tokio 运行时内调用。
+    pub fn with_sink(mut self, sink: Arc<dyn EventSink>, buffer: usize) -> Self {
+        let (sender, mut receiver) = mpsc::channel::<RecordedEvent>(buffer);
+        let name = sink.name().to_string();
+        tokio::spawn(async move {
+            while let Some(event) = receiver.recv().await {
+                if let Err(e) = sink.send(&event).await {
+                    tracing::warn!(
+                        "Failed to forward {} event to sink {}: {}",
+                        event.event.event_name(),
+                        sink.name(),
+                        e
+                    );
+                }
+            }
+        });
+        self.sinks.push(SinkQueue { name, sender });
+        self
+    }
+
     /// 创建会话特定的事件流
     pub fn create_session_stream(&self, session_id: String) -> EventReceiver {
-        let (sender, receiver) = broadcast::channel(100);
-        self.session_senders.insert(session_id, sender);
+        let (sender, receiver) = broadcast::channel(self.session_capacity);
+        self.session_channels.insert(
+            session_id,
+            SessionChannel {
+                sender,
+                next_seq: 1,
+                pending: VecDeque::new(),
+                joined_services: HashSet::new(),
+                ready_announced: false,
+            },
+        );
         receiver
     }
 
-    /// 获取会话特定的事件流
-    pub fn get_session_stream(&self, session_id: &str) -> Option<EventReceiver> {
-        self.session_senders
-            .get(session_id)
-            .map(|sender| sender.subscribe())
+    /// 订阅会话事件流，并取走此前无人接收的暂存事件
+    pub fn get_session_stream(&self, session_id: &str) -> Option<EventSubscription> {
+        // 持有条目写锁，保证暂存事件与实时事件之间既不重复也不遗漏
+        self.session_channels
+            .get_mut(session_id)
+            .map(|mut channel| {
+                let receiver = channel.sender.subscribe();
+                EventSubscription {
+                    pending: channel.pending.drain(..).collect(),
+                    receiver,
+                }
+            })
     }
 
-    /// 发布事件到特定会话
+    /// 发布事件到特定会话，并为其分配会话内序号
+    ///
+    /// 没有订阅者时事件会暂存，交给下一个订阅者；暂存区满或会话不存在时计入丢弃数。
     pub fn publish_to_session(&self, session_id: &str, event: SessionEvent) {
-        if let Some(sender) = self.session_senders.get(session_id) {
-            let _ = sender.send(event.clone());
+        match self.session_channels.get_mut(session_id) {
+            Some(mut channel) => {
+                // 以下全部在持有条目锁时完成，保证各订阅中同一会话的事件按序号顺序出现
+                let event = SequencedEvent {
+                    seq: Some(channel.next_seq),
+                    event,
+                };
+                channel.next_seq += 1;
+                if let Some(history) = &self.history {
+                    let _ = history.send((session_id.to_string(), RecordedEvent::now(&event)));
+                }
+                self.forward_to_sinks(&event);
+                let _ = self.global_sender.send(event.clone());
+                if let Err(broadcast::error::SendError(event)) = channel.sender.send(event) {
+                    if channel.pending.len() >= PENDING_EVENTS_CAPACITY {
+                        channel.pending.pop_front();
+                        self.record_dropped(1);
+                        tracing::warn!(
+                            "Pending event buffer full for session {}, dropped oldest event",
+                            session_id
+                        );
+                    }
+                    channel.pending.push_back(event);
+                }
+            }
+            None => {
+                self.record_dropped(1);
+                tracing::debug!(
+                    "Dropped {} event for unknown session {}",
+                    event.event_name(),
+                    session_id
+                );
+                // 仍发布到全局流，但不属于任何会话事件流，没有序号
+                self.publish_global(event);
+            }
+        }
+    }
+
+    /// 将事件放入各事件接收端的队列
+    fn forward_to_sinks(&self, event: &SequencedEvent) {
+        if self.sinks.is_empty() {
+            return;
+        }
+        let recorded = RecordedEvent::now(event);
+        for sink in &self.sinks {
+            if let Err(mpsc::error::TrySendError::Full(_)) = sink.sender.try_send(recorded.clone())
+            {
+                self.record_dropped(1);
+                tracing::warn!(
+                    "Event sink {} is falling behind, dropped {} event",
+                    sink.name,
+                    event.event.event_name()
+                );
+            }
         }
-        // 同时发布到全局流
-        let _ = self.global_sender.send(event);
+    }
+
+    /// 确认微服务已加入会话
+    ///
+    /// 房间事件、加入通知的成功响应和 service-ready 调用都可以证明微服务已加入，先到者生效：
+    /// 每个微服务只发布一次 `MicroserviceJoined`，确认加入的微服务达到 `expected_services`
+    /// 个（会话的就绪法定数量）后发布一次 `SessionReady`。
+    pub fn announce_service_joined(
+        &self,
+        session_id: &str,
+        service_id: &str,
+        expected_services: usize,
+    ) {
+        // 发布前释放条目锁，publish_to_session 需要再次获取
+        let joined = {
+            let Some(mut channel) = self.session_channels.get_mut(session_id) else {
+                return;
+            };
+            if !channel.joined_services.insert(service_id.to_string()) {
+                return;
+            }
+            channel.joined_services.len()
+        };
+
+        self.publish_to_session(
+            session_id,
+            SessionEvent::MicroserviceJoined {
+                session_id: session_id.to_string(),
+                service_id: service_id.to_string(),
+            },
+        );
+        if joined >= expected_services {
+            self.announce_session_ready(session_id);
+        }
+    }
+
+    /// 发布 `SessionReady`，每个会话只发布一次；返回本次是否发布
+    pub fn announce_session_ready(&self, session_id: &str) -> bool {
+        let first = match self.session_channels.get_mut(session_id) {
+            Some(mut channel) => !std::mem::replace(&mut channel.ready_announced, true),
+            None => false,
+        };
+        if first {
+            self.publish_to_session(
+                session_id,
+                SessionEvent::SessionReady {
+                    session_id: session_id.to_string(),
+                    all_participants_joined: true,
+                },
+            );
+        }
+        first
+    }
+
+    /// 记录未能送达的事件，例如订阅者处理过慢而被跳过的事件
+    pub fn record_dropped(&self, count: u64) {
+        self.dropped_events.fetch_add(count, Ordering::Relaxed);
+    }
+
+    /// 累计丢弃的事件数
+    pub fn dropped_events(&self) -> u64 {
+        self.dropped_events.load(Ordering::Relaxed)
     }
 
     /// 发布全局事件
     pub fn publish_global(&self, event: SessionEvent) {
+        let event = SequencedEvent { seq: None, event };
+        self.forward_to_sinks(&event);
         let _ = self.global_sender.send(event);
     }
 
-    /// 发布参与者加入事件
+    /// 发布参与者加入事件，角色由调用方通过 `ParticipantRole::classify` 确定
     pub async fn publish_participant_joined(
         &self,
         session_id: &str,
         participant_identity: &str,
+        role: ParticipantRole,
     ) -> Result<(), crate::utils::errors::SessionManagerError> {
-        let event = if participant_identity.starts_with("session-manager-") {
+        let event = match role {
             // 忽略会话管理器自己的加入事件
-            return Ok(());
-        } else if participant_identity.contains("user") || !participant_identity.contains("service")
-        {
-            SessionEvent::ClientJoined {
+            ParticipantRole::Manager => return Ok(()),
+            ParticipantRole::Client => SessionEvent::ClientJoined {
                 session_id: session_id.to_string(),
                 user_identity: participant_identity.to_string(),
-            }
-        } else {
-            SessionEvent::MicroserviceJoined {
+            },
+            ParticipantRole::Service => SessionEvent::MicroserviceJoined {
                 session_id: session_id.to_string(),
                 service_id: participant_identity.to_string(),
-            }
+            },
         };
 
         self.publish_to_session(session_id, event);
@@ -126,7 +362,9 @@ impl EventBus {
 
     /// 清理会话事件流
     pub fn cleanup_session(&self, session_id: &str) {
-        self.session_senders.remove(session_id);
+        if let Some((_, channel)) = self.session_channels.remove(session_id) {
+            self.record_dropped(channel.pending.len() as u64);
+        }
     }
 
     /// 获取全局事件流
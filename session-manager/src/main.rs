@@ -1,43 +1,60 @@
 use anyhow::Result;
-use session_manager::{config::AppConfig, server::Server};
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use session_manager::{
+    config::AppConfig,
+    server::{build_runtime, Server},
+    utils::logging::{
+        default_filter_directive, fmt_layer, parse_filter_directive, LogFormat, LogLevelHandle,
+    },
+};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 
-#[tokio::main]
-async fn main() -> Result<()> {
+fn main() -> Result<()> {
     // 加载配置
     let config = AppConfig::load()?;
+    config.validate()?;
 
-    // 初始化日志
-    let env_filter = tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| {
-        format!("session_manager={},tower_http=info,livekit={},livekit_api={}",
-                config.logging.level, config.logging.level, config.logging.level).into()
-    });
+    // 按配置的工作线程数构建运行时
+    let runtime = build_runtime(&config.server)?;
+    runtime.block_on(run(config))
+}
+
+async fn run(config: AppConfig) -> Result<()> {
+    // 初始化日志（过滤层可通过管理接口热更新）
+    let directive = std::env::var(EnvFilter::DEFAULT_ENV)
+        .ok()
+        .filter(|directive| parse_filter_directive(directive).is_ok())
+        .unwrap_or_else(|| default_filter_directive(&config.logging.level));
+    let (env_filter, log_level) = LogLevelHandle::new(&directive)?;
 
-    let fmt_layer = tracing_subscriber::fmt::layer()
-        .with_target(true)
-        .with_line_number(true);
+    let log_format: LogFormat = config.logging.format.parse()?;
+    let fmt_layer = fmt_layer(log_format, std::io::stdout);
 
     // 如果启用了 Vector 日志，添加 Vector 层
     if config.vector_log.enabled {
         // Extract host:port from endpoint URL
         let vector_addr = if config.vector_log.endpoint.starts_with("http://") {
-            config.vector_log.endpoint.strip_prefix("http://").unwrap_or(&config.vector_log.endpoint)
+            config
+                .vector_log
+                .endpoint
+                .strip_prefix("http://")
+                .unwrap_or(&config.vector_log.endpoint)
         } else {
             &config.vector_log.endpoint
         };
-        
-        let vector_layer = tracing_vector::VectorLayer::new(
-            &config.vector_log.source_name,
-            vector_addr,
-        );
-        
+
+        let vector_layer =
+            tracing_vector::VectorLayer::new(&config.vector_log.source_name, vector_addr);
+
         tracing_subscriber::registry()
             .with(env_filter)
             .with(fmt_layer)
             .with(vector_layer)
             .init();
-            
-        tracing::info!("Vector logging initialized successfully to: {}", vector_addr);
+
+        tracing::info!(
+            "Vector logging initialized successfully to: {}",
+            vector_addr
+        );
     } else {
         tracing_subscriber::registry()
             .with(env_filter)
@@ -48,7 +65,7 @@ async fn main() -> Result<()> {
     tracing::info!("Starting session manager with config: {:?}", config);
 
     // 启动服务器
-    let server = Server::new(config).await?;
+    let server = Server::with_log_level(config, Some(log_level)).await?;
     server.run().await?;
 
     Ok(())
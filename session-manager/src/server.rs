@@ -1,44 +1,134 @@
 use axum::{
-    routing::{get, post},
+    routing::{get, patch, post},
     Router,
 };
-use std::sync::Arc;
-use tokio::net::TcpListener;
+use socket2::{Domain, Socket, Type};
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::{atomic::AtomicBool, Arc};
+use tokio::{net::TcpListener, sync::watch};
 use tower::ServiceBuilder;
 use tower_http::{cors::CorsLayer, trace::TraceLayer};
 
 use crate::{
-    api::handlers,
-    config::AppConfig,
-    services::{microservice_registry::MicroserviceRegistry, session_service::SessionServiceImpl},
+    api::{handlers, streams},
+    config::{AppConfig, ServerConfig},
+    domain::NotifyRetryPolicy,
+    services::{
+        event_sink::NatsSink, microservice_registry::MicroserviceRegistry,
+        rate_limiter::RateLimiter, service_discovery::StaticDiscovery,
+        session_service::SessionServiceImpl, session_sweeper::SessionSweeper,
+    },
     storage::memory::MemoryStorage,
-    utils::errors::Result,
+    utils::{
+        errors::{Result, SessionManagerError},
+        logging::LogLevelHandle,
+    },
 };
 
+/// 按配置构建 Tokio 运行时；未配置 `workers` 时使用默认线程数（CPU 核数）
+pub fn build_runtime(config: &ServerConfig) -> Result<tokio::runtime::Runtime> {
+    let mut builder = tokio::runtime::Builder::new_multi_thread();
+    builder.enable_all();
+
+    if let Some(workers) = config.workers {
+        if workers == 0 {
+            return Err(SessionManagerError::Configuration(
+                "server.workers must be greater than 0".to_string(),
+            ));
+        }
+        builder.worker_threads(workers);
+    }
+
+    builder
+        .build()
+        .map_err(|e| SessionManagerError::Configuration(format!("Failed to build runtime: {}", e)))
+}
+
+/// 创建监听套接字：开启 SO_REUSEADDR，使部署重启时不会因旧连接处于 TIME_WAIT 而绑定失败
+pub fn bind_listener(addr: SocketAddr, backlog: u32) -> std::io::Result<TcpListener> {
+    let socket = Socket::new(Domain::for_address(addr), Type::STREAM, None)?;
+    socket.set_reuse_address(true)?;
+    socket.set_nonblocking(true)?;
+    socket.bind(&addr.into())?;
+    socket.listen(backlog.min(i32::MAX as u32) as i32)?;
+    TcpListener::from_std(socket.into())
+}
+
 pub struct Server {
     config: AppConfig,
     app: Router,
+    shutdown: watch::Sender<bool>,
 }
 
 impl Server {
     pub async fn new(config: AppConfig) -> Result<Self> {
+        Self::with_log_level(config, None).await
+    }
+
+    /// 创建服务器；提供 `log_level` 时启用运行时日志级别调整接口
+    pub async fn with_log_level(
+        config: AppConfig,
+        log_level: Option<LogLevelHandle>,
+    ) -> Result<Self> {
+        config.validate()?;
+
         // 创建存储
-        let storage = Arc::new(MemoryStorage::new());
+        let storage = Arc::new(MemoryStorage::with_history_retention(
+            std::time::Duration::from_secs(config.streams.history_retention_secs),
+        ));
 
         // 创建事件总线
-        let event_bus = crate::events::EventBus::new();
+        let mut event_bus = crate::events::EventBus::with_capacity(
+            config.streams.global_channel_capacity,
+            config.streams.session_channel_capacity,
+        );
+        if config.streams.history_capacity > 0 {
+            event_bus = event_bus.with_history(storage.clone(), config.streams.history_capacity);
+        }
+        if config.nats.enabled {
+            let sink = NatsSink::connect(&config.nats).await?;
+            event_bus = event_bus.with_sink(Arc::new(sink), config.nats.buffer);
+        }
 
         // 创建微服务注册表
-        let microservice_registry = Arc::new(MicroserviceRegistry::new());
+        let mut microservice_registry = MicroserviceRegistry::new();
+        if !config.discovery.static_services.is_empty() {
+            microservice_registry = microservice_registry
+                .with_discovery_source(Arc::new(StaticDiscovery::from_config(&config.discovery)));
+        }
+        let microservice_registry = Arc::new(microservice_registry);
 
         // 创建会话服务
-        let session_service = Arc::new(SessionServiceImpl::new(
-            storage,
-            microservice_registry.clone(),
-            config.livekit.clone(),
-            config.livekit.server_url.clone(),
-            event_bus.clone(),
-        ));
+        let session_service = Arc::new(
+            SessionServiceImpl::new(
+                storage.clone(),
+                microservice_registry.clone(),
+                config.livekit.clone(),
+                config.livekit.server_url.clone(),
+                event_bus.clone(),
+                SessionServiceImpl::build_http_client()?,
+                NotifyRetryPolicy::from(&config.microservices),
+            )
+            .with_max_sessions(config.server.max_sessions)
+            .with_metadata_limits(config.metadata_limits.clone()),
+        );
+
+        // 定期清理无人加入的废弃会话
+        if config.sweeper.enabled {
+            let sweeper = Arc::new(SessionSweeper::new(
+                session_service.clone(),
+                config.sweeper.clone(),
+            ));
+            sweeper.spawn_sweep_task();
+        }
+
+        // 创建会话创建限流器
+        let rate_limiter = Arc::new(RateLimiter::new(config.rate_limit.clone()));
+        rate_limiter.spawn_cleanup_task();
+
+        // 关闭信号：置为 true 后事件流发送 close 事件并结束
+        let (shutdown, shutdown_rx) = watch::channel(false);
 
         // 创建应用状态
         let app_state = handlers::AppState {
@@ -46,16 +136,73 @@ impl Server {
             microservice_registry,
             config: config.clone(),
             event_bus,
+            rate_limiter,
+            storage,
+            log_level,
+            shutdown: shutdown_rx,
+            draining: Arc::new(AtomicBool::new(false)),
         };
 
         // 构建路由
         let app = Router::new()
             .route("/health", get(handlers::health_check))
+            .route("/ready", get(handlers::readiness_check))
             .route(
                 "/api/v1/microservices/register",
                 post(handlers::register_microservice),
             )
+            .route(
+                "/api/v1/microservices/health",
+                get(handlers::microservices_health),
+            )
             .route("/api/v1/create-session", post(handlers::create_session))
+            .route("/api/v1/sessions", get(handlers::list_sessions))
+            .route(
+                "/api/v1/sessions/validate",
+                post(handlers::validate_session),
+            )
+            .route(
+                "/api/v1/sessions/{session_id}",
+                get(handlers::get_session_status).delete(handlers::delete_session),
+            )
+            .route(
+                "/api/v1/sessions/{session_id}/events/history",
+                get(handlers::get_session_event_history),
+            )
+            .route(
+                "/api/v1/sessions/{session_id}/metadata",
+                patch(handlers::update_session_metadata),
+            )
+            .route(
+                "/api/v1/sessions/{session_id}/refresh-token",
+                post(handlers::refresh_token),
+            )
+            .route(
+                "/api/v1/sessions/{session_id}/leave",
+                post(handlers::leave_session),
+            )
+            .route(
+                "/api/v1/sessions/{session_id}/service-ready",
+                post(handlers::service_ready),
+            )
+            .route(
+                "/api/v1/debug/sessions/export",
+                get(handlers::export_sessions),
+            )
+            .route("/api/v1/admin/log-level", post(handlers::set_log_level))
+            .route(
+                "/api/v1/admin/terminate-all",
+                post(handlers::terminate_all_sessions),
+            )
+            .route("/api/v1/admin/drain", post(handlers::drain))
+            .route("/api/v1/admin/undrain", post(handlers::undrain))
+            .route("/events", get(streams::global_events_stream))
+            .route("/ws", get(streams::global_events_ws))
+            .route(
+                "/sessions/{session_id}/events",
+                get(streams::session_events_stream),
+            )
+            .route("/sessions/{session_id}/ws", get(streams::session_events_ws))
             .with_state(app_state)
             .layer(
                 ServiceBuilder::new()
@@ -63,18 +210,47 @@ impl Server {
                     .layer(CorsLayer::permissive()),
             );
 
-        Ok(Self { config, app })
+        Ok(Self {
+            config,
+            app,
+            shutdown,
+        })
     }
 
+    /// 运行服务器，收到 Ctrl+C 后优雅关闭
     pub async fn run(self) -> Result<()> {
+        self.run_until(async {
+            if let Err(e) = tokio::signal::ctrl_c().await {
+                tracing::error!("Failed to listen for shutdown signal: {}", e);
+                std::future::pending::<()>().await;
+            }
+        })
+        .await
+    }
+
+    /// 运行服务器直到 `signal` 完成，随后优雅关闭：
+    /// 先通知 SSE 事件流发送 close 事件并结束，再等待进行中的请求完成
+    pub async fn run_until(self, signal: impl Future<Output = ()> + Send + 'static) -> Result<()> {
         let addr = format!("{}:{}", self.config.server.host, self.config.server.port);
         tracing::info!("Starting server on {}", addr);
 
-        let listener = TcpListener::bind(&addr)
+        let socket_addr = tokio::net::lookup_host(&addr)
             .await
-            .map_err(|e| crate::utils::errors::SessionManagerError::Internal(e.into()))?;
+            .map_err(|e| SessionManagerError::Internal(e.into()))?
+            .next()
+            .ok_or_else(|| {
+                SessionManagerError::Configuration(format!("Cannot resolve address {}", addr))
+            })?;
+        let listener = bind_listener(socket_addr, self.config.server.backlog)
+            .map_err(|e| SessionManagerError::Internal(e.into()))?;
 
+        let shutdown = self.shutdown;
         axum::serve(listener, self.app)
+            .with_graceful_shutdown(async move {
+                signal.await;
+                tracing::info!("Shutting down, closing event streams");
+                shutdown.send_replace(true);
+            })
             .await
             .map_err(|e| crate::utils::errors::SessionManagerError::Internal(e.into()))?;
 
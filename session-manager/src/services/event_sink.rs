@@ -0,0 +1,90 @@
+use crate::{
+    config::NatsSinkConfig,
+    events::{RecordedEvent, SessionEvent},
+    utils::errors::{Result, SessionManagerError},
+};
+use async_trait::async_trait;
+
+/// Destination for session events beyond the SSE/WebSocket streams, e.g. a message bus
+///
+/// Register sinks with [`crate::events::EventBus::with_sink`]; each one receives every
+/// published event, in publish order, from its own background task.
+#[async_trait]
+pub trait EventSink: Send + Sync + std::fmt::Debug {
+    /// Name used in logs
+    fn name(&self) -> &str;
+
+    /// Deliver one event
+    async fn send(&self, event: &RecordedEvent) -> Result<()>;
+}
+
+/// Publishes events as JSON to NATS, configured by the `[nats]` config section
+///
+/// Each event goes to `{subject_prefix}.{session_id}.{event_name}`, or
+/// `{subject_prefix}.global.{event_name}` for events not tied to a session, so consumers
+/// can subscribe to e.g. `session-manager.events.*.session_ready`.
+#[derive(Debug, Clone)]
+pub struct NatsSink {
+    client: async_nats::Client,
+    subject_prefix: String,
+}
+
+impl NatsSink {
+    pub fn new(client: async_nats::Client, subject_prefix: String) -> Self {
+        Self {
+            client,
+            subject_prefix,
+        }
+    }
+
+    /// Connect to the configured NATS server
+    ///
+    /// Does not wait for the server to be reachable: the client keeps reconnecting in the
+    /// background and buffers events published in the meantime.
+    pub async fn connect(config: &NatsSinkConfig) -> Result<Self> {
+        let client = async_nats::ConnectOptions::new()
+            .name("session-manager")
+            .retry_on_initial_connect()
+            .connect(config.url.as_str())
+            .await
+            .map_err(|e| {
+                SessionManagerError::Configuration(format!(
+                    "Failed to connect to NATS at {}: {}",
+                    config.url, e
+                ))
+            })?;
+        Ok(Self::new(client, config.subject_prefix.clone()))
+    }
+
+    /// Subject `event` is published on
+    pub fn subject(&self, event: &SessionEvent) -> String {
+        event_subject(&self.subject_prefix, event)
+    }
+}
+
+#[async_trait]
+impl EventSink for NatsSink {
+    fn name(&self) -> &str {
+        "nats"
+    }
+
+    async fn send(&self, event: &RecordedEvent) -> Result<()> {
+        let payload =
+            serde_json::to_vec(event).map_err(|e| SessionManagerError::EventSink(e.to_string()))?;
+        self.client
+            .publish(self.subject(&event.event), payload.into())
+            .await
+            .map_err(|e| SessionManagerError::EventSink(e.to_string()))
+    }
+}
+
+/// `{prefix}.{session_id}.{event_name}`, with `global` in place of the session for events
+/// not tied to one
+pub fn event_subject(prefix: &str, event: &SessionEvent) -> String {
+    format!(
+        "{}.{}.{}",
+        prefix,
+        event.session_id().unwrap_or("global"),
+        event.event_name()
+    )
+}
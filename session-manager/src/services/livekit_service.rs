@@ -1,16 +1,156 @@
 use crate::{
     config::LiveKitConfig,
+    domain::{IdentityScheme, ParticipantMetadata, ParticipantRole},
     events::EventBus,
     utils::errors::{Result, SessionManagerError},
 };
 use livekit::prelude::*;
 use livekit_api::{
     access_token::{AccessToken, VideoGrants},
-    services::room::{CreateRoomOptions, RoomClient},
+    services::{
+        room::{CreateRoomOptions, RoomClient},
+        ServiceError, ServiceResult, TwirpError, TwirpErrorCode,
+    },
+};
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+    sync::Arc,
+    time::Duration,
 };
-use std::{collections::HashMap, sync::Arc, time::Duration};
 use tokio::sync::RwLock;
 
+/// Delay before the first retry of a room API call; doubled for every further retry
+const ROOM_API_RETRY_BASE_DELAY: Duration = Duration::from_millis(200);
+
+/// Run a LiveKit room API call with the configured timeout, retrying transient failures.
+///
+/// Transport errors, timeouts and `unavailable`/`internal`/`deadline_exceeded` responses are
+/// retried up to `api_max_attempts`; anything else fails immediately. A timeout surfaces as
+/// a `deadline_exceeded` [`SessionManagerError::LiveKit`].
+pub async fn call_room_api<T, F, Fut>(
+    config: &LiveKitConfig,
+    operation: &str,
+    mut call: F,
+) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = ServiceResult<T>>,
+{
+    let timeout = Duration::from_millis(config.api_timeout_ms);
+    let max_attempts = config.api_max_attempts.max(1);
+    let mut attempt = 1;
+
+    loop {
+        let error = match tokio::time::timeout(timeout, call()).await {
+            Ok(Ok(value)) => return Ok(value),
+            Ok(Err(e)) => e,
+            Err(_) => ServiceError::Twirp(TwirpError::Twirp(TwirpErrorCode {
+                code: TwirpErrorCode::DEADLINE_EXCEEDED.to_string(),
+                msg: format!("{} timed out after {:?}", operation, timeout),
+            })),
+        };
+
+        if attempt >= max_attempts || !is_transient(&error) {
+            return Err(SessionManagerError::LiveKit(error));
+        }
+
+        let delay = ROOM_API_RETRY_BASE_DELAY * 2u32.saturating_pow(attempt - 1);
+        tracing::warn!(
+            "LiveKit {} failed (attempt {}/{}): {} - retrying in {:?}",
+            operation,
+            attempt,
+            max_attempts,
+            error,
+            delay
+        );
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+    }
+}
+
+/// Whether a room API call failed because the room is already present on the server
+///
+/// Clients that supply an explicit `room_name` and retry hit this; the room is usable.
+pub fn is_room_already_exists(error: &SessionManagerError) -> bool {
+    matches!(
+        error,
+        SessionManagerError::LiveKit(ServiceError::Twirp(TwirpError::Twirp(code)))
+            if code.code == TwirpErrorCode::ALREADY_EXISTS
+    )
+}
+
+/// Fail with [`SessionManagerError::RoomFull`] if `room_name` already holds
+/// `max_participants` participants
+///
+/// `reconnecting` names a participant whose new connection replaces its old one, so it is
+/// not counted. A room that does not exist yet has space. Other failures to list the
+/// participants are only logged: handing out a token must not depend on LiveKit answering.
+pub async fn check_room_capacity(
+    config: &LiveKitConfig,
+    room_name: &str,
+    reconnecting: Option<&str>,
+) -> Result<()> {
+    if config.max_participants == 0 {
+        return Ok(());
+    }
+
+    let api_url = config.api_url();
+    let room_client = RoomClient::with_api_key(&api_url, &config.api_key, &config.api_secret);
+
+    let participants = match call_room_api(config, "list_participants", || {
+        room_client.list_participants(room_name)
+    })
+    .await
+    {
+        Ok(participants) => participants,
+        Err(SessionManagerError::LiveKit(ServiceError::Twirp(TwirpError::Twirp(code))))
+            if code.code == TwirpErrorCode::NOT_FOUND =>
+        {
+            return Ok(());
+        }
+        Err(e) => {
+            tracing::warn!(
+                "Could not check capacity of room {}: {} - assuming it has space",
+                room_name,
+                e
+            );
+            return Ok(());
+        }
+    };
+
+    let occupied = participants
+        .iter()
+        .filter(|participant| Some(participant.identity.as_str()) != reconnecting)
+        .count();
+    if occupied >= config.max_participants as usize {
+        tracing::warn!(
+            "Room {} is full ({}/{} participants)",
+            room_name,
+            occupied,
+            config.max_participants
+        );
+        return Err(SessionManagerError::RoomFull {
+            room_name: room_name.to_string(),
+            max_participants: config.max_participants,
+        });
+    }
+    Ok(())
+}
+
+fn is_transient(error: &ServiceError) -> bool {
+    match error {
+        ServiceError::Twirp(TwirpError::Request(_)) => true,
+        ServiceError::Twirp(TwirpError::Twirp(code)) => matches!(
+            code.code.as_str(),
+            TwirpErrorCode::UNAVAILABLE
+                | TwirpErrorCode::INTERNAL
+                | TwirpErrorCode::DEADLINE_EXCEEDED
+        ),
+        _ => false,
+    }
+}
+
 #[derive(Debug)]
 pub struct LiveKitService {
     room_client: RoomClient,
@@ -27,15 +167,7 @@ struct RoomConnection {
 
 impl LiveKitService {
     pub fn new(config: LiveKitConfig, event_bus: Arc<EventBus>) -> Self {
-        // RoomClient expects HTTP URL for API calls - ensure we use HTTP scheme
-        let api_url = if config.server_url.starts_with("ws://") {
-            config.server_url.replace("ws://", "http://")
-        } else if config.server_url.starts_with("wss://") {
-            config.server_url.replace("wss://", "https://")
-        } else {
-            // Already HTTP/HTTPS, use as-is
-            config.server_url.clone()
-        };
+        let api_url = config.api_url();
 
         let room_client = RoomClient::with_api_key(&api_url, &config.api_key, &config.api_secret);
 
@@ -50,16 +182,21 @@ impl LiveKitService {
     pub async fn create_room(&self, room_name: &str) -> Result<()> {
         let options = CreateRoomOptions {
             empty_timeout: 300, // 5 minutes
-            max_participants: 50,
+            max_participants: self.config.max_participants,
             ..Default::default()
         };
 
-        self.room_client
-            .create_room(room_name, options)
-            .await
-            .map_err(SessionManagerError::LiveKit)?;
-
-        tracing::info!("Created LiveKit room: {}", room_name);
+        match call_room_api(&self.config, "create_room", || {
+            self.room_client.create_room(room_name, options.clone())
+        })
+        .await
+        {
+            Ok(_) => tracing::info!("Created LiveKit room: {}", room_name),
+            Err(e) if is_room_already_exists(&e) => {
+                tracing::info!("LiveKit room {} already exists, reusing it", room_name)
+            }
+            Err(e) => return Err(e),
+        }
         Ok(())
     }
 
@@ -90,16 +227,21 @@ impl LiveKitService {
     }
 
     pub async fn delete_room(&self, room_name: &str) -> Result<()> {
-        self.room_client
-            .delete_room(room_name)
-            .await
-            .map_err(SessionManagerError::LiveKit)?;
+        call_room_api(&self.config, "delete_room", || {
+            self.room_client.delete_room(room_name)
+        })
+        .await?;
 
         tracing::info!("Deleted LiveKit room: {}", room_name);
         Ok(())
     }
 
-    pub async fn join_room_as_manager(&self, room_name: &str, session_id: &str) -> Result<()> {
+    pub async fn join_room_as_manager(
+        &self,
+        room_name: &str,
+        session_id: &str,
+        service_ids: HashSet<String>,
+    ) -> Result<()> {
         // 检查是否已经连接到这个房间
         if self.room_connections.read().await.contains_key(room_name) {
             tracing::debug!("Already connected to room: {}", room_name);
@@ -107,19 +249,22 @@ impl LiveKitService {
         }
 
         // 会话管理器作为特殊参与者加入房间来管理生命周期
-        let manager_identity = format!("session-manager-{}", session_id);
+        let manager_identity = self.config.identity.manager(session_id);
         let video_grants = VideoGrants {
             room_join: true,
             room: room_name.to_string(),
-            room_admin: true,    // 管理员权限
-            can_publish: true,   // 允许发布以建立连接
-            can_subscribe: true, // 允许订阅以建立连接
-            hidden: true,        // 对其他参与者隐藏
+            room_admin: true,                    // 管理员权限
+            can_publish: true,                   // 允许发布以建立连接
+            can_subscribe: true,                 // 允许订阅以建立连接
+            hidden: self.config.hidden_observer, // 默认对其他参与者隐藏
             ..Default::default()
         };
 
         let token = AccessToken::with_api_key(&self.config.api_key, &self.config.api_secret)
             .with_identity(&manager_identity)
+            .with_metadata(
+                &ParticipantMetadata::new(ParticipantRole::Manager, session_id).to_json(),
+            )
             .with_grants(video_grants)
             .with_ttl(Duration::from_secs(3600 * 24)) // 24 hours
             .to_jwt()
@@ -149,11 +294,19 @@ impl LiveKitService {
         let event_bus = self.event_bus.clone();
         let room_name_clone = room_name.to_string();
         let session_id_clone = session_id.to_string();
+        let identities = self.config.identity.clone();
 
         let event_handle = tokio::spawn(async move {
             while let Some(event) = event_rx.recv().await {
-                Self::handle_room_event(event, &event_bus, &room_name_clone, &session_id_clone)
-                    .await;
+                Self::handle_room_event(
+                    event,
+                    &event_bus,
+                    &room_name_clone,
+                    &session_id_clone,
+                    &service_ids,
+                    &identities,
+                )
+                .await;
             }
             tracing::info!("Room event listener stopped for room: {}", room_name_clone);
         });
@@ -177,6 +330,8 @@ impl LiveKitService {
         event_bus: &Arc<EventBus>,
         room_name: &str,
         session_id: &str,
+        service_ids: &HashSet<String>,
+        identities: &IdentityScheme,
     ) {
         match event {
             RoomEvent::ParticipantConnected(participant) => {
@@ -185,7 +340,17 @@ impl LiveKitService {
 
                 // 发布参与者加入事件
                 if let Err(e) = event_bus
-                    .publish_participant_joined(session_id, &identity)
+                    .publish_participant_joined(
+                        session_id,
+                        &identity,
+                        ParticipantRole::from_participant(
+                            &identity,
+                            &participant.metadata(),
+                            session_id,
+                            service_ids,
+                            identities,
+                        ),
+                    )
                     .await
                 {
                     tracing::error!("Failed to publish participant joined event: {}", e);
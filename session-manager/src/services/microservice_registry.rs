@@ -1,22 +1,98 @@
 use crate::{
     domain::{MicroserviceInfo, ServiceStatus},
+    services::service_discovery::DiscoverySource,
     utils::errors::Result,
 };
 use dashmap::DashMap;
+use futures::stream::{self, StreamExt};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Upper bound for a single `/health` probe so the summary never hangs on a dead service
+pub const HEALTH_PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// How many services are probed at the same time
+pub const HEALTH_PROBE_CONCURRENCY: usize = 8;
+
+/// Outcome of probing a microservice's `/health` endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceHealthStatus {
+    /// Answered with a success status
+    Healthy,
+    /// Answered, but with an error status
+    Unhealthy,
+    /// Did not answer in time or refused the connection
+    Unreachable,
+}
+
+/// Health of one microservice
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceHealth {
+    pub status: ServiceHealthStatus,
+    /// Time until the service answered or the probe gave up
+    pub latency_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
 
 #[derive(Debug)]
 pub struct MicroserviceRegistry {
     services: Arc<DashMap<String, MicroserviceInfo>>,
+    /// Round-robin position per service type for picking instances by type
+    next_pick: DashMap<String, usize>,
+    /// External sources of services that may not have registered themselves
+    discovery: Vec<Arc<dyn DiscoverySource>>,
+    /// Client for health probes
+    http_client: reqwest::Client,
 }
 
 impl MicroserviceRegistry {
     pub fn new() -> Self {
         Self {
             services: Arc::new(DashMap::new()),
+            next_pick: DashMap::new(),
+            discovery: Vec::new(),
+            http_client: reqwest::Client::new(),
         }
     }
 
+    /// Also offer the services found by `source` as available
+    ///
+    /// A self-registered service wins over a discovered one with the same id, so its
+    /// reported status (e.g. disconnected) is respected.
+    pub fn with_discovery_source(mut self, source: Arc<dyn DiscoverySource>) -> Self {
+        self.discovery.push(source);
+        self
+    }
+
+    /// Available services: the self-registered ones plus any discovered ones that have
+    /// not registered
+    async fn available_services(&self) -> Vec<MicroserviceInfo> {
+        let mut services: Vec<MicroserviceInfo> = self
+            .services
+            .iter()
+            .filter(|entry| entry.is_available())
+            .map(|entry| entry.clone())
+            .collect();
+
+        for source in &self.discovery {
+            match source.discover().await {
+                Ok(discovered) => services.extend(discovered.into_iter().filter(|service| {
+                    service.is_available() && !self.services.contains_key(&service.service_id)
+                })),
+                Err(e) => tracing::warn!("Service discovery via {:?} failed: {}", source, e),
+            }
+        }
+
+        // Several sources may list the same service; keep the first
+        let mut seen = HashSet::new();
+        services.retain(|service| seen.insert(service.service_id.clone()));
+        services
+    }
+
     pub async fn register_service(&self, service: MicroserviceInfo) -> Result<()> {
         tracing::info!("Registering microservice: {}", service.service_id);
         self.services.insert(service.service_id.clone(), service);
@@ -31,24 +107,47 @@ impl MicroserviceRegistry {
         &self,
         service_ids: &[String],
     ) -> Result<Vec<MicroserviceInfo>> {
-        let mut services = Vec::new();
-        for service_id in service_ids {
-            if let Some(service) = self.services.get(service_id) {
-                if service.is_available() {
-                    services.push(service.clone());
-                }
-            }
+        let available = self.available_services().await;
+        Ok(service_ids
+            .iter()
+            .filter_map(|service_id| {
+                available
+                    .iter()
+                    .find(|service| &service.service_id == service_id)
+                    .cloned()
+            })
+            .collect())
+    }
+
+    /// Pick an available instance registered with `service_type`, skipping the ids in
+    /// `exclude`. Instances are handed out round-robin to spread sessions over the pool.
+    pub async fn pick_service_by_type(
+        &self,
+        service_type: &str,
+        exclude: &HashSet<String>,
+    ) -> Result<Option<MicroserviceInfo>> {
+        let mut candidates: Vec<MicroserviceInfo> = self
+            .available_services()
+            .await
+            .into_iter()
+            .filter(|service| {
+                service.service_type() == Some(service_type)
+                    && !exclude.contains(&service.service_id)
+            })
+            .collect();
+        if candidates.is_empty() {
+            return Ok(None);
         }
-        Ok(services)
+        candidates.sort_by(|a, b| a.service_id.cmp(&b.service_id));
+
+        let mut next = self.next_pick.entry(service_type.to_string()).or_insert(0);
+        let index = *next % candidates.len();
+        *next = next.wrapping_add(1);
+        Ok(Some(candidates.swap_remove(index)))
     }
 
     pub async fn get_all_available_services(&self) -> Result<Vec<MicroserviceInfo>> {
-        Ok(self
-            .services
-            .iter()
-            .filter(|entry| entry.is_available())
-            .map(|entry| entry.clone())
-            .collect())
+        Ok(self.available_services().await)
     }
 
     pub async fn update_service_status(
@@ -80,6 +179,52 @@ impl MicroserviceRegistry {
     pub async fn get_service_count(&self) -> usize {
         self.services.len()
     }
+
+    /// Probe the `/health` endpoint of every registered service
+    ///
+    /// At most `concurrency` probes run at once and each gives up after `timeout`.
+    pub async fn check_health(
+        &self,
+        timeout: Duration,
+        concurrency: usize,
+    ) -> HashMap<String, ServiceHealth> {
+        let services: Vec<MicroserviceInfo> =
+            self.services.iter().map(|entry| entry.clone()).collect();
+
+        stream::iter(services)
+            .map(|service| async move {
+                let health = self.probe_health(&service.endpoint, timeout).await;
+                (service.service_id, health)
+            })
+            .buffer_unordered(concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    async fn probe_health(&self, endpoint: &str, timeout: Duration) -> ServiceHealth {
+        let url = format!("{}/health", endpoint.trim_end_matches('/'));
+        let started = Instant::now();
+        let result = self.http_client.get(&url).timeout(timeout).send().await;
+        let latency_ms = started.elapsed().as_millis() as u64;
+
+        let (status, message) = match result {
+            Ok(response) if response.status().is_success() => (ServiceHealthStatus::Healthy, None),
+            Ok(response) => (
+                ServiceHealthStatus::Unhealthy,
+                Some(format!("Health check returned {}", response.status())),
+            ),
+            Err(e) if e.is_timeout() => (
+                ServiceHealthStatus::Unreachable,
+                Some(format!("No answer within {:?}", timeout)),
+            ),
+            Err(e) => (ServiceHealthStatus::Unreachable, Some(e.to_string())),
+        };
+        ServiceHealth {
+            status,
+            latency_ms,
+            message,
+        }
+    }
 }
 
 impl Default for MicroserviceRegistry {
@@ -1,7 +1,19 @@
+pub mod event_sink;
 pub mod livekit_service;
 pub mod microservice_registry;
+pub mod observer_pool;
+pub mod rate_limiter;
+pub mod room_stats;
+pub mod service_discovery;
 pub mod session_service;
+pub mod session_sweeper;
 
+pub use event_sink::*;
 pub use livekit_service::*;
 pub use microservice_registry::*;
+pub use observer_pool::*;
+pub use rate_limiter::*;
+pub use room_stats::*;
+pub use service_discovery::*;
 pub use session_service::*;
+pub use session_sweeper::*;
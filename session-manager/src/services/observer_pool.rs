@@ -0,0 +1,113 @@
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Limits how many LiveKit rooms the session manager observes at the same time
+///
+/// Every observer connection holds an [`ObserverPermit`] for as long as it stays in the
+/// room; sessions created while the pool is exhausted wait for a permit before connecting.
+/// The pool also makes sure each session has at most one observer, see
+/// [`ObserverPool::claim`].
+#[derive(Debug, Clone)]
+pub struct ObserverPool {
+    semaphore: Arc<Semaphore>,
+    limit: usize,
+    observed: Arc<Mutex<HashSet<String>>>,
+}
+
+/// Slot in the [`ObserverPool`], released when dropped
+#[derive(Debug)]
+pub struct ObserverPermit {
+    _permit: OwnedSemaphorePermit,
+    _claim: Option<ObserverClaim>,
+}
+
+/// Exclusive right to observe one session, released when dropped
+#[derive(Debug)]
+pub struct ObserverClaim {
+    session_id: String,
+    observed: Arc<Mutex<HashSet<String>>>,
+}
+
+impl ObserverClaim {
+    pub fn session_id(&self) -> &str {
+        &self.session_id
+    }
+}
+
+impl Drop for ObserverClaim {
+    fn drop(&mut self) {
+        self.observed.lock().unwrap().remove(&self.session_id);
+    }
+}
+
+impl ObserverPermit {
+    /// Hold `claim` for as long as this permit, i.e. while the connection stays open
+    pub fn with_claim(mut self, claim: ObserverClaim) -> Self {
+        self._claim = Some(claim);
+        self
+    }
+}
+
+impl ObserverPool {
+    pub fn new(limit: usize) -> Self {
+        let limit = limit.max(1);
+        Self {
+            semaphore: Arc::new(Semaphore::new(limit)),
+            limit,
+            observed: Arc::default(),
+        }
+    }
+
+    /// Claim the right to connect an observer for `session_id`
+    ///
+    /// Returns `None` while another claim for the session is alive, so concurrent
+    /// connection attempts for the same session cannot both open a room connection.
+    pub fn claim(&self, session_id: &str) -> Option<ObserverClaim> {
+        let inserted = self.observed.lock().unwrap().insert(session_id.to_string());
+        inserted.then(|| ObserverClaim {
+            session_id: session_id.to_string(),
+            observed: self.observed.clone(),
+        })
+    }
+
+    /// Whether an observer for `session_id` is connected or connecting
+    pub fn is_observed(&self, session_id: &str) -> bool {
+        self.observed.lock().unwrap().contains(session_id)
+    }
+
+    /// Take a slot if one is free right now
+    pub fn try_acquire(&self) -> Option<ObserverPermit> {
+        self.semaphore
+            .clone()
+            .try_acquire_owned()
+            .ok()
+            .map(|permit| ObserverPermit {
+                _permit: permit,
+                _claim: None,
+            })
+    }
+
+    /// Wait for a free slot; waiters are served in FIFO order
+    pub async fn acquire(&self) -> ObserverPermit {
+        let permit = self
+            .semaphore
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("observer pool semaphore is never closed");
+        ObserverPermit {
+            _permit: permit,
+            _claim: None,
+        }
+    }
+
+    /// Number of observer connections currently open
+    pub fn in_use(&self) -> usize {
+        self.limit - self.semaphore.available_permits()
+    }
+
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+}
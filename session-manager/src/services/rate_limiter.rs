@@ -0,0 +1,84 @@
+use crate::config::RateLimitConfig;
+use dashmap::DashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Per-key token bucket rate limiter
+#[derive(Debug)]
+pub struct RateLimiter {
+    buckets: Arc<DashMap<String, TokenBucket>>,
+    config: RateLimitConfig,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            buckets: Arc::new(DashMap::new()),
+            config,
+        }
+    }
+
+    /// Take one token for `key`, or return how long to wait until one is available
+    pub fn check(&self, key: &str) -> std::result::Result<(), Duration> {
+        if !self.config.enabled {
+            return Ok(());
+        }
+
+        let now = Instant::now();
+        let burst = self.config.burst as f64;
+        let rate = self.config.requests_per_second;
+
+        let mut bucket = self
+            .buckets
+            .entry(key.to_string())
+            .or_insert_with(|| TokenBucket {
+                tokens: burst,
+                last_refill: now,
+            });
+
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(burst);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            let wait_secs = (1.0 - bucket.tokens) / rate;
+            Err(Duration::try_from_secs_f64(wait_secs).unwrap_or(Duration::MAX))
+        }
+    }
+
+    /// Drop buckets that have not been touched for the configured idle timeout
+    pub fn cleanup_idle(&self) {
+        let idle_timeout = Duration::from_secs(self.config.idle_timeout_secs);
+        let before = self.buckets.len();
+        self.buckets
+            .retain(|_, bucket| bucket.last_refill.elapsed() < idle_timeout);
+
+        let removed = before.saturating_sub(self.buckets.len());
+        if removed > 0 {
+            tracing::debug!("Removed {} idle rate limit buckets", removed);
+        }
+    }
+
+    /// Periodically clean up idle buckets in the background
+    pub fn spawn_cleanup_task(self: &Arc<Self>) -> tokio::task::JoinHandle<()> {
+        let limiter = Arc::clone(self);
+        let interval = Duration::from_secs(limiter.config.idle_timeout_secs.max(1));
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                limiter.cleanup_idle();
+            }
+        })
+    }
+}
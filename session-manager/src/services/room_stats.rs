@@ -0,0 +1,141 @@
+use crate::{
+    domain::{Session, SessionRoomConnection, SessionStatus},
+    events::{EventBus, SessionEvent},
+    storage::SessionStorage,
+    utils::errors::Result,
+};
+use async_trait::async_trait;
+use livekit::prelude::ConnectionQuality as LiveKitConnectionQuality;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+
+pub use session_protocol::{ConnectionQuality, ParticipantStats, RoomStats};
+
+/// Where a [`RoomStatsPoller`] reads a session's room stats from
+#[async_trait]
+pub trait RoomStatsSource: Send + Sync {
+    async fn room_stats(&self) -> Result<RoomStats>;
+}
+
+/// Reads stats from the participants the session manager's observer sees in the room
+pub struct ObserverRoomStats {
+    connection: Arc<RwLock<SessionRoomConnection>>,
+}
+
+impl ObserverRoomStats {
+    pub fn new(connection: Arc<RwLock<SessionRoomConnection>>) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait]
+impl RoomStatsSource for ObserverRoomStats {
+    async fn room_stats(&self) -> Result<RoomStats> {
+        let connection = self.connection.read().await;
+        let participants = connection
+            .room
+            .remote_participants()
+            .into_values()
+            .map(|participant| ParticipantStats {
+                identity: participant.identity().to_string(),
+                track_count: participant.track_publications().len() as u32,
+                connection_quality: connection_quality(participant.connection_quality()),
+            })
+            .collect();
+        Ok(RoomStats::from_participants(participants))
+    }
+}
+
+fn connection_quality(quality: LiveKitConnectionQuality) -> ConnectionQuality {
+    match quality {
+        LiveKitConnectionQuality::Excellent => ConnectionQuality::Excellent,
+        LiveKitConnectionQuality::Good => ConnectionQuality::Good,
+        LiveKitConnectionQuality::Poor => ConnectionQuality::Poor,
+        LiveKitConnectionQuality::Lost => ConnectionQuality::Lost,
+    }
+}
+
+/// Periodically caches a session's room stats on the stored session and publishes them
+/// as `stats` events
+#[derive(Clone)]
+pub struct RoomStatsPoller {
+    storage: Arc<dyn SessionStorage>,
+    event_bus: EventBus,
+    interval: Duration,
+}
+
+impl RoomStatsPoller {
+    pub fn new(storage: Arc<dyn SessionStorage>, event_bus: EventBus, interval: Duration) -> Self {
+        Self {
+            storage,
+            event_bus,
+            interval,
+        }
+    }
+
+    /// Poll `source` every interval until the session ends
+    pub fn spawn(
+        &self,
+        session_id: String,
+        source: Arc<dyn RoomStatsSource>,
+    ) -> tokio::task::JoinHandle<()> {
+        let poller = self.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(poller.interval).await;
+                match poller.poll_once(&session_id, source.as_ref()).await {
+                    Ok(true) => {}
+                    Ok(false) => break,
+                    Err(e) => {
+                        tracing::warn!(
+                            "Failed to collect room stats for session {}: {}",
+                            session_id,
+                            e
+                        );
+                    }
+                }
+            }
+            tracing::debug!("Stopped collecting room stats for session {}", session_id);
+        })
+    }
+
+    /// Take one snapshot, store it and publish it
+    ///
+    /// Returns `false` once the session is gone or terminating.
+    pub async fn poll_once(&self, session_id: &str, source: &dyn RoomStatsSource) -> Result<bool> {
+        if self.live_session(session_id).await?.is_none() {
+            return Ok(false);
+        }
+        let stats = source.room_stats().await?;
+
+        // Reload so the update does not overwrite changes made while collecting
+        let Some(mut session) = self.live_session(session_id).await? else {
+            return Ok(false);
+        };
+        session.room_stats = Some(stats.clone());
+        self.storage.update_session(&session).await?;
+
+        self.event_bus.publish_to_session(
+            session_id,
+            SessionEvent::Stats {
+                session_id: session_id.to_string(),
+                stats,
+            },
+        );
+        Ok(true)
+    }
+
+    async fn live_session(&self, session_id: &str) -> Result<Option<Session>> {
+        Ok(self
+            .storage
+            .get_session(session_id)
+            .await?
+            .filter(|session| {
+                !matches!(
+                    session.status,
+                    SessionStatus::Terminating | SessionStatus::Terminated
+                )
+            }))
+    }
+}
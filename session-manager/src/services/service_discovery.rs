@@ -0,0 +1,47 @@
+use crate::{config::DiscoveryConfig, domain::MicroserviceInfo, utils::errors::Result};
+use async_trait::async_trait;
+
+/// External source of microservices, consulted alongside self-registration
+///
+/// Lets sessions be assigned services that are known to exist (e.g. from deployment
+/// config or DNS) but have not registered with this session manager yet.
+#[async_trait]
+pub trait DiscoverySource: Send + Sync + std::fmt::Debug {
+    /// The services this source currently knows about
+    async fn discover(&self) -> Result<Vec<MicroserviceInfo>>;
+}
+
+/// A fixed list of services taken from the `[discovery]` config section
+#[derive(Debug, Clone)]
+pub struct StaticDiscovery {
+    services: Vec<MicroserviceInfo>,
+}
+
+impl StaticDiscovery {
+    pub fn new(services: Vec<MicroserviceInfo>) -> Self {
+        Self { services }
+    }
+
+    pub fn from_config(config: &DiscoveryConfig) -> Self {
+        Self::new(
+            config
+                .static_services
+                .iter()
+                .map(|service| {
+                    MicroserviceInfo::new(
+                        service.service_id.clone(),
+                        service.endpoint.clone(),
+                        service.metadata.clone(),
+                    )
+                })
+                .collect(),
+        )
+    }
+}
+
+#[async_trait]
+impl DiscoverySource for StaticDiscovery {
+    async fn discover(&self) -> Result<Vec<MicroserviceInfo>> {
+        Ok(self.services.clone())
+    }
+}
@@ -1,22 +1,138 @@
 use crate::{
-    config::LiveKitConfig,
-    domain::{Session, SessionStatus},
-    services::MicroserviceRegistry,
+    config::{LiveKitConfig, MetadataLimitConfig},
+    domain::{
+        Capabilities, LeaveReason, MicroserviceInfo, NotifyRetryPolicy, ParticipantRole, Session,
+        SessionStatus,
+    },
+    services::{
+        livekit_service::check_room_capacity, MicroserviceRegistry, ObserverPool,
+        ObserverRoomStats, RoomStatsPoller,
+    },
     storage::SessionStorage,
-    utils::errors::Result,
+    utils::{
+        errors::{Result, SessionManagerError},
+        validation,
+    },
 };
 use async_trait::async_trait;
+use futures::stream::{FuturesUnordered, StreamExt};
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
 use tracing::*;
 use uuid::Uuid;
 
 #[async_trait]
 pub trait SessionService: Send + Sync {
     async fn create_session(&self, request: CreateSessionRequest) -> Result<(Session, String)>;
+    /// Validate a creation request and resolve its services without creating a room,
+    /// minting tokens or storing anything
+    async fn validate_session(&self, request: &CreateSessionRequest) -> Result<SessionValidation>;
     async fn get_session(&self, session_id: &str) -> Result<Option<Session>>;
+    /// List sessions page by page, optionally only those in `status` or created by `owner`
+    async fn list_sessions(
+        &self,
+        status: Option<SessionStatus>,
+        owner: Option<&str>,
+        limit: usize,
+        cursor: Option<&str>,
+    ) -> Result<SessionPage>;
+    async fn leave_session(&self, session_id: &str, user_identity: &str) -> Result<Session>;
+    async fn mark_service_ready(&self, session_id: &str, service_id: &str) -> Result<Session>;
+    async fn update_metadata(
+        &self,
+        session_id: &str,
+        patch: HashMap<String, Option<String>>,
+    ) -> Result<Session>;
+    /// Mint a fresh client token for a session that has not been terminated
+    async fn refresh_client_token(&self, session_id: &str) -> Result<(Session, String)>;
+    /// Tear down the session's room, telling its services why they have to leave
+    async fn terminate_session(&self, session_id: &str, reason: LeaveReason) -> Result<Session>;
+    /// Tear down sessions older than `max_age` that are still waiting and have nobody in
+    /// the room, removing them from storage. Returns the ids of the removed sessions.
+    async fn sweep_abandoned_sessions(&self, max_age: std::time::Duration) -> Result<Vec<String>>;
+    /// Terminate live sessions that outlived their maximum lifetime, active or not, and
+    /// remove them from storage. A session's own `max_lifetime_secs` wins over
+    /// `default_max_lifetime`; sessions with neither never expire. Returns the removed ids.
+    async fn expire_sessions(
+        &self,
+        default_max_lifetime: Option<std::time::Duration>,
+    ) -> Result<Vec<String>>;
+    /// Remove sessions that have been `Terminated` for longer than `grace` from storage,
+    /// whichever way they ended. Returns the removed ids.
+    async fn purge_terminated_sessions(&self, grace: std::time::Duration) -> Result<Vec<String>>;
+    /// Terminate every stored session, delete its room and remove it from storage.
+    /// Returns how many sessions were still live; sessions that fail to terminate stay
+    /// in storage so a repeated call picks them up again.
+    async fn terminate_all_sessions(&self) -> Result<usize>;
+    async fn check_readiness(&self) -> Vec<DependencyStatus>;
 }
 
+/// Outcome of a dry-run session creation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionValidation {
+    /// Services the session would be created with
+    pub available_services: Vec<String>,
+    /// Requested service ids and types that are not registered or not available
+    pub missing_services: Vec<String>,
+}
+
+/// One page of sessions ordered by creation time
+#[derive(Debug, Clone)]
+pub struct SessionPage {
+    pub sessions: Vec<Session>,
+    /// Opaque cursor for the next page, `None` on the last page
+    pub next_cursor: Option<String>,
+}
+
+/// Announce that the session reached its ready quorum
+fn publish_session_ready(event_bus: &crate::events::EventBus, session_id: &str) {
+    event_bus.publish_to_session(
+        session_id,
+        crate::events::SessionEvent::SessionStatusChanged {
+            session_id: session_id.to_string(),
+            status: SessionStatus::Ready,
+            reason: None,
+        },
+    );
+    event_bus.announce_session_ready(session_id);
+}
+
+/// Sort key used for stable pagination: creation time, then id
+fn session_sort_key(session: &Session) -> (i64, &str) {
+    (session.created_at.timestamp_micros(), session.id.as_str())
+}
+
+fn encode_cursor(session: &Session) -> String {
+    format!("{}-{}", session.created_at.timestamp_micros(), session.id)
+}
+
+fn decode_cursor(cursor: &str) -> Result<(i64, String)> {
+    cursor
+        .split_once('-')
+        .and_then(|(micros, id)| Some((micros.parse().ok()?, id.to_string())))
+        .ok_or_else(|| SessionManagerError::InvalidRequest(format!("Invalid cursor: {}", cursor)))
+}
+
+/// Readiness of a single external dependency
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyStatus {
+    pub name: String,
+    pub healthy: bool,
+    pub message: Option<String>,
+}
+
+/// Timeouts for the shared microservice notification client
+const NOTIFY_CONNECT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+const NOTIFY_REQUEST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+const NOTIFY_POOL_IDLE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(90);
+
+/// Upper bound for a single dependency probe so `/ready` never hangs
+const READINESS_PROBE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(3);
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateSessionRequest {
     pub user_identity: String,
@@ -24,6 +140,29 @@ pub struct CreateSessionRequest {
     pub room_name: Option<String>,
     pub metadata: Option<std::collections::HashMap<String, String>>,
     pub required_services: Option<Vec<String>>,
+    /// Service types to fill with any available registered instance, one per entry
+    #[serde(default)]
+    pub required_service_types: Option<Vec<String>>,
+    /// Return the user's existing `Ready`/`Active` session instead of creating a new one
+    #[serde(default)]
+    pub reuse_existing: bool,
+    /// Connect the session manager to the room to watch services join; when `false` the
+    /// session relies on join acknowledgements and `service-ready` calls instead
+    #[serde(default = "default_observe")]
+    pub observe: bool,
+    /// Authenticated principal creating the session, recorded as its owner
+    #[serde(default)]
+    pub owner: Option<String>,
+    /// Maximum lifetime in seconds, overriding the sweeper's default
+    #[serde(default)]
+    pub max_lifetime_secs: Option<u64>,
+    /// Become `Ready` once this many services are ready instead of all of them
+    #[serde(default)]
+    pub min_ready_services: Option<usize>,
+}
+
+fn default_observe() -> bool {
+    true
 }
 
 pub struct SessionServiceImpl {
@@ -32,6 +171,119 @@ pub struct SessionServiceImpl {
     livekit_config: LiveKitConfig,
     livekit_url: String,
     event_bus: crate::events::EventBus,
+    http_client: reqwest::Client,
+    notify_retry: NotifyRetryPolicy,
+    observer_pool: ObserverPool,
+    /// Collects room stats for observed sessions; `None` when disabled
+    room_stats: Option<RoomStatsPoller>,
+    /// Upper bound on non-terminated sessions, 0 for none
+    max_sessions: usize,
+    /// Limits metadata updates have to stay within, the same as at creation
+    metadata_limits: MetadataLimitConfig,
+    /// Sessions being created but not stored yet, counted against `max_sessions`
+    creating: Arc<AtomicUsize>,
+}
+
+/// A reserved slot for a session being created, released on drop
+struct CreatingSlot(Arc<AtomicUsize>);
+
+impl Drop for CreatingSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Undoes a session whose creation did not finish
+///
+/// `create_session` awaits several steps; when its future is dropped part way, e.g.
+/// because axum cancels the handler after the client disconnects, or a step fails, the
+/// guard removes what was already set up: the observer connection, the LiveKit room, the
+/// stored session and its event stream. The cleanup is async, so it runs on a spawned
+/// task.
+struct CreationGuard {
+    session_id: String,
+    /// Latest state of the session being created, including its observer connection
+    session: Option<Session>,
+    /// The room belongs to this session alone, so it is deleted along with it
+    owns_room: bool,
+    stored: bool,
+    completed: bool,
+    storage: Arc<dyn SessionStorage>,
+    livekit_config: LiveKitConfig,
+    event_bus: crate::events::EventBus,
+}
+
+impl CreationGuard {
+    fn new(service: &SessionServiceImpl, session_id: String) -> Self {
+        Self {
+            session_id,
+            session: None,
+            owns_room: false,
+            stored: false,
+            completed: false,
+            storage: service.storage.clone(),
+            livekit_config: service.livekit_config.clone(),
+            event_bus: service.event_bus.clone(),
+        }
+    }
+
+    fn track(&mut self, session: &Session) {
+        self.session = Some(session.clone());
+    }
+
+    /// The session was created; keep everything
+    fn complete(mut self) {
+        self.completed = true;
+    }
+}
+
+impl Drop for CreationGuard {
+    fn drop(&mut self) {
+        if self.completed {
+            return;
+        }
+        self.event_bus.cleanup_session(&self.session_id);
+        let Some(mut session) = self.session.take() else {
+            return;
+        };
+        if !self.owns_room && !self.stored && session.room_connection.is_none() {
+            return;
+        }
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            tracing::warn!(
+                "Creation of session {} was abandoned outside a runtime, room {} is not cleaned up",
+                self.session_id,
+                session.room_name
+            );
+            return;
+        };
+
+        tracing::warn!(
+            "Creation of session {} did not complete, cleaning up",
+            self.session_id
+        );
+        let (owns_room, stored) = (self.owns_room, self.stored);
+        let storage = self.storage.clone();
+        let livekit_config = self.livekit_config.clone();
+        runtime.spawn(
+            async move {
+                if let Err(e) = session.disconnect_from_livekit().await {
+                    tracing::warn!("Failed to disconnect abandoned session: {}", e);
+                }
+                if owns_room {
+                    if let Err(e) = session.delete_livekit_room(&livekit_config).await {
+                        tracing::warn!("Failed to delete room of abandoned session: {}", e);
+                    }
+                }
+                if stored {
+                    if let Err(e) = storage.delete_session(&session.id).await {
+                        tracing::warn!("Failed to delete abandoned session: {}", e);
+                    }
+                }
+            }
+            .instrument(tracing::Span::current()),
+        );
+    }
 }
 
 impl SessionServiceImpl {
@@ -41,50 +293,328 @@ impl SessionServiceImpl {
         livekit_config: LiveKitConfig,
         livekit_url: String,
         event_bus: crate::events::EventBus,
+        http_client: reqwest::Client,
+        notify_retry: NotifyRetryPolicy,
     ) -> Self {
+        let room_stats = (livekit_config.room_stats_interval_secs > 0).then(|| {
+            RoomStatsPoller::new(
+                storage.clone(),
+                event_bus.clone(),
+                std::time::Duration::from_secs(livekit_config.room_stats_interval_secs),
+            )
+        });
         Self {
             storage,
             microservice_registry,
+            observer_pool: ObserverPool::new(livekit_config.max_observer_connections),
+            room_stats,
             livekit_config,
             livekit_url,
             event_bus,
+            http_client,
+            notify_retry,
+            max_sessions: 0,
+            metadata_limits: MetadataLimitConfig::default(),
+            creating: Arc::new(AtomicUsize::new(0)),
         }
     }
-}
 
-#[async_trait]
-impl SessionService for SessionServiceImpl {
-    #[instrument(
-        name = "create_session",
-        skip(self),
-        fields(
-            user_identity = %request.user_identity,
-            user_name = ?request.user_name,
-            room_name = ?request.room_name,
-            required_services = ?request.required_services,
-            session_id,
-            microservices_count,
-            session_status
-        )
-    )]
-    async fn create_session(&self, request: CreateSessionRequest) -> Result<(Session, String)> {
-        // 1. Generate session ID and room name
-        let session_id = Uuid::new_v4().to_string();
-        let room_name = request
-            .room_name
-            .unwrap_or_else(|| format!("room-{}", session_id));
+    /// Reject new sessions with [`SessionManagerError::AtCapacity`] while `max_sessions`
+    /// sessions are not terminated; 0 disables the limit
+    pub fn with_max_sessions(mut self, max_sessions: usize) -> Self {
+        self.max_sessions = max_sessions;
+        self
+    }
 
-        // Record session_id in the span
-        tracing::Span::current().record("session_id", &session_id);
+    /// Reject metadata updates whose merged result exceeds `limits`
+    pub fn with_metadata_limits(mut self, limits: MetadataLimitConfig) -> Self {
+        self.metadata_limits = limits;
+        self
+    }
 
-        tracing::info!("Creating session for room {}", room_name);
+    /// Reserve room for one more session under `max_sessions`
+    ///
+    /// The slot is taken before counting, so concurrent creates can only be rejected
+    /// too eagerly, never let through beyond the limit.
+    async fn reserve_session_slot(&self) -> Result<Option<CreatingSlot>> {
+        if self.max_sessions == 0 {
+            return Ok(None);
+        }
 
-        // 2. Get registered microservices (optional)
-        let registered_services = if let Some(required_service_ids) = request.required_services {
+        let creating = self.creating.fetch_add(1, Ordering::SeqCst) + 1;
+        let slot = CreatingSlot(self.creating.clone());
+        let live = self
+            .storage
+            .list_sessions()
+            .await?
+            .iter()
+            .filter(|session| session.status != SessionStatus::Terminated)
+            .count();
+        if live + creating > self.max_sessions {
+            tracing::warn!(
+                "Session limit reached ({} live, {} being created, max {})",
+                live,
+                creating - 1,
+                self.max_sessions
+            );
+            return Err(SessionManagerError::AtCapacity {
+                max_sessions: self.max_sessions,
+            });
+        }
+        Ok(Some(slot))
+    }
+
+    /// Build the HTTP client shared by all microservice notifications
+    pub fn build_http_client() -> Result<reqwest::Client> {
+        let client = reqwest::Client::builder()
+            .connect_timeout(NOTIFY_CONNECT_TIMEOUT)
+            .timeout(NOTIFY_REQUEST_TIMEOUT)
+            .pool_idle_timeout(NOTIFY_POOL_IDLE_TIMEOUT)
+            .build()?;
+        Ok(client)
+    }
+
+    /// Start collecting room stats through the session's observer connection, if any
+    fn spawn_room_stats(room_stats: Option<&RoomStatsPoller>, session: &Session) {
+        if let (Some(poller), Some(connection)) = (room_stats, &session.room_connection) {
+            poller.spawn(
+                session.id.clone(),
+                Arc::new(ObserverRoomStats::new(connection.clone())),
+            );
+        }
+    }
+
+    /// Connect the observer for a session created while the pool was exhausted, once a
+    /// slot frees up. Skipped if the session ends in the meantime.
+    fn spawn_deferred_observer(&self, session_id: String) {
+        let storage = self.storage.clone();
+        let observer_pool = self.observer_pool.clone();
+        let livekit_config = self.livekit_config.clone();
+        let event_bus = Arc::new(self.event_bus.clone());
+        let room_stats = self.room_stats.clone();
+
+        tokio::spawn(async move {
+            let Some(claim) = observer_pool.claim(&session_id) else {
+                tracing::debug!("Session {} is already observed", session_id);
+                return;
+            };
+            let permit = observer_pool.acquire().await.with_claim(claim);
+
+            let session = match storage.get_session(&session_id).await {
+                Ok(Some(session)) => session,
+                Ok(None) => return,
+                Err(e) => {
+                    tracing::error!(
+                        "Failed to load session {} for observation: {}",
+                        session_id,
+                        e
+                    );
+                    return;
+                }
+            };
+            if matches!(
+                session.status,
+                SessionStatus::Terminating | SessionStatus::Terminated
+            ) {
+                return;
+            }
+
+            let mut observed = session.clone();
+            if let Err(e) = observed
+                .connect_to_livekit(livekit_config, event_bus, permit)
+                .await
+            {
+                tracing::error!("Deferred observer for session {} failed: {}", session_id, e);
+                return;
+            }
+
+            // Attach the connection to the latest stored state without touching its status
+            let mut latest = match storage.get_session(&session_id).await {
+                Ok(Some(latest)) => latest,
+                _ => session,
+            };
+            if matches!(
+                latest.status,
+                SessionStatus::Terminating | SessionStatus::Terminated
+            ) {
+                // Terminated while connecting; give the slot back
+                let _ = observed.disconnect_from_livekit().await;
+                return;
+            }
+            latest.room_connection = observed.room_connection.take();
+            match storage.update_session(&latest).await {
+                Ok(()) => {
+                    tracing::info!("Deferred observer connected for session {}", session_id);
+                    Self::spawn_room_stats(room_stats.as_ref(), &latest);
+                }
+                Err(e) => tracing::error!(
+                    "Failed to store deferred observer for session {}: {}",
+                    session_id,
+                    e
+                ),
+            }
+        });
+    }
+
+    /// Store the capabilities each microservice reports as its join notification completes
+    ///
+    /// With `mark_ready`, used for observer-less sessions, an acknowledged notification also
+    /// marks the service ready since no room events will report it joining. Otherwise the
+    /// acknowledgement still proves the join, so a room event the observer misses (or sees
+    /// before it starts) does not leave the session waiting; the event bus deduplicates it
+    /// against the observer's own announcement.
+    fn spawn_join_ack_handler(
+        &self,
+        session_id: String,
+        notifications: Vec<tokio::task::JoinHandle<Option<(String, Capabilities)>>>,
+        mark_ready: bool,
+        expected_services: usize,
+    ) {
+        let storage = self.storage.clone();
+        let event_bus = self.event_bus.clone();
+
+        tokio::spawn(async move {
+            let mut notifications: FuturesUnordered<_> = notifications.into_iter().collect();
+            while let Some(notification) = notifications.next().await {
+                let Ok(Some((service_id, capabilities))) = notification else {
+                    continue;
+                };
+                if !mark_ready {
+                    event_bus.announce_service_joined(&session_id, &service_id, expected_services);
+                    if capabilities.is_empty() {
+                        continue;
+                    }
+                }
+
+                let mut session = match storage.get_session(&session_id).await {
+                    Ok(Some(session)) => session,
+                    Ok(None) => return,
+                    Err(e) => {
+                        tracing::error!(
+                            "Failed to load session {} to store capabilities: {}",
+                            session_id,
+                            e
+                        );
+                        continue;
+                    }
+                };
+                if !session.set_service_capabilities(&service_id, capabilities) {
+                    continue;
+                }
+                let became_ready = mark_ready
+                    && !matches!(
+                        session.status,
+                        SessionStatus::Terminating | SessionStatus::Terminated
+                    )
+                    && session.mark_service_ready(&service_id)
+                    && session.ready_microservices.len() == session.ready_quorum();
+                match storage.update_session(&session).await {
+                    Ok(()) => tracing::debug!(
+                        "Recorded join acknowledgement of service {} for session {}",
+                        service_id,
+                        session_id
+                    ),
+                    Err(e) => {
+                        tracing::error!(
+                            "Failed to record join acknowledgement of service {} for session {}: {}",
+                            service_id,
+                            session_id,
+                            e
+                        );
+                        continue;
+                    }
+                }
+                if became_ready {
+                    publish_session_ready(&event_bus, &session_id);
+                }
+            }
+        });
+    }
+
+    /// Most recently created `Ready` or `Active` session of `user_identity` that was
+    /// created by the same `owner`
+    async fn find_reusable_session(
+        &self,
+        user_identity: &str,
+        owner: Option<&str>,
+    ) -> Result<Option<Session>> {
+        let session = self
+            .storage
+            .list_sessions()
+            .await?
+            .into_iter()
+            .filter(|session| {
+                session.user_identity.as_deref() == Some(user_identity)
+                    && session.owner.as_deref() == owner
+                    && matches!(session.status, SessionStatus::Ready | SessionStatus::Active)
+            })
+            .max_by(|a, b| session_sort_key(a).cmp(&session_sort_key(b)));
+        Ok(session)
+    }
+
+    /// Look up the requested services, or every available service when none are
+    /// requested by id or type; lookup failures leave the session without microservices
+    async fn resolve_services(&self, request: &CreateSessionRequest) -> Vec<MicroserviceInfo> {
+        let Some(required_types) = request.required_service_types.as_deref() else {
+            return self
+                .resolve_services_by_id(request.required_services.as_deref())
+                .await;
+        };
+
+        let mut services = self
+            .resolve_services_by_id(Some(
+                request.required_services.as_deref().unwrap_or_default(),
+            ))
+            .await;
+        for service_type in required_types {
+            let selected: HashSet<String> = services
+                .iter()
+                .map(|service| service.service_id.clone())
+                .collect();
+            match self
+                .microservice_registry
+                .pick_service_by_type(service_type, &selected)
+                .await
+            {
+                Ok(Some(service)) => {
+                    tracing::debug!(
+                        "Selected {} for service type {}",
+                        service.service_id,
+                        service_type
+                    );
+                    services.push(service);
+                }
+                // Every instance is already in the session; one of them fills this slot too
+                Ok(None)
+                    if services
+                        .iter()
+                        .any(|service| service.service_type() == Some(service_type.as_str())) =>
+                {
+                    tracing::debug!(
+                        "No further instance of service type {}, sharing a selected one",
+                        service_type
+                    );
+                }
+                Ok(None) => {
+                    tracing::warn!("No available instance of service type {}", service_type);
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to pick service of type {}: {}", service_type, e);
+                }
+            }
+        }
+        services
+    }
+
+    async fn resolve_services_by_id(
+        &self,
+        required_services: Option<&[String]>,
+    ) -> Vec<MicroserviceInfo> {
+        if let Some(required_service_ids) = required_services {
             // If specific microservice IDs are specified, get those services
             match self
                 .microservice_registry
-                .get_services_by_ids(&required_service_ids)
+                .get_services_by_ids(required_service_ids)
                 .await
             {
                 Ok(services) => {
@@ -116,14 +646,202 @@ impl SessionService for SessionServiceImpl {
                     Vec::new() // Continue creating session, but without microservices
                 }
             }
+        }
+    }
+
+    /// Reject inputs LiveKit would choke on before allocating anything
+    fn validate_request(&self, request: &CreateSessionRequest) -> Result<()> {
+        validation::validate_user_identity(&request.user_identity, &self.livekit_config.identity)?;
+        if let Some(room_name) = &request.room_name {
+            validation::validate_room_name(room_name)?;
+        }
+        if request.max_lifetime_secs == Some(0) {
+            return Err(SessionManagerError::InvalidRequest(
+                "max_lifetime_secs must be positive".to_string(),
+            ));
+        }
+        if request
+            .max_lifetime_secs
+            .is_some_and(|secs| secs > validation::MAX_SESSION_LIFETIME_SECS)
+        {
+            return Err(SessionManagerError::InvalidRequest(format!(
+                "max_lifetime_secs must not exceed {}",
+                validation::MAX_SESSION_LIFETIME_SECS
+            )));
+        }
+        if request.min_ready_services == Some(0) {
+            return Err(SessionManagerError::InvalidRequest(
+                "min_ready_services must be positive".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Render the configured room name template, suffixing `-2`, `-3`, ... while the
+    /// name is still used by a live session
+    async fn unique_room_name(&self, session_id: &str, user_identity: &str) -> Result<String> {
+        let base = self
+            .livekit_config
+            .room_name
+            .render(session_id, user_identity);
+        let taken: HashSet<String> = self
+            .storage
+            .list_sessions()
+            .await?
+            .into_iter()
+            .filter(|session| session.status != SessionStatus::Terminated)
+            .map(|session| session.room_name)
+            .collect();
+
+        let mut room_name = base.clone();
+        let mut suffix = 2;
+        while taken.contains(&room_name) {
+            let tail = format!("-{}", suffix);
+            let keep = validation::MAX_ROOM_NAME_LEN - tail.len();
+            room_name = format!("{}{}", &base[..base.len().min(keep)], tail);
+            suffix += 1;
+        }
+        Ok(room_name)
+    }
+
+    /// Probe the LiveKit API with a lightweight `list_rooms` call
+    async fn check_livekit(&self) -> DependencyStatus {
+        use livekit_api::services::room::RoomClient;
+
+        let api_url = self.livekit_config.api_url();
+
+        let room_client = RoomClient::with_api_key(
+            &api_url,
+            &self.livekit_config.api_key,
+            &self.livekit_config.api_secret,
+        );
+
+        let (healthy, message) =
+            match tokio::time::timeout(READINESS_PROBE_TIMEOUT, room_client.list_rooms(Vec::new()))
+                .await
+            {
+                Ok(Ok(_)) => (true, None),
+                Ok(Err(e)) => (false, Some(format!("LiveKit API error: {}", e))),
+                Err(_) => (
+                    false,
+                    Some(format!(
+                        "LiveKit API did not respond within {:?}",
+                        READINESS_PROBE_TIMEOUT
+                    )),
+                ),
+            };
+
+        DependencyStatus {
+            name: "livekit".to_string(),
+            healthy,
+            message,
+        }
+    }
+
+    /// Verify that session storage accepts writes
+    async fn check_storage(&self) -> DependencyStatus {
+        let (healthy, message) = match tokio::time::timeout(
+            READINESS_PROBE_TIMEOUT,
+            self.storage.health_check(),
+        )
+        .await
+        {
+            Ok(Ok(())) => (true, None),
+            Ok(Err(e)) => (false, Some(e.to_string())),
+            Err(_) => (
+                false,
+                Some(format!(
+                    "Storage did not respond within {:?}",
+                    READINESS_PROBE_TIMEOUT
+                )),
+            ),
+        };
+
+        DependencyStatus {
+            name: "storage".to_string(),
+            healthy,
+            message,
+        }
+    }
+}
+
+#[async_trait]
+impl SessionService for SessionServiceImpl {
+    #[instrument(
+        name = "create_session",
+        skip(self),
+        fields(
+            user_identity = %request.user_identity,
+            user_name = ?request.user_name,
+            room_name = ?request.room_name,
+            required_services = ?request.required_services,
+            session_id,
+            microservices_count,
+            session_status
+        )
+    )]
+    async fn create_session(&self, request: CreateSessionRequest) -> Result<(Session, String)> {
+        self.validate_request(&request)?;
+
+        // Reconnecting clients rejoin their live session with a fresh token
+        if request.reuse_existing {
+            if let Some(session) = self
+                .find_reusable_session(&request.user_identity, request.owner.as_deref())
+                .await?
+            {
+                tracing::Span::current().record("session_id", &session.id);
+                tracing::info!(
+                    "Reusing existing {:?} session for user {}",
+                    session.status,
+                    request.user_identity
+                );
+                session.ensure_room_capacity(&self.livekit_config).await?;
+                let access_token = session.generate_client_token(&self.livekit_config).await?;
+                return Ok((session, access_token));
+            }
+        }
+
+        // Held until the session is stored so concurrent creates see it
+        let _slot = self.reserve_session_slot().await?;
+
+        // 1. Generate session ID and room name
+        let session_id = Uuid::new_v4().to_string();
+        let room_name = match request.room_name.clone() {
+            Some(room_name) => {
+                // Explicit room names may be shared, so the room can already be full
+                check_room_capacity(&self.livekit_config, &room_name, None).await?;
+                room_name
+            }
+            None => {
+                self.unique_room_name(&session_id, &request.user_identity)
+                    .await?
+            }
         };
 
+        // Record session_id in the span
+        tracing::Span::current().record("session_id", &session_id);
+
+        // Open the per-session event stream before anything can publish to it
+        self.event_bus.create_session_stream(session_id.clone());
+        // Undoes everything below unless creation completes
+        let mut guard = CreationGuard::new(self, session_id.clone());
+
+        tracing::info!("Creating session for room {}", room_name);
+
+        // 2. Get registered microservices (optional)
+        let registered_services = self.resolve_services(&request).await;
+
         // 3. Create session object
         let mut session = Session::new(
             session_id.clone(),
             room_name.clone(),
             request.metadata.unwrap_or_default(),
         );
+        session.user_identity = Some(request.user_identity.clone());
+        session.owner = request.owner.clone();
+        session.observe = request.observe;
+        session.max_lifetime_secs = request.max_lifetime_secs;
+        session.min_ready_services = request.min_ready_services;
 
         // Add microservices to session (if any)
         for service in registered_services {
@@ -137,25 +855,64 @@ impl SessionService for SessionServiceImpl {
         );
 
         // 4. Session creates its own LiveKit room
-        session.create_livekit_room(&self.livekit_config).await?;
+        // Rooms named by the caller may be shared with live sessions, so they are only
+        // ours once this call created them. Generated names are unique, so such a room
+        // is ours even if the call is cancelled before LiveKit answers.
+        guard.track(&session);
+        guard.owns_room = request.room_name.is_none();
+        if session.create_livekit_room(&self.livekit_config).await? {
+            guard.owns_room = true;
+        }
 
         // 5. Set session status and connect to LiveKit
+        let mut observe_deferred = false;
         if session.registered_microservices.is_empty() {
             // No microservices, session is immediately ready
-            session.update_status(SessionStatus::Ready);
+            session.transition_to(SessionStatus::Ready)?;
             tracing::info!("Session created without microservices - immediately ready");
-        } else {
-            // Has microservices, let Session connect to LiveKit and monitor participants
-            let livekit_config = self.livekit_config.clone();
-            let event_bus = Arc::new(self.event_bus.clone());
-
-            session
-                .connect_to_livekit(livekit_config.clone(), event_bus)
-                .await?;
+        } else if !session.observe {
+            // Observer-less: services count as ready once they acknowledge the join
+            // notification or call the service-ready endpoint
+            session.transition_to(SessionStatus::WaitingForServices)?;
             tracing::info!(
-                "Session connected to LiveKit and monitoring for {} microservices",
+                "Observer-less session waiting for {} microservices to report ready",
                 session.registered_microservices.len()
             );
+        } else {
+            // Has microservices, let Session connect to LiveKit and monitor participants.
+            // The claim keeps a second connection attempt for this session from
+            // opening another observer.
+            let claim = self.observer_pool.claim(&session.id).ok_or_else(|| {
+                SessionManagerError::Internal(anyhow::anyhow!(
+                    "Session {} is already being observed",
+                    session.id
+                ))
+            })?;
+            match self.observer_pool.try_acquire() {
+                Some(permit) => {
+                    let permit = permit.with_claim(claim);
+                    let livekit_config = self.livekit_config.clone();
+                    let event_bus = Arc::new(self.event_bus.clone());
+
+                    session
+                        .connect_to_livekit(livekit_config.clone(), event_bus, permit)
+                        .await?;
+                    guard.track(&session);
+                    tracing::info!(
+                        "Session connected to LiveKit and monitoring for {} microservices",
+                        session.registered_microservices.len()
+                    );
+                }
+                None => {
+                    // Still create the session; observation starts once a slot frees up
+                    session.transition_to(SessionStatus::WaitingForServices)?;
+                    observe_deferred = true;
+                    tracing::warn!(
+                        "Observer connection limit ({}) reached, deferring observation",
+                        self.observer_pool.limit()
+                    );
+                }
+            }
         }
 
         // Record final session status in the span
@@ -163,16 +920,29 @@ impl SessionService for SessionServiceImpl {
 
         // 6. Save session
         self.storage.save_session(&session).await?;
+        guard.stored = true;
+        Self::spawn_room_stats(self.room_stats.as_ref(), &session);
 
         // 7. Generate user access token
-        let access_token = session.generate_client_token(&self.livekit_config)?;
+        let access_token = session.generate_client_token(&self.livekit_config).await?;
 
         // 8. Notify microservices to join room (don't wait)
         if !session.registered_microservices.is_empty() {
             // Session notifies microservices to join - monitors their joining via events
-            session
-                .notify_microservices_to_join(&self.livekit_config, &self.livekit_url)
+            let notifications = session
+                .notify_microservices_to_join(
+                    &self.livekit_config,
+                    &self.livekit_url,
+                    &self.http_client,
+                    &self.notify_retry,
+                )
                 .await?;
+            self.spawn_join_ack_handler(
+                session.id.clone(),
+                notifications,
+                !session.observe,
+                session.ready_quorum(),
+            );
         }
 
         // 9. Publish session creation event
@@ -186,6 +956,11 @@ impl SessionService for SessionServiceImpl {
             },
         );
 
+        if observe_deferred {
+            self.spawn_deferred_observer(session.id.clone());
+        }
+
+        guard.complete();
         tracing::info!("Session created successfully");
         Ok((session, access_token))
     }
@@ -217,4 +992,496 @@ impl SessionService for SessionServiceImpl {
             }
         }
     }
+
+    #[instrument(
+        name = "validate_session",
+        skip(self, request),
+        fields(user_identity = %request.user_identity)
+    )]
+    async fn validate_session(&self, request: &CreateSessionRequest) -> Result<SessionValidation> {
+        self.validate_request(request)?;
+
+        let services = self.resolve_services(request).await;
+        let mut available_services: Vec<String> = services
+            .iter()
+            .map(|service| service.service_id.clone())
+            .collect();
+        if request.required_services.is_none() && request.required_service_types.is_none() {
+            available_services.sort();
+        }
+        let missing_types =
+            request
+                .required_service_types
+                .iter()
+                .flatten()
+                .filter(|service_type| {
+                    !services
+                        .iter()
+                        .any(|service| service.service_type() == Some(service_type.as_str()))
+                });
+        let missing_services = request
+            .required_services
+            .iter()
+            .flatten()
+            .filter(|service_id| !available_services.contains(service_id))
+            .chain(missing_types)
+            .cloned()
+            .collect();
+
+        Ok(SessionValidation {
+            available_services,
+            missing_services,
+        })
+    }
+
+    #[instrument(name = "list_sessions", skip(self), fields(returned))]
+    async fn list_sessions(
+        &self,
+        status: Option<SessionStatus>,
+        owner: Option<&str>,
+        limit: usize,
+        cursor: Option<&str>,
+    ) -> Result<SessionPage> {
+        let after = cursor.map(decode_cursor).transpose()?;
+
+        let mut sessions: Vec<Session> = self
+            .storage
+            .list_sessions()
+            .await?
+            .into_iter()
+            .filter(|session| status.as_ref().is_none_or(|s| session.status == *s))
+            .filter(|session| owner.is_none_or(|owner| session.owner.as_deref() == Some(owner)))
+            .collect();
+        sessions.sort_by(|a, b| session_sort_key(a).cmp(&session_sort_key(b)));
+
+        let mut page: Vec<Session> = sessions
+            .into_iter()
+            .filter(|session| match &after {
+                Some((micros, id)) => session_sort_key(session) > (*micros, id.as_str()),
+                None => true,
+            })
+            .take(limit + 1)
+            .collect();
+
+        let next_cursor = if page.len() > limit {
+            page.truncate(limit);
+            page.last().map(encode_cursor)
+        } else {
+            None
+        };
+
+        tracing::Span::current().record("returned", page.len());
+        Ok(SessionPage {
+            sessions: page,
+            next_cursor,
+        })
+    }
+
+    #[instrument(name = "leave_session", skip(self), fields(remaining_participants))]
+    async fn leave_session(&self, session_id: &str, user_identity: &str) -> Result<Session> {
+        let session = self.storage.get_session(session_id).await?.ok_or_else(|| {
+            SessionManagerError::SessionNotFound {
+                session_id: session_id.to_string(),
+            }
+        })?;
+
+        if session.user_identity.as_deref() != Some(user_identity) {
+            return Err(SessionManagerError::Forbidden(format!(
+                "{} is not a participant of session {}",
+                user_identity, session_id
+            )));
+        }
+
+        if matches!(
+            session.status,
+            SessionStatus::Terminating | SessionStatus::Terminated
+        ) {
+            tracing::debug!("Session already terminating, ignoring leave");
+            return Ok(session);
+        }
+
+        self.event_bus.publish_to_session(
+            session_id,
+            crate::events::SessionEvent::ClientLeft {
+                session_id: session_id.to_string(),
+                user_identity: user_identity.to_string(),
+            },
+        );
+
+        // Any client other than the leaving one
+        let identities = &self.livekit_config.identity;
+        let leaving_identity = identities.client(session_id);
+        let service_ids: HashSet<String> = session
+            .registered_microservices
+            .iter()
+            .map(|service| service.service_id.clone())
+            .collect();
+        let remaining = session
+            .remote_participant_identities()
+            .await
+            .into_iter()
+            .filter(|identity| {
+                *identity != leaving_identity
+                    && ParticipantRole::classify(identity, session_id, &service_ids, identities)
+                        == ParticipantRole::Client
+            })
+            .count();
+        tracing::Span::current().record("remaining_participants", remaining);
+
+        if remaining > 0 {
+            tracing::info!(
+                "Client {} left, {} participants remain",
+                user_identity,
+                remaining
+            );
+            return Ok(session);
+        }
+
+        tracing::info!(
+            "Client {} left with no participants remaining",
+            user_identity
+        );
+        self.terminate_session(session_id, LeaveReason::ClientLeft)
+            .await
+    }
+
+    #[instrument(name = "mark_service_ready", skip(self))]
+    async fn mark_service_ready(&self, session_id: &str, service_id: &str) -> Result<Session> {
+        let mut session = self.storage.get_session(session_id).await?.ok_or_else(|| {
+            SessionManagerError::SessionNotFound {
+                session_id: session_id.to_string(),
+            }
+        })?;
+
+        if !session
+            .registered_microservices
+            .iter()
+            .any(|service| service.service_id == service_id)
+        {
+            return Err(SessionManagerError::InvalidRequest(format!(
+                "Service {} is not part of session {}",
+                service_id, session_id
+            )));
+        }
+
+        if matches!(
+            session.status,
+            SessionStatus::Terminating | SessionStatus::Terminated
+        ) {
+            return Err(SessionManagerError::InvalidRequest(format!(
+                "Session {} is terminating",
+                session_id
+            )));
+        }
+
+        if session.observe {
+            // Being ready implies having joined, whether or not the observer saw it
+            self.event_bus
+                .announce_service_joined(session_id, service_id, session.ready_quorum());
+        }
+
+        if !session.mark_service_ready(service_id) {
+            tracing::debug!("Service already marked ready");
+            return Ok(session);
+        }
+
+        self.storage.update_session(&session).await?;
+        tracing::info!(
+            "Service ready, {} pending",
+            session.get_pending_services().len()
+        );
+
+        // Only the service completing the quorum announces readiness
+        if session.is_ready() && session.ready_microservices.len() == session.ready_quorum() {
+            publish_session_ready(&self.event_bus, session_id);
+        }
+
+        Ok(session)
+    }
+
+    #[instrument(name = "update_metadata", skip(self, patch), fields(keys = patch.len()))]
+    async fn update_metadata(
+        &self,
+        session_id: &str,
+        patch: HashMap<String, Option<String>>,
+    ) -> Result<Session> {
+        let mut session = self.storage.get_session(session_id).await?.ok_or_else(|| {
+            SessionManagerError::SessionNotFound {
+                session_id: session_id.to_string(),
+            }
+        })?;
+
+        if session.status == SessionStatus::Terminated {
+            return Err(SessionManagerError::InvalidRequest(format!(
+                "Session {} is terminated",
+                session_id
+            )));
+        }
+
+        let exposed_changed = patch
+            .keys()
+            .any(|key| self.livekit_config.room_metadata_keys.contains(key));
+        session.merge_metadata(patch);
+        // Patches build on the stored metadata, so the limits apply to the merged result
+        validation::validate_metadata("metadata", &session.metadata, &self.metadata_limits)?;
+        self.storage.update_session(&session).await?;
+
+        if exposed_changed {
+            if let Err(e) = session
+                .update_livekit_room_metadata(&self.livekit_config)
+                .await
+            {
+                tracing::warn!("Failed to update room metadata: {}", e);
+            }
+        }
+
+        self.event_bus.publish_to_session(
+            session_id,
+            crate::events::SessionEvent::MetadataChanged {
+                session_id: session_id.to_string(),
+                metadata: session.metadata.clone(),
+            },
+        );
+
+        tracing::info!("Session metadata updated");
+        Ok(session)
+    }
+
+    #[instrument(name = "refresh_client_token", skip(self))]
+    async fn refresh_client_token(&self, session_id: &str) -> Result<(Session, String)> {
+        let session = self.storage.get_session(session_id).await?.ok_or_else(|| {
+            SessionManagerError::SessionNotFound {
+                session_id: session_id.to_string(),
+            }
+        })?;
+
+        if matches!(
+            session.status,
+            SessionStatus::Terminating | SessionStatus::Terminated
+        ) {
+            return Err(SessionManagerError::InvalidRequest(format!(
+                "Session {} is terminated",
+                session_id
+            )));
+        }
+
+        session.ensure_room_capacity(&self.livekit_config).await?;
+        let access_token = session.generate_client_token(&self.livekit_config).await?;
+        tracing::info!("Client token refreshed");
+        Ok((session, access_token))
+    }
+
+    #[instrument(name = "terminate_session", skip(self))]
+    async fn terminate_session(&self, session_id: &str, reason: LeaveReason) -> Result<Session> {
+        let mut session = self.storage.get_session(session_id).await?.ok_or_else(|| {
+            SessionManagerError::SessionNotFound {
+                session_id: session_id.to_string(),
+            }
+        })?;
+
+        if session.status == SessionStatus::Terminated {
+            return Ok(session);
+        }
+
+        session.transition_to(SessionStatus::Terminating)?;
+        self.storage.update_session(&session).await?;
+        self.event_bus.publish_to_session(
+            session_id,
+            crate::events::SessionEvent::SessionStatusChanged {
+                session_id: session_id.to_string(),
+                status: SessionStatus::Terminating,
+                reason: Some(reason),
+            },
+        );
+
+        session
+            .notify_services_to_leave(&self.http_client, reason)
+            .await;
+
+        // Disconnects the observer and marks the session Terminated
+        session.disconnect_from_livekit().await?;
+
+        if let Err(e) = session.delete_livekit_room(&self.livekit_config).await {
+            tracing::warn!("Failed to delete room during termination: {}", e);
+        }
+
+        self.storage.update_session(&session).await?;
+        self.event_bus.publish_to_session(
+            session_id,
+            crate::events::SessionEvent::SessionStatusChanged {
+                session_id: session_id.to_string(),
+                status: SessionStatus::Terminated,
+                reason: None,
+            },
+        );
+        self.event_bus.cleanup_session(session_id);
+
+        tracing::info!("Session terminated");
+        Ok(session)
+    }
+
+    #[instrument(name = "sweep_abandoned_sessions", skip(self), fields(swept))]
+    async fn sweep_abandoned_sessions(&self, max_age: std::time::Duration) -> Result<Vec<String>> {
+        let max_age = chrono::Duration::from_std(max_age).unwrap_or(chrono::Duration::MAX);
+        let Some(cutoff) = chrono::Utc::now().checked_sub_signed(max_age) else {
+            return Ok(Vec::new());
+        };
+
+        let mut swept = Vec::new();
+        for session in self.storage.list_sessions().await? {
+            let waiting = matches!(
+                session.status,
+                SessionStatus::Creating | SessionStatus::WaitingForServices
+            );
+            if !waiting || session.created_at > cutoff {
+                continue;
+            }
+
+            // Only the session manager's own observer may be in the room
+            let manager = self.livekit_config.identity.manager(&session.id);
+            if session
+                .remote_participant_identities()
+                .await
+                .iter()
+                .any(|identity| *identity != manager)
+            {
+                continue;
+            }
+
+            tracing::info!(
+                "Sweeping abandoned session {} created at {}",
+                session.id,
+                session.created_at
+            );
+            if let Err(e) = self
+                .terminate_session(&session.id, LeaveReason::Timeout)
+                .await
+            {
+                tracing::warn!(
+                    "Failed to terminate abandoned session {}: {}",
+                    session.id,
+                    e
+                );
+                continue;
+            }
+            self.storage.delete_session(&session.id).await?;
+            swept.push(session.id);
+        }
+
+        tracing::Span::current().record("swept", swept.len());
+        Ok(swept)
+    }
+
+    #[instrument(name = "expire_sessions", skip(self), fields(expired))]
+    async fn expire_sessions(
+        &self,
+        default_max_lifetime: Option<std::time::Duration>,
+    ) -> Result<Vec<String>> {
+        let now = chrono::Utc::now();
+
+        let mut expired = Vec::new();
+        for session in self.storage.list_sessions().await? {
+            if session.status == SessionStatus::Terminated {
+                continue;
+            }
+            let Some(max_lifetime) = session
+                .max_lifetime_secs
+                .map(std::time::Duration::from_secs)
+                .or(default_max_lifetime)
+            else {
+                continue;
+            };
+            // A deadline past the representable range never arrives
+            let Some(expires_at) = chrono::Duration::from_std(max_lifetime)
+                .ok()
+                .and_then(|lifetime| session.created_at.checked_add_signed(lifetime))
+            else {
+                continue;
+            };
+            if expires_at > now {
+                continue;
+            }
+
+            tracing::info!(
+                "Session {} exceeded its maximum lifetime of {}s",
+                session.id,
+                max_lifetime.as_secs()
+            );
+            if let Err(e) = self
+                .terminate_session(&session.id, LeaveReason::Expired)
+                .await
+            {
+                tracing::warn!("Failed to terminate expired session {}: {}", session.id, e);
+                continue;
+            }
+            self.storage.delete_session(&session.id).await?;
+            expired.push(session.id);
+        }
+
+        tracing::Span::current().record("expired", expired.len());
+        Ok(expired)
+    }
+
+    #[instrument(name = "purge_terminated_sessions", skip(self), fields(purged))]
+    async fn purge_terminated_sessions(&self, grace: std::time::Duration) -> Result<Vec<String>> {
+        let grace = chrono::Duration::from_std(grace).unwrap_or(chrono::Duration::MAX);
+        let Some(cutoff) = chrono::Utc::now().checked_sub_signed(grace) else {
+            return Ok(Vec::new());
+        };
+
+        let mut purged = Vec::new();
+        for session in self.storage.list_sessions().await? {
+            if session.status != SessionStatus::Terminated || session.updated_at > cutoff {
+                continue;
+            }
+            tracing::debug!(
+                "Removing session {} terminated at {}",
+                session.id,
+                session.updated_at
+            );
+            self.storage.delete_session(&session.id).await?;
+            purged.push(session.id);
+        }
+
+        tracing::Span::current().record("purged", purged.len());
+        Ok(purged)
+    }
+
+    #[instrument(name = "terminate_all_sessions", skip(self), fields(terminated))]
+    async fn terminate_all_sessions(&self) -> Result<usize> {
+        let mut terminated = 0;
+        for session in self.storage.list_sessions().await? {
+            if session.status != SessionStatus::Terminated {
+                if let Err(e) = self
+                    .terminate_session(&session.id, LeaveReason::AdminTerminate)
+                    .await
+                {
+                    tracing::warn!("Failed to terminate session {}: {}", session.id, e);
+                    continue;
+                }
+                terminated += 1;
+            }
+            self.storage.delete_session(&session.id).await?;
+        }
+
+        tracing::Span::current().record("terminated", terminated);
+        tracing::warn!("Terminated {} sessions", terminated);
+        Ok(terminated)
+    }
+
+    async fn check_readiness(&self) -> Vec<DependencyStatus> {
+        let (livekit, storage) = tokio::join!(self.check_livekit(), self.check_storage());
+
+        for dependency in [&livekit, &storage] {
+            if !dependency.healthy {
+                tracing::warn!(
+                    "Readiness check failed for {}: {}",
+                    dependency.name,
+                    dependency.message.as_deref().unwrap_or("unknown error")
+                );
+            }
+        }
+
+        vec![livekit, storage]
+    }
 }
@@ -0,0 +1,83 @@
+use crate::{config::SweeperConfig, services::SessionService};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Periodically removes sessions that were created but never joined, sessions that
+/// outlived their maximum lifetime, and sessions that ended a while ago
+pub struct SessionSweeper {
+    session_service: Arc<dyn SessionService>,
+    config: SweeperConfig,
+}
+
+impl SessionSweeper {
+    pub fn new(session_service: Arc<dyn SessionService>, config: SweeperConfig) -> Self {
+        Self {
+            session_service,
+            config,
+        }
+    }
+
+    /// Run a single sweep, returning the ids of removed sessions
+    pub async fn sweep(&self) -> Vec<String> {
+        let max_age = Duration::from_secs(self.config.max_age_secs);
+        let mut swept = match self.session_service.sweep_abandoned_sessions(max_age).await {
+            Ok(swept) => {
+                if !swept.is_empty() {
+                    tracing::info!("Swept {} abandoned sessions", swept.len());
+                }
+                swept
+            }
+            Err(e) => {
+                tracing::warn!("Abandoned session sweep failed: {}", e);
+                Vec::new()
+            }
+        };
+
+        let max_lifetime = (self.config.max_lifetime_secs > 0)
+            .then(|| Duration::from_secs(self.config.max_lifetime_secs));
+        match self.session_service.expire_sessions(max_lifetime).await {
+            Ok(expired) => {
+                if !expired.is_empty() {
+                    tracing::info!("Terminated {} expired sessions", expired.len());
+                }
+                swept.extend(expired);
+            }
+            Err(e) => tracing::warn!("Expired session sweep failed: {}", e),
+        }
+
+        let grace = Duration::from_secs(self.config.terminated_grace_secs);
+        match self.session_service.purge_terminated_sessions(grace).await {
+            Ok(purged) => {
+                if !purged.is_empty() {
+                    tracing::info!("Removed {} terminated sessions", purged.len());
+                }
+                swept.extend(purged);
+            }
+            Err(e) => tracing::warn!("Terminated session purge failed: {}", e),
+        }
+        swept
+    }
+
+    /// Sweep in the background every `interval_secs`
+    pub fn spawn_sweep_task(self: &Arc<Self>) -> tokio::task::JoinHandle<()> {
+        let sweeper = Arc::clone(self);
+        let interval = Duration::from_secs(sweeper.config.interval_secs.max(1));
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                // A panicking sweep must not end the periodic task
+                let sweep = tokio::spawn({
+                    let sweeper = Arc::clone(&sweeper);
+                    async move {
+                        sweeper.sweep().await;
+                    }
+                });
+                if let Err(e) = sweep.await {
+                    tracing::error!("Session sweep aborted: {}", e);
+                }
+            }
+        })
+    }
+}
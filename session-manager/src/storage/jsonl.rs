@@ -0,0 +1,75 @@
+use crate::{
+    domain::Session,
+    storage::SessionStorage,
+    utils::errors::{Result, SessionManagerError},
+};
+use futures::{
+    future,
+    stream::{self, Stream, StreamExt, TryStreamExt},
+};
+use std::sync::Arc;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt};
+
+/// Stream every stored session as one JSON line, skipping runtime-only fields.
+///
+/// Sessions are fetched one at a time, so memory use does not grow with the session count.
+/// Sessions deleted while the export is running are skipped.
+pub fn export_sessions(
+    storage: Arc<dyn SessionStorage>,
+) -> impl Stream<Item = Result<Vec<u8>>> + Send {
+    stream::once(async move {
+        let session_ids = storage.list_session_ids().await?;
+        Ok::<_, SessionManagerError>(
+            stream::iter(session_ids)
+                .then(move |session_id| {
+                    let storage = storage.clone();
+                    async move { storage.get_session(&session_id).await }
+                })
+                .try_filter_map(future::ok)
+                .and_then(|session| future::ready(session_to_line(&session))),
+        )
+    })
+    .try_flatten()
+}
+
+/// Read sessions from JSON lines and save them into `storage`.
+///
+/// Blank lines are ignored. Returns the number of imported sessions.
+pub async fn import_sessions<R>(storage: &dyn SessionStorage, reader: R) -> Result<usize>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let mut lines = reader.lines();
+    let mut line_number = 0;
+    let mut imported = 0;
+
+    while let Some(line) = lines
+        .next_line()
+        .await
+        .map_err(|e| SessionManagerError::Storage(format!("Failed to read import: {}", e)))?
+    {
+        line_number += 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let session: Session = serde_json::from_str(&line).map_err(|e| {
+            SessionManagerError::InvalidRequest(format!(
+                "Invalid session on line {}: {}",
+                line_number, e
+            ))
+        })?;
+        storage.save_session(&session).await?;
+        imported += 1;
+    }
+
+    Ok(imported)
+}
+
+fn session_to_line(session: &Session) -> Result<Vec<u8>> {
+    let mut line = serde_json::to_vec(session).map_err(|e| {
+        SessionManagerError::Storage(format!("Failed to serialize session {}: {}", session.id, e))
+    })?;
+    line.push(b'\n');
+    Ok(line)
+}
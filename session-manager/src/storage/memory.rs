@@ -1,19 +1,56 @@
-use crate::{domain::Session, storage::SessionStorage, utils::errors::Result};
+use crate::{
+    domain::Session,
+    events::RecordedEvent,
+    storage::{EndedSession, SessionStorage},
+    utils::errors::Result,
+};
 use async_trait::async_trait;
+use chrono::Utc;
 use dashmap::DashMap;
+use std::collections::VecDeque;
 use std::sync::Arc;
+use std::time::Duration;
 
 #[derive(Debug)]
 pub struct MemoryStorage {
     sessions: Arc<DashMap<String, Session>>,
+    events: Arc<DashMap<String, VecDeque<RecordedEvent>>>,
+    // 已删除会话的所有者与删除时间，其事件历史保留 `history_retention`
+    ended: Arc<DashMap<String, EndedSession>>,
+    history_retention: Duration,
 }
 
 impl MemoryStorage {
+    /// 创建内存存储；会话删除时事件历史一并删除
     pub fn new() -> Self {
+        Self::with_history_retention(Duration::ZERO)
+    }
+
+    /// 创建内存存储，会话删除后其事件历史再保留 `retention`
+    pub fn with_history_retention(retention: Duration) -> Self {
         Self {
             sessions: Arc::new(DashMap::new()),
+            events: Arc::new(DashMap::new()),
+            ended: Arc::new(DashMap::new()),
+            history_retention: retention,
         }
     }
+
+    // 清除超过保留期的已删除会话及其事件历史
+    fn purge_expired_history(&self) {
+        let retention =
+            chrono::Duration::from_std(self.history_retention).unwrap_or(chrono::Duration::MAX);
+        let Some(cutoff) = Utc::now().checked_sub_signed(retention) else {
+            return;
+        };
+        self.ended.retain(|session_id, ended| {
+            let keep = ended.deleted_at > cutoff;
+            if !keep {
+                self.events.remove(session_id);
+            }
+            keep
+        });
+    }
 }
 
 #[async_trait]
@@ -33,13 +70,74 @@ impl SessionStorage for MemoryStorage {
     }
 
     async fn delete_session(&self, session_id: &str) -> Result<()> {
-        self.sessions.remove(session_id);
+        let removed = self.sessions.remove(session_id);
+        match removed {
+            Some((_, session))
+                if !self.history_retention.is_zero() && self.events.contains_key(session_id) =>
+            {
+                self.ended.insert(
+                    session.id.clone(),
+                    EndedSession {
+                        session_id: session.id,
+                        owner: session.owner,
+                        deleted_at: Utc::now(),
+                    },
+                );
+            }
+            // 之前删除时保留的历史不因重复删除而延长
+            _ if self.ended.contains_key(session_id) => {}
+            _ => {
+                self.events.remove(session_id);
+            }
+        }
+        self.purge_expired_history();
         Ok(())
     }
 
     async fn list_sessions(&self) -> Result<Vec<Session>> {
         Ok(self.sessions.iter().map(|entry| entry.clone()).collect())
     }
+
+    async fn list_session_ids(&self) -> Result<Vec<String>> {
+        Ok(self
+            .sessions
+            .iter()
+            .map(|entry| entry.key().clone())
+            .collect())
+    }
+
+    async fn append_event(
+        &self,
+        session_id: &str,
+        event: RecordedEvent,
+        capacity: usize,
+    ) -> Result<()> {
+        let mut events = self.events.entry(session_id.to_string()).or_default();
+        while events.len() >= capacity.max(1) {
+            events.pop_front();
+        }
+        events.push_back(event);
+        Ok(())
+    }
+
+    async fn get_events(&self, session_id: &str) -> Result<Vec<RecordedEvent>> {
+        self.purge_expired_history();
+        Ok(self
+            .events
+            .get(session_id)
+            .map(|events| events.iter().cloned().collect())
+            .unwrap_or_default())
+    }
+
+    async fn get_ended_session(&self, session_id: &str) -> Result<Option<EndedSession>> {
+        self.purge_expired_history();
+        Ok(self.ended.get(session_id).map(|entry| entry.clone()))
+    }
+
+    async fn health_check(&self) -> Result<()> {
+        // 内存存储始终可写
+        Ok(())
+    }
 }
 
 impl Default for MemoryStorage {
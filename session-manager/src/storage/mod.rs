@@ -1,7 +1,17 @@
+pub mod jsonl;
 pub mod memory;
 
-use crate::{domain::Session, utils::errors::Result};
+use crate::{domain::Session, events::RecordedEvent, utils::errors::Result};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+/// 已从存储删除、但事件历史仍在保留期内的会话，记录所有者以便继续校验历史查询权限
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EndedSession {
+    pub session_id: String,
+    pub owner: Option<String>,
+    pub deleted_at: DateTime<Utc>,
+}
 
 #[async_trait]
 pub trait SessionStorage: Send + Sync {
@@ -10,4 +20,19 @@ pub trait SessionStorage: Send + Sync {
     async fn update_session(&self, session: &Session) -> Result<()>;
     async fn delete_session(&self, session_id: &str) -> Result<()>;
     async fn list_sessions(&self) -> Result<Vec<Session>>;
+    /// 列出所有会话 ID，便于逐条读取而不一次性加载全部会话
+    async fn list_session_ids(&self) -> Result<Vec<String>>;
+    /// 追加一条会话事件历史，超过 `capacity` 条时丢弃最旧的记录
+    async fn append_event(
+        &self,
+        session_id: &str,
+        event: RecordedEvent,
+        capacity: usize,
+    ) -> Result<()>;
+    /// 按发布顺序读取会话的事件历史；会话删除后历史仍保留至保留期结束
+    async fn get_events(&self, session_id: &str) -> Result<Vec<RecordedEvent>>;
+    /// 查询已删除但事件历史尚未过期的会话
+    async fn get_ended_session(&self, session_id: &str) -> Result<Option<EndedSession>>;
+    /// 检查存储是否可用（用于就绪探针）
+    async fn health_check(&self) -> Result<()>;
 }
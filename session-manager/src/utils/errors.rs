@@ -1,3 +1,4 @@
+use crate::domain::SessionStatus;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -8,6 +9,13 @@ pub enum SessionManagerError {
     #[error("LiveKit error: {0}")]
     LiveKit(#[from] livekit_api::services::ServiceError),
 
+    #[error("Failed to connect to LiveKit room {room_name}: {source}")]
+    LiveKitConnect {
+        room_name: String,
+        #[source]
+        source: livekit::RoomError,
+    },
+
     #[error("Storage error: {0}")]
     Storage(String),
 
@@ -17,14 +25,56 @@ pub enum SessionManagerError {
     #[error("Configuration error: {0}")]
     Configuration(String),
 
+    #[error("Event sink error: {0}")]
+    EventSink(String),
+
     #[error("Timeout waiting for microservices to join")]
     MicroserviceJoinTimeout,
 
     #[error("Invalid request: {0}")]
     InvalidRequest(String),
 
-    #[error("Internal error: {0}")]
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
+    #[error("Room {room_name} is full ({max_participants} participants)")]
+    RoomFull {
+        room_name: String,
+        max_participants: u32,
+    },
+
+    #[error("Rate limit exceeded, retry after {retry_after_secs} seconds")]
+    RateLimited { retry_after_secs: u64 },
+
+    /// 未结束的会话数已达 `max_sessions` 上限
+    #[error("Session limit of {max_sessions} reached, try again later")]
+    AtCapacity { max_sessions: usize },
+
+    /// 会话状态只能按 `Creating -> WaitingForServices -> Ready -> Active -> Terminating -> Terminated` 前进
+    #[error("Session {session_id} cannot move from {from:?} to {to:?}")]
+    InvalidStatusTransition {
+        session_id: String,
+        from: SessionStatus,
+        to: SessionStatus,
+    },
+
+    /// 实例正在排空，不再接受新会话
+    #[error("Server is draining and does not accept new sessions")]
+    Draining,
+
+    /// 带上下文的内部错误；消息包含完整的原因链，`source()` 可逐层访问原始错误
+    #[error("Internal error: {0:#}")]
     Internal(#[from] anyhow::Error),
 }
 
+impl SessionManagerError {
+    /// 按 `source()` 逐层遍历导致该错误的底层错误（不含自身）
+    pub fn causes(&self) -> impl Iterator<Item = &(dyn std::error::Error + 'static)> {
+        std::iter::successors(std::error::Error::source(self), |e| e.source())
+    }
+}
+
 pub type Result<T> = std::result::Result<T, SessionManagerError>;
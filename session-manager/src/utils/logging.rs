@@ -0,0 +1,123 @@
+use crate::utils::errors::{Result, SessionManagerError};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use tracing::Subscriber;
+use tracing_subscriber::{
+    fmt::MakeWriter, registry::LookupSpan, reload, EnvFilter, Layer, Registry,
+};
+
+/// 可热更新的日志过滤层，需直接叠加在 `Registry` 上
+pub type ReloadableFilter = reload::Layer<EnvFilter, Registry>;
+
+/// 由 `logging.level` 生成默认的过滤指令
+pub fn default_filter_directive(level: &str) -> String {
+    format!(
+        "session_manager={},tower_http=info,livekit={},livekit_api={}",
+        level, level, level
+    )
+}
+
+/// `logging.format` 支持的日志输出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    /// 默认的单行文本格式
+    Full,
+    /// 更紧凑的单行文本格式
+    Compact,
+    /// 多行、便于人工阅读的格式
+    Pretty,
+    /// 每行一个 JSON 对象，便于日志采集
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = SessionManagerError;
+
+    fn from_str(format: &str) -> Result<Self> {
+        match format.trim().to_ascii_lowercase().as_str() {
+            "full" | "text" => Ok(LogFormat::Full),
+            "compact" => Ok(LogFormat::Compact),
+            "pretty" => Ok(LogFormat::Pretty),
+            "json" => Ok(LogFormat::Json),
+            _ => Err(SessionManagerError::Configuration(format!(
+                "Unknown logging.format '{}', expected one of full, compact, pretty, json",
+                format
+            ))),
+        }
+    }
+}
+
+/// 按 `format` 构建输出到 `writer` 的日志格式层
+pub fn fmt_layer<S, W>(format: LogFormat, writer: W) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+    W: for<'writer> MakeWriter<'writer> + Send + Sync + 'static,
+{
+    let layer = tracing_subscriber::fmt::layer()
+        .with_writer(writer)
+        .with_target(true)
+        .with_line_number(true);
+
+    match format {
+        LogFormat::Full => layer.boxed(),
+        LogFormat::Compact => layer.compact().boxed(),
+        LogFormat::Pretty => layer.pretty().boxed(),
+        LogFormat::Json => layer.json().boxed(),
+    }
+}
+
+/// 校验过滤指令，返回对应的 `EnvFilter`
+pub fn parse_filter_directive(directive: &str) -> Result<EnvFilter> {
+    if directive.trim().is_empty() {
+        return Err(SessionManagerError::InvalidRequest(
+            "Log filter directive must not be empty".to_string(),
+        ));
+    }
+
+    EnvFilter::builder().parse(directive).map_err(|e| {
+        SessionManagerError::InvalidRequest(format!(
+            "Invalid log filter directive '{}': {}",
+            directive, e
+        ))
+    })
+}
+
+/// 运行时切换日志过滤指令的句柄
+#[derive(Clone)]
+pub struct LogLevelHandle {
+    handle: reload::Handle<EnvFilter, Registry>,
+    directive: Arc<Mutex<String>>,
+}
+
+impl LogLevelHandle {
+    /// 以 `directive` 创建过滤层及其句柄；过滤层需加入全局 subscriber 后句柄才生效
+    pub fn new(directive: &str) -> Result<(ReloadableFilter, Self)> {
+        let filter = parse_filter_directive(directive)?;
+        let (layer, handle) = reload::Layer::new(filter);
+
+        Ok((
+            layer,
+            Self {
+                handle,
+                directive: Arc::new(Mutex::new(directive.to_string())),
+            },
+        ))
+    }
+
+    /// 当前生效的过滤指令
+    pub fn current(&self) -> String {
+        self.directive.lock().unwrap().clone()
+    }
+
+    /// 校验并切换过滤指令，返回切换前的指令
+    pub fn set(&self, directive: &str) -> Result<String> {
+        let filter = parse_filter_directive(directive)?;
+
+        let mut current = self.directive.lock().unwrap();
+        self.handle.reload(filter).map_err(|e| {
+            SessionManagerError::Configuration(format!("Failed to reload log filter: {}", e))
+        })?;
+
+        Ok(std::mem::replace(&mut *current, directive.to_string()))
+    }
+}
@@ -1 +1,3 @@
 pub mod errors;
+pub mod logging;
+pub mod validation;
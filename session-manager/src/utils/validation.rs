@@ -0,0 +1,99 @@
+use crate::{
+    config::MetadataLimitConfig,
+    domain::IdentityScheme,
+    utils::errors::{Result, SessionManagerError},
+};
+use std::collections::HashMap;
+
+/// 用户标识最大长度（字节）
+pub const MAX_USER_IDENTITY_LEN: usize = 128;
+/// 房间名最大长度（字节）
+pub const MAX_ROOM_NAME_LEN: usize = 128;
+/// 单个会话最长存活时间上限（秒），30 天
+pub const MAX_SESSION_LIFETIME_SECS: u64 = 30 * 24 * 3600;
+
+/// 校验用户标识
+///
+/// 允许的字符：ASCII 字母、数字以及 `-` `_` `.` `@`，长度 1..=128，
+/// 且不得以会话管理器自身使用的身份前缀（见 [`IdentityScheme`]）开头。
+pub fn validate_user_identity(user_identity: &str, identities: &IdentityScheme) -> Result<()> {
+    validate_name("user_identity", user_identity, MAX_USER_IDENTITY_LEN, |c| {
+        c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '@')
+    })?;
+
+    if let Some(prefix) = identities.reserved_prefix(user_identity) {
+        return Err(SessionManagerError::InvalidRequest(format!(
+            "user_identity must not start with reserved prefix '{}'",
+            prefix
+        )));
+    }
+
+    Ok(())
+}
+
+/// 校验房间名
+///
+/// 允许的字符：ASCII 字母、数字以及 `-` `_` `.`，长度 1..=128。
+pub fn validate_room_name(room_name: &str) -> Result<()> {
+    validate_name("room_name", room_name, MAX_ROOM_NAME_LEN, |c| {
+        c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')
+    })
+}
+
+/// 校验 metadata 的键数量与序列化后的大小（含边界值，即恰好等于上限时允许）
+pub fn validate_metadata(
+    field: &str,
+    metadata: &HashMap<String, String>,
+    limits: &MetadataLimitConfig,
+) -> Result<()> {
+    if metadata.len() > limits.max_keys {
+        return Err(SessionManagerError::InvalidRequest(format!(
+            "{} must have at most {} keys, got {}",
+            field,
+            limits.max_keys,
+            metadata.len()
+        )));
+    }
+
+    let size = serde_json::to_vec(metadata)
+        .map_err(|e| SessionManagerError::Internal(e.into()))?
+        .len();
+    if size > limits.max_bytes {
+        return Err(SessionManagerError::InvalidRequest(format!(
+            "{} must be at most {} bytes when serialized, got {}",
+            field, limits.max_bytes, size
+        )));
+    }
+
+    Ok(())
+}
+
+fn validate_name(
+    field: &str,
+    value: &str,
+    max_len: usize,
+    allowed: impl Fn(char) -> bool,
+) -> Result<()> {
+    if value.is_empty() {
+        return Err(SessionManagerError::InvalidRequest(format!(
+            "{} must not be empty",
+            field
+        )));
+    }
+
+    if value.len() > max_len {
+        return Err(SessionManagerError::InvalidRequest(format!(
+            "{} must be at most {} characters",
+            field, max_len
+        )));
+    }
+
+    if let Some(invalid) = value.chars().find(|c| !allowed(*c)) {
+        return Err(SessionManagerError::InvalidRequest(format!(
+            "{} contains invalid character {:?}",
+            field, invalid
+        )));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,160 @@
+use axum::{http::StatusCode, response::IntoResponse, Json, Router};
+use microservice_sdk::{
+    CircuitBreakerConfig, CircuitState, MicroserviceConfig, MicroserviceError, SessionManagerClient,
+};
+use serde_json::json;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpListener;
+
+const COOLDOWN: Duration = Duration::from_millis(300);
+
+/// A session manager that counts requests and fails them while `healthy` is false
+struct FlakySessionManager {
+    url: String,
+    hits: Arc<AtomicUsize>,
+    healthy: Arc<AtomicBool>,
+}
+
+async fn spawn_flaky_session_manager() -> FlakySessionManager {
+    let hits = Arc::new(AtomicUsize::new(0));
+    let healthy = Arc::new(AtomicBool::new(false));
+    let (hits_handle, healthy_handle) = (hits.clone(), healthy.clone());
+    let app = Router::new().fallback(move || {
+        let (hits, healthy) = (hits_handle.clone(), healthy_handle.clone());
+        async move {
+            hits.fetch_add(1, Ordering::SeqCst);
+            if !healthy.load(Ordering::SeqCst) {
+                return (
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    Json(json!({"error": "Unavailable", "message": "overloaded"})),
+                )
+                    .into_response();
+            }
+            Json(json!({
+                "success": true,
+                "service_id": "breaker-service",
+                "message": "registered"
+            }))
+            .into_response()
+        }
+    });
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+
+    FlakySessionManager {
+        url: format!("http://{}", addr),
+        hits,
+        healthy,
+    }
+}
+
+fn client(session_manager_url: String) -> SessionManagerClient {
+    let config = MicroserviceConfig::new(
+        session_manager_url,
+        "breaker-service".to_string(),
+        "http://127.0.0.1:9".to_string(),
+    )
+    .with_circuit_breaker(CircuitBreakerConfig {
+        failure_threshold: 3,
+        cooldown: COOLDOWN,
+    });
+    SessionManagerClient::new(config).unwrap()
+}
+
+/// Fail enough requests to open the circuit
+async fn trip(client: &SessionManagerClient) {
+    for _ in 0..3 {
+        let error = client.register().await.unwrap_err();
+        assert!(
+            matches!(
+                error,
+                MicroserviceError::SessionManagerError { status: 503, .. }
+            ),
+            "{}",
+            error
+        );
+    }
+    assert_eq!(client.circuit_state(), CircuitState::Open);
+}
+
+#[tokio::test]
+async fn test_open_circuit_fails_fast_without_network_calls() {
+    let session_manager = spawn_flaky_session_manager().await;
+    let client = client(session_manager.url.clone());
+    assert_eq!(client.circuit_state(), CircuitState::Closed);
+
+    trip(&client).await;
+    assert_eq!(session_manager.hits.load(Ordering::SeqCst), 3);
+
+    // Further calls, including from clones, never reach the session manager
+    let clone = client.clone();
+    for _ in 0..5 {
+        let error = clone.register().await.unwrap_err();
+        assert!(
+            matches!(error, MicroserviceError::CircuitOpen { retry_after } if retry_after <= COOLDOWN),
+            "{}",
+            error
+        );
+        assert!(matches!(
+            client.notify_ready("breaker-service", "session-1").await,
+            Err(MicroserviceError::CircuitOpen { .. })
+        ));
+    }
+    assert_eq!(session_manager.hits.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_half_open_probe_closes_or_reopens_the_circuit() {
+    let session_manager = spawn_flaky_session_manager().await;
+    let client = client(session_manager.url.clone());
+    trip(&client).await;
+
+    // A failed probe opens the circuit for another cooldown
+    tokio::time::sleep(COOLDOWN).await;
+    assert_eq!(client.circuit_state(), CircuitState::HalfOpen);
+    assert!(client.register().await.is_err());
+    assert_eq!(session_manager.hits.load(Ordering::SeqCst), 4);
+    assert_eq!(client.circuit_state(), CircuitState::Open);
+    assert!(matches!(
+        client.register().await,
+        Err(MicroserviceError::CircuitOpen { .. })
+    ));
+
+    // Once the session manager recovers, the probe closes the circuit
+    session_manager.healthy.store(true, Ordering::SeqCst);
+    tokio::time::sleep(COOLDOWN).await;
+    client.register().await.unwrap();
+    assert_eq!(client.circuit_state(), CircuitState::Closed);
+    assert_eq!(client.circuit_breaker().consecutive_failures(), 0);
+    client.register().await.unwrap();
+    assert_eq!(session_manager.hits.load(Ordering::SeqCst), 6);
+}
+
+#[tokio::test]
+async fn test_client_errors_do_not_open_the_circuit() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let app = Router::new().fallback(|| async {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "InvalidRequest", "message": "bad metadata"})),
+        )
+    });
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    let client = client(format!("http://{}", addr));
+
+    for _ in 0..5 {
+        assert!(matches!(
+            client.register().await,
+            Err(MicroserviceError::SessionManagerError { status: 400, .. })
+        ));
+    }
+    assert_eq!(client.circuit_state(), CircuitState::Closed);
+}
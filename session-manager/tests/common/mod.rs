@@ -0,0 +1,120 @@
+//! Shared helpers for session-manager integration tests
+#![allow(dead_code)]
+
+use reqwest::Client;
+use session_manager::{config::AppConfig, server::Server};
+use std::time::Duration;
+use tokio::{task::JoinHandle, time::sleep};
+
+// Test configuration for LiveKit
+pub const LIVEKIT_URL: &str = "ws://localhost:7880";
+pub const LIVEKIT_API_KEY: &str = "devkey";
+pub const LIVEKIT_API_SECRET: &str =
+    "devkey_secret_that_is_at_least_32_characters_long_for_security";
+
+/// Build a config for a local test server on `port` against the test LiveKit instance
+pub fn test_config(port: u16) -> AppConfig {
+    let mut config = AppConfig::default();
+    config.server.host = "127.0.0.1".to_string();
+    config.server.port = port;
+    config.server.workers = Some(1);
+    config.livekit.server_url = LIVEKIT_URL.to_string();
+    config.livekit.api_key = LIVEKIT_API_KEY.to_string();
+    config.livekit.api_secret = LIVEKIT_API_SECRET.to_string();
+    config.vector_log.enabled = false;
+    config
+}
+
+/// Start a server in the background and return its base URL
+pub async fn start_server(config: AppConfig) -> (String, JoinHandle<()>) {
+    let base_url = format!("http://{}:{}", config.server.host, config.server.port);
+    let server = Server::new(config).await.expect("Failed to create server");
+    let handle = tokio::spawn(async move {
+        server.run().await.expect("Server failed to run");
+    });
+
+    // Wait for server to start
+    sleep(Duration::from_millis(500)).await;
+
+    (base_url, handle)
+}
+
+pub async fn wait_for_livekit() {
+    let client = Client::new();
+    const MAX_ATTEMPTS: u32 = 30;
+
+    for attempt in 1..=MAX_ATTEMPTS {
+        match client.get("http://localhost:7880").send().await {
+            Ok(response) if response.status().is_success() => return,
+            _ => {
+                tracing::debug!(
+                    "Waiting for LiveKit service... attempt {}/{}",
+                    attempt,
+                    MAX_ATTEMPTS
+                );
+                sleep(Duration::from_secs(2)).await;
+            }
+        }
+    }
+
+    panic!(
+        "LiveKit service did not start within {} seconds",
+        MAX_ATTEMPTS * 2
+    );
+}
+
+/// Create a session without microservices and return the parsed response
+pub async fn create_session(
+    client: &Client,
+    base_url: &str,
+    body: serde_json::Value,
+) -> serde_json::Value {
+    let response = client
+        .post(format!("{}/api/v1/create-session", base_url))
+        .json(&body)
+        .send()
+        .await
+        .expect("Session creation request failed");
+    assert!(
+        response.status().is_success(),
+        "Session creation should succeed, got {}",
+        response.status()
+    );
+    response
+        .json()
+        .await
+        .expect("Failed to parse session response")
+}
+
+/// Build a session service over `storage` without starting an HTTP server
+pub fn session_service(
+    storage: std::sync::Arc<session_manager::storage::memory::MemoryStorage>,
+) -> session_manager::services::SessionServiceImpl {
+    let config = test_config(0);
+    session_manager::services::SessionServiceImpl::new(
+        storage,
+        std::sync::Arc::new(session_manager::services::MicroserviceRegistry::new()),
+        config.livekit.clone(),
+        config.livekit.server_url.clone(),
+        session_manager::events::EventBus::new(),
+        session_manager::services::SessionServiceImpl::build_http_client()
+            .expect("Failed to build HTTP client"),
+        session_manager::domain::NotifyRetryPolicy::from(&config.microservices),
+    )
+}
+
+/// Start a stand-in LiveKit server that accepts every room API call and return its URL
+///
+/// Each call gets an empty `200`, which decodes as the default response message, so
+/// sessions without microservices can be created without a real LiveKit instance.
+pub async fn spawn_mock_livekit() -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("Failed to bind mock LiveKit");
+    let addr = listener.local_addr().unwrap();
+    let app = axum::Router::new().fallback(|| async { axum::http::StatusCode::OK });
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    format!("ws://{}", addr)
+}
@@ -0,0 +1,168 @@
+use session_manager::{config::AppConfig, SessionManagerError};
+use std::path::PathBuf;
+
+const BASE: &str = r#"
+[server]
+host = "0.0.0.0"
+port = 8080
+workers = 4
+
+[livekit]
+server_url = "ws://localhost:7880"
+api_key = "devkey"
+api_secret = "secret"
+max_participants = 20
+
+[microservices]
+registration_timeout = 30
+join_timeout = 60
+
+[logging]
+level = "debug"
+format = "json"
+
+[vector_log]
+enabled = true
+endpoint = "http://localhost:8686"
+source_name = "session-manager"
+
+[livekit.participant_timeouts]
+client_timeout_secs = 30
+service_timeout_secs = 90
+
+[discovery]
+static_services = [
+    { service_id = "asr", endpoint = "http://asr:9000" },
+    { service_id = "tts", endpoint = "http://tts:9000" },
+]
+"#;
+
+/// Write `contents` to a file unique to this test
+fn config_file(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "session-manager-{}-{}.toml",
+        std::process::id(),
+        name
+    ));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn test_overlay_overrides_nested_keys_and_keeps_the_rest() {
+    let base = config_file("nested-base", BASE);
+    let overlay = config_file(
+        "nested-overlay",
+        r#"
+[server]
+port = 9090
+
+[livekit]
+server_url = "wss://livekit.prod.example.com"
+hidden_observer = false
+
+[logging]
+level = "info"
+"#,
+    );
+
+    let config = AppConfig::from_files(&[&base, &overlay]).unwrap();
+
+    // Keys set by the overlay win
+    assert_eq!(config.server.port, 9090);
+    assert_eq!(config.livekit.server_url, "wss://livekit.prod.example.com");
+    assert!(!config.livekit.hidden_observer);
+    assert_eq!(config.logging.level, "info");
+    // Sibling keys in the same sections come from the base
+    assert_eq!(config.server.host, "0.0.0.0");
+    assert_eq!(config.server.workers, Some(4));
+    assert_eq!(config.livekit.api_key, "devkey");
+    assert_eq!(config.livekit.max_participants, 20);
+    assert_eq!(config.logging.format, "json");
+    // Sections the overlay does not mention are untouched
+    assert_eq!(config.microservices.join_timeout, 60);
+    assert!(config.vector_log.enabled);
+}
+
+#[test]
+fn test_later_files_win_and_tables_merge_at_every_depth() {
+    let base = config_file("depth-base", BASE);
+    let staging = config_file(
+        "depth-staging",
+        r#"
+[server]
+port = 8081
+
+[livekit.participant_timeouts]
+client_timeout_secs = 120
+
+[discovery]
+static_services = [{ service_id = "llm", endpoint = "http://llm:9000" }]
+"#,
+    );
+    let local = config_file(
+        "depth-local",
+        r#"
+[server]
+port = 8082
+"#,
+    );
+
+    let config = AppConfig::from_files(&[&base, &staging, &local]).unwrap();
+
+    assert_eq!(config.server.port, 8082);
+    let timeouts = &config.livekit.participant_timeouts;
+    assert_eq!(timeouts.client_timeout_secs, 120);
+    assert_eq!(timeouts.service_timeout_secs, 90);
+    assert_eq!(config.livekit.api_key, "devkey");
+    // Arrays are replaced as a whole, not merged element by element
+    let services: Vec<&str> = config
+        .discovery
+        .static_services
+        .iter()
+        .map(|service| service.service_id.as_str())
+        .collect();
+    assert_eq!(services, ["llm"]);
+
+    // Order matters: the base applied last replaces the overlays' values
+    let config = AppConfig::from_files(&[&local, &staging, &base]).unwrap();
+    assert_eq!(config.server.port, 8080);
+    assert_eq!(config.livekit.participant_timeouts.client_timeout_secs, 30);
+    assert_eq!(config.discovery.static_services.len(), 2);
+}
+
+#[test]
+fn test_missing_layers_are_reported() {
+    let base = config_file("missing-base", BASE);
+    let missing = std::env::temp_dir().join("session-manager-does-not-exist.toml");
+
+    let error = AppConfig::from_files(&[&base, &missing]).unwrap_err();
+    assert!(
+        matches!(&error, SessionManagerError::Configuration(message) if message.contains("session-manager-does-not-exist.toml")),
+        "{}",
+        error
+    );
+
+    // Without any file the defaults apply
+    let config = AppConfig::from_files::<PathBuf>(&[]).unwrap();
+    assert_eq!(config.server.port, AppConfig::default().server.port);
+}
+
+#[test]
+fn test_invalid_layers_are_reported() {
+    let base = config_file("invalid-base", BASE);
+    let broken = config_file("invalid-broken", "[server\nport = 1");
+    let error = AppConfig::from_files(&[&base, &broken]).unwrap_err();
+    assert!(
+        matches!(&error, SessionManagerError::Configuration(message) if message.contains("invalid-broken")),
+        "{}",
+        error
+    );
+
+    // An overlay cannot change a value's type
+    let wrong_type = config_file("invalid-type", "[server]\nport = \"http\"\n");
+    assert!(matches!(
+        AppConfig::from_files(&[&base, &wrong_type]),
+        Err(SessionManagerError::Configuration(_))
+    ));
+}
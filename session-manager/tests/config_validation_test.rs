@@ -0,0 +1,198 @@
+use session_manager::{config::AppConfig, server::Server, SessionManagerError};
+
+mod common;
+
+fn valid_config() -> AppConfig {
+    common::test_config(8080)
+}
+
+/// The problems reported for `config`, or an empty list if it is valid
+fn problems(config: &AppConfig) -> String {
+    match config.validate() {
+        Ok(()) => String::new(),
+        Err(SessionManagerError::Configuration(message)) => message,
+        Err(e) => panic!("unexpected error: {}", e),
+    }
+}
+
+fn assert_rejected(config: AppConfig, problem: &str) {
+    let message = problems(&config);
+    assert!(
+        message.contains(problem),
+        "expected {:?} in {:?}",
+        problem,
+        message
+    );
+}
+
+#[test]
+fn test_default_and_test_configs_are_valid() {
+    assert_eq!(problems(&AppConfig::default()), "");
+    assert_eq!(problems(&valid_config()), "");
+}
+
+#[test]
+fn test_empty_required_fields_are_rejected() {
+    let mut config = valid_config();
+    config.livekit.api_key = String::new();
+    assert_rejected(config, "livekit.api_key must not be empty");
+
+    let mut config = valid_config();
+    config.livekit.api_secret = " ".to_string();
+    assert_rejected(config, "livekit.api_secret must not be empty");
+
+    let mut config = valid_config();
+    config.server.host = String::new();
+    assert_rejected(config, "server.host must not be empty");
+
+    let mut config = valid_config();
+    config.vector_log.enabled = true;
+    config.vector_log.source_name = String::new();
+    assert_rejected(config, "vector_log.source_name must not be empty");
+}
+
+#[test]
+fn test_zero_port_is_rejected() {
+    let mut config = valid_config();
+    config.server.port = 0;
+    assert_rejected(config, "server.port must be greater than 0");
+}
+
+#[test]
+fn test_unparseable_livekit_url_is_rejected() {
+    let mut config = valid_config();
+    config.livekit.server_url = "localhost 7880".to_string();
+    assert_rejected(
+        config,
+        "livekit.server_url 'localhost 7880' is not a valid URL",
+    );
+}
+
+#[test]
+fn test_unsupported_livekit_scheme_is_rejected() {
+    let mut config = valid_config();
+    config.livekit.server_url = "ftp://localhost:7880".to_string();
+    assert_rejected(config, "uses unsupported scheme 'ftp'");
+
+    for url in [
+        "ws://lk:7880",
+        "wss://lk.example.com",
+        "http://127.0.0.1:7880",
+    ] {
+        let mut config = valid_config();
+        config.livekit.server_url = url.to_string();
+        assert_eq!(problems(&config), "", "{}", url);
+    }
+}
+
+#[test]
+fn test_zero_timeouts_are_rejected() {
+    let mut config = valid_config();
+    config.livekit.api_timeout_ms = 0;
+    assert_rejected(config, "livekit.api_timeout_ms must be greater than 0");
+
+    let mut config = valid_config();
+    config.microservices.registration_timeout = 0;
+    assert_rejected(
+        config,
+        "microservices.registration_timeout must be greater than 0",
+    );
+
+    let mut config = valid_config();
+    config.microservices.join_timeout = 0;
+    assert_rejected(config, "microservices.join_timeout must be greater than 0");
+
+    let mut config = valid_config();
+    config.sweeper.interval_secs = 0;
+    assert_rejected(config, "sweeper.interval_secs must be greater than 0");
+
+    let mut config = valid_config();
+    config.sweeper.terminated_grace_secs = u64::MAX;
+    assert_rejected(
+        config,
+        "sweeper.terminated_grace_secs must not exceed 604800 (7 days)",
+    );
+
+    // Disabled features are not checked
+    let mut config = valid_config();
+    config.sweeper.enabled = false;
+    config.sweeper.interval_secs = 0;
+    assert_eq!(problems(&config), "");
+}
+
+#[test]
+fn test_invalid_vector_endpoint_is_rejected() {
+    for endpoint in [
+        "localhost",
+        "localhost:",
+        ":9000",
+        "localhost:port",
+        "vector/logs:9000",
+    ] {
+        let mut config = valid_config();
+        config.vector_log.enabled = true;
+        config.vector_log.endpoint = endpoint.to_string();
+        assert_rejected(config, "must be host:port");
+    }
+
+    let mut config = valid_config();
+    config.vector_log.enabled = true;
+    config.vector_log.endpoint = "http://vector:9000".to_string();
+    assert_eq!(problems(&config), "");
+
+    // Not used while vector logging is off
+    let mut config = valid_config();
+    config.vector_log.endpoint = "localhost".to_string();
+    assert_eq!(problems(&config), "");
+}
+
+#[test]
+fn test_all_problems_are_listed_together() {
+    let mut config = valid_config();
+    config.server.port = 0;
+    config.livekit.api_key = String::new();
+    config.livekit.server_url = "not a url".to_string();
+    config.logging.format = "xml".to_string();
+
+    let message = problems(&config);
+    assert!(
+        message.starts_with("Invalid configuration: "),
+        "{}",
+        message
+    );
+    for problem in [
+        "server.port",
+        "livekit.api_key",
+        "livekit.server_url",
+        "logging.format",
+    ] {
+        assert!(
+            message.contains(problem),
+            "{} missing from {}",
+            problem,
+            message
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_server_rejects_invalid_config() {
+    let mut config = valid_config();
+    config.livekit.api_key = String::new();
+
+    let error = Server::new(config).await.err().expect("config is invalid");
+    assert!(matches!(error, SessionManagerError::Configuration(_)));
+}
+
+#[test]
+fn test_livekit_api_url_uses_http_schemes() {
+    for (server_url, api_url) in [
+        ("ws://livekit:7880", "http://livekit:7880"),
+        ("wss://livekit.example.com", "https://livekit.example.com"),
+        ("https://livekit.example.com", "https://livekit.example.com"),
+    ] {
+        let mut config = valid_config();
+        config.livekit.server_url = server_url.to_string();
+        assert_eq!(config.livekit.api_url(), api_url);
+    }
+}
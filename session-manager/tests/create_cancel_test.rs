@@ -0,0 +1,208 @@
+use async_trait::async_trait;
+use axum::{extract::Request, http::StatusCode, response::IntoResponse, Router};
+use session_manager::{
+    domain::{NotifyRetryPolicy, Session},
+    events::{EventBus, RecordedEvent},
+    services::{
+        session_service::CreateSessionRequest, MicroserviceRegistry, SessionService,
+        SessionServiceImpl,
+    },
+    storage::{memory::MemoryStorage, EndedSession, SessionStorage},
+    Result,
+};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Notify;
+
+mod common;
+
+/// Records the room API calls it gets and answers each with an empty `200`, or with
+/// `already_exists` for room creation when `rooms_exist` is set
+async fn spawn_recording_livekit(calls: Arc<Mutex<Vec<String>>>, rooms_exist: bool) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let app = Router::new().fallback(move |request: Request| async move {
+        let path = request.uri().path().to_string();
+        calls.lock().unwrap().push(path.clone());
+        if rooms_exist && path.contains("CreateRoom") {
+            let body =
+                serde_json::json!({ "code": "already_exists", "msg": "room already exists" });
+            return (StatusCode::CONFLICT, body.to_string()).into_response();
+        }
+        StatusCode::OK.into_response()
+    });
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    format!("ws://{}", addr)
+}
+
+/// Memory storage whose saves hang until released, so creation can be cancelled after
+/// the room exists
+struct StalledStorage {
+    inner: MemoryStorage,
+    save_started: Notify,
+    release: Notify,
+}
+
+#[async_trait]
+impl SessionStorage for StalledStorage {
+    async fn save_session(&self, session: &Session) -> Result<()> {
+        self.save_started.notify_one();
+        self.release.notified().await;
+        self.inner.save_session(session).await
+    }
+
+    async fn get_session(&self, session_id: &str) -> Result<Option<Session>> {
+        self.inner.get_session(session_id).await
+    }
+
+    async fn update_session(&self, session: &Session) -> Result<()> {
+        self.inner.update_session(session).await
+    }
+
+    async fn delete_session(&self, session_id: &str) -> Result<()> {
+        self.inner.delete_session(session_id).await
+    }
+
+    async fn list_sessions(&self) -> Result<Vec<Session>> {
+        self.inner.list_sessions().await
+    }
+
+    async fn list_session_ids(&self) -> Result<Vec<String>> {
+        self.inner.list_session_ids().await
+    }
+
+    async fn append_event(
+        &self,
+        session_id: &str,
+        event: RecordedEvent,
+        capacity: usize,
+    ) -> Result<()> {
+        self.inner.append_event(session_id, event, capacity).await
+    }
+
+    async fn get_events(&self, session_id: &str) -> Result<Vec<RecordedEvent>> {
+        self.inner.get_events(session_id).await
+    }
+
+    async fn get_ended_session(&self, session_id: &str) -> Result<Option<EndedSession>> {
+        self.inner.get_ended_session(session_id).await
+    }
+
+    async fn health_check(&self) -> Result<()> {
+        self.inner.health_check().await
+    }
+}
+
+fn request(user_identity: &str) -> CreateSessionRequest {
+    CreateSessionRequest {
+        user_identity: user_identity.to_string(),
+        user_name: None,
+        room_name: None,
+        metadata: None,
+        required_services: None,
+        required_service_types: None,
+        reuse_existing: false,
+        observe: false,
+        owner: None,
+        max_lifetime_secs: None,
+        min_ready_services: None,
+    }
+}
+
+fn count(calls: &Mutex<Vec<String>>, method: &str) -> usize {
+    calls
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|path| path.ends_with(method))
+        .count()
+}
+
+/// A session service against a recording LiveKit stand-in and stalling storage
+async fn stalled_service(
+    rooms_exist: bool,
+) -> (
+    Arc<SessionServiceImpl>,
+    Arc<StalledStorage>,
+    Arc<Mutex<Vec<String>>>,
+) {
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let mut config = common::test_config(0);
+    config.livekit.server_url = spawn_recording_livekit(calls.clone(), rooms_exist).await;
+    let storage = Arc::new(StalledStorage {
+        inner: MemoryStorage::new(),
+        save_started: Notify::new(),
+        release: Notify::new(),
+    });
+    let service = Arc::new(SessionServiceImpl::new(
+        storage.clone(),
+        Arc::new(MicroserviceRegistry::new()),
+        config.livekit.clone(),
+        config.livekit.server_url.clone(),
+        EventBus::new(),
+        SessionServiceImpl::build_http_client().unwrap(),
+        NotifyRetryPolicy::from(&config.microservices),
+    ));
+    (service, storage, calls)
+}
+
+#[tokio::test]
+async fn test_aborted_creation_deletes_the_room() {
+    let (service, storage, calls) = stalled_service(false).await;
+
+    let create = tokio::spawn({
+        let service = service.clone();
+        async move { service.create_session(request("cancelled-user")).await }
+    });
+
+    // The room exists and the session is being stored when the caller goes away
+    tokio::time::timeout(Duration::from_secs(5), storage.save_started.notified())
+        .await
+        .expect("creation never reached the save");
+    assert_eq!(count(&calls, "CreateRoom"), 1);
+    create.abort();
+    assert!(create.await.unwrap_err().is_cancelled());
+
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while count(&calls, "DeleteRoom") == 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("room of the cancelled session was not deleted");
+    assert!(storage.list_session_ids().await.unwrap().is_empty());
+
+    // Completed creations keep their room
+    storage.release.notify_one();
+    let (session, _) = service.create_session(request("kept-user")).await.unwrap();
+    assert!(storage.get_session(&session.id).await.unwrap().is_some());
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(count(&calls, "CreateRoom"), 2);
+    assert_eq!(count(&calls, "DeleteRoom"), 1);
+}
+
+#[tokio::test]
+async fn test_aborted_creation_keeps_a_shared_room() {
+    let (service, storage, calls) = stalled_service(true).await;
+
+    // Another session already created the room this one joins
+    let mut shared = request("second-user");
+    shared.room_name = Some("support-desk".to_string());
+    let create = tokio::spawn({
+        let service = service.clone();
+        async move { service.create_session(shared).await }
+    });
+
+    tokio::time::timeout(Duration::from_secs(5), storage.save_started.notified())
+        .await
+        .expect("creation never reached the save");
+    assert_eq!(count(&calls, "CreateRoom"), 1);
+    create.abort();
+    assert!(create.await.unwrap_err().is_cancelled());
+
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(count(&calls, "DeleteRoom"), 0);
+    assert!(storage.list_session_ids().await.unwrap().is_empty());
+}
@@ -0,0 +1,75 @@
+use microservice_sdk::{CreateSessionRequest, MicroserviceError};
+use serde_json::json;
+
+#[test]
+fn test_builder_produces_expected_json() {
+    let request = CreateSessionRequest::builder()
+        .user_identity("builder-user")
+        .room_name("support-desk")
+        .required_service("asr-service")
+        .required_service("tts-service")
+        .required_service_type("LLM")
+        .metadata_entry("robot", "arm-7")
+        .metadata_entry("site", "lab")
+        .metadata_entry("robot", "arm-8")
+        .reuse_existing(true)
+        .observe(false)
+        .min_ready_services(2)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        json!({
+            "user_identity": "builder-user",
+            "room_name": "support-desk",
+            "required_services": ["asr-service", "tts-service"],
+            "required_service_types": ["LLM"],
+            "metadata": { "robot": "arm-8", "site": "lab" },
+            "reuse_existing": true,
+            "observe": false,
+            "min_ready_services": 2
+        })
+    );
+}
+
+#[test]
+fn test_minimal_builder_matches_new() {
+    let built = CreateSessionRequest::builder()
+        .user_identity("plain-user")
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        serde_json::to_value(&built).unwrap(),
+        serde_json::to_value(CreateSessionRequest::new("plain-user")).unwrap()
+    );
+    assert_eq!(
+        serde_json::to_value(&built).unwrap(),
+        json!({ "user_identity": "plain-user" })
+    );
+}
+
+#[test]
+fn test_builder_rejects_invalid_requests() {
+    for builder in [
+        CreateSessionRequest::builder().room_name("no-user"),
+        CreateSessionRequest::builder().user_identity("  "),
+        CreateSessionRequest::builder()
+            .user_identity("user")
+            .max_lifetime_secs(0),
+        CreateSessionRequest::builder()
+            .user_identity("user")
+            .min_ready_services(0),
+    ] {
+        let error = builder.build().unwrap_err();
+        assert!(
+            matches!(error, MicroserviceError::InvalidRequest(_)),
+            "{}",
+            error
+        );
+    }
+
+    let error = CreateSessionRequest::builder().build().unwrap_err();
+    assert!(error.to_string().contains("user_identity"), "{}", error);
+}
@@ -0,0 +1,94 @@
+use reqwest::{Client, StatusCode};
+use serde_json::{json, Value};
+
+mod common;
+
+const ADMIN_TOKEN: &str = "rolling-deploy-admin";
+
+async fn admin_post(client: &Client, url: String, token: &str) -> reqwest::Response {
+    client.post(url).bearer_auth(token).send().await.unwrap()
+}
+
+#[tokio::test]
+async fn test_drain_rejects_new_sessions_but_keeps_existing_ones() {
+    let mut config = common::test_config(8803);
+    config.livekit.server_url = common::spawn_mock_livekit().await;
+    config.auth.admin_token = Some(ADMIN_TOKEN.to_string());
+    let (base_url, server_handle) = common::start_server(config).await;
+    let client = Client::new();
+    let request = json!({ "user_identity": "drain-user", "required_services": [] });
+
+    let session = common::create_session(&client, &base_url, request.clone()).await;
+    let session_id = session["session_id"].as_str().unwrap();
+
+    // Draining needs the admin token
+    let response = admin_post(&client, format!("{}/api/v1/admin/drain", base_url), "wrong").await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = admin_post(
+        &client,
+        format!("{}/api/v1/admin/drain", base_url),
+        ADMIN_TOKEN,
+    )
+    .await;
+    assert!(response.status().is_success(), "{}", response.status());
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["draining"], true);
+
+    let response = client
+        .post(format!("{}/api/v1/create-session", base_url))
+        .json(&request)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["error"], "Draining");
+
+    // The existing session stays reachable
+    let response = client
+        .get(format!("{}/api/v1/sessions/{}", base_url, session_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let status: Value = response.json().await.unwrap();
+    assert_eq!(status["session_id"], session_id);
+    let response = client
+        .get(format!("{}/sessions/{}/events", base_url, session_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    drop(response);
+
+    let response = client
+        .get(format!("{}/ready", base_url))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["status"], "draining");
+
+    let response = admin_post(
+        &client,
+        format!("{}/api/v1/admin/undrain", base_url),
+        ADMIN_TOKEN,
+    )
+    .await;
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["draining"], false);
+    common::create_session(&client, &base_url, request).await;
+    let body: Value = client
+        .get(format!("{}/ready", base_url))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_ne!(body["status"], "draining");
+
+    server_handle.abort();
+}
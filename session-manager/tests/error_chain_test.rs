@@ -0,0 +1,52 @@
+use livekit_api::access_token::AccessTokenError;
+use session_manager::{domain::Session, SessionManagerError};
+use std::collections::HashMap;
+use std::error::Error;
+
+mod common;
+
+#[tokio::test]
+async fn test_token_error_keeps_source_chain() {
+    let mut config = common::test_config(0).livekit;
+    config.api_secret = String::new();
+    let session = Session::new(
+        "session-chain".to_string(),
+        "room-chain".to_string(),
+        HashMap::new(),
+    );
+
+    let error = session.generate_client_token(&config).await.unwrap_err();
+    assert!(matches!(error, SessionManagerError::Internal(_)));
+    assert_eq!(
+        error.to_string(),
+        "Internal error: Failed to generate client token: Invalid API Key or Secret Key"
+    );
+
+    let causes: Vec<String> = error.causes().map(ToString::to_string).collect();
+    assert_eq!(
+        causes,
+        vec![
+            "Failed to generate client token",
+            "Invalid API Key or Secret Key"
+        ]
+    );
+    let root = error.causes().last().unwrap();
+    assert!(matches!(
+        root.downcast_ref::<AccessTokenError>(),
+        Some(AccessTokenError::InvalidKeys)
+    ));
+}
+
+#[test]
+fn test_livekit_connect_error_exposes_room_error() {
+    let error = SessionManagerError::LiveKitConnect {
+        room_name: "room-chain".to_string(),
+        source: livekit::RoomError::AlreadyClosed,
+    };
+
+    let source = error.source().expect("connect error has a source");
+    assert!(source.downcast_ref::<livekit::RoomError>().is_some());
+    assert!(error
+        .to_string()
+        .starts_with("Failed to connect to LiveKit room room-chain"));
+}
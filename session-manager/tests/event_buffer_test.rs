@@ -0,0 +1,105 @@
+use futures::StreamExt;
+use reqwest::Client;
+use serde_json::json;
+use session_manager::events::{EventBus, SequencedEvent, SessionEvent};
+use std::time::Duration;
+
+mod common;
+
+fn error_event(session_id: &str, message: &str) -> SessionEvent {
+    SessionEvent::Error {
+        session_id: session_id.to_string(),
+        message: message.to_string(),
+    }
+}
+
+fn message(event: &SequencedEvent) -> &str {
+    match &event.event {
+        SessionEvent::Error { message, .. } => message,
+        other => panic!("unexpected event {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_events_without_subscribers_are_delivered_to_next_subscriber() {
+    let event_bus = EventBus::new();
+    drop(event_bus.create_session_stream("s1".to_string()));
+
+    event_bus.publish_to_session("s1", error_event("s1", "first"));
+    event_bus.publish_to_session("s1", error_event("s1", "second"));
+
+    let mut subscription = event_bus.get_session_stream("s1").unwrap();
+    let pending: Vec<&str> = subscription.pending.iter().map(message).collect();
+    assert_eq!(pending, ["first", "second"]);
+
+    // Later events arrive live, and pending events are handed out only once
+    event_bus.publish_to_session("s1", error_event("s1", "third"));
+    assert_eq!(
+        message(&subscription.receiver.recv().await.unwrap()),
+        "third"
+    );
+    assert!(event_bus
+        .get_session_stream("s1")
+        .unwrap()
+        .pending
+        .is_empty());
+    assert_eq!(event_bus.dropped_events(), 0);
+}
+
+#[tokio::test]
+async fn test_dropped_events_are_counted() {
+    let event_bus = EventBus::new();
+    drop(event_bus.create_session_stream("s1".to_string()));
+
+    // The pending buffer keeps the newest 100 events
+    for i in 0..105 {
+        event_bus.publish_to_session("s1", error_event("s1", &i.to_string()));
+    }
+    assert_eq!(event_bus.dropped_events(), 5);
+
+    let subscription = event_bus.get_session_stream("s1").unwrap();
+    assert_eq!(subscription.pending.len(), 100);
+    assert_eq!(message(&subscription.pending[0]), "5");
+
+    event_bus.publish_to_session("unknown", error_event("unknown", "lost"));
+    assert_eq!(event_bus.dropped_events(), 6);
+}
+
+#[tokio::test]
+async fn test_sse_subscriber_receives_session_created_after_the_fact() {
+    let mut config = common::test_config(8780);
+    config.livekit.server_url = common::spawn_mock_livekit().await;
+    let (base_url, server_handle) = common::start_server(config).await;
+    let client = Client::new();
+
+    // SessionCreated is published before anyone can subscribe
+    let session = common::create_session(
+        &client,
+        &base_url,
+        json!({ "user_identity": "late-subscriber", "required_services": [] }),
+    )
+    .await;
+    let session_id = session["session_id"].as_str().unwrap();
+
+    let response = client
+        .get(format!("{}/sessions/{}/events", base_url, session_id))
+        .send()
+        .await
+        .expect("Subscribe failed");
+    assert!(response.status().is_success());
+
+    let mut body = response.bytes_stream();
+    let mut received = String::new();
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+    while !received.contains("event: session_created") {
+        let chunk = tokio::time::timeout_at(deadline, body.next())
+            .await
+            .expect("session_created not delivered")
+            .expect("stream ended")
+            .expect("stream error");
+        received.push_str(&String::from_utf8_lossy(&chunk));
+    }
+    assert!(received.contains(session_id));
+
+    server_handle.abort();
+}
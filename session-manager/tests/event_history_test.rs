@@ -0,0 +1,183 @@
+use chrono::Utc;
+use reqwest::Client;
+use serde_json::{json, Value};
+use session_manager::{
+    domain::Session,
+    events::{RecordedEvent, SessionEvent},
+    storage::{memory::MemoryStorage, SessionStorage},
+};
+use std::collections::HashMap;
+use std::time::Duration;
+
+mod common;
+
+fn recorded(service_id: &str) -> RecordedEvent {
+    RecordedEvent {
+        timestamp: Utc::now(),
+        seq: Some(1),
+        event: SessionEvent::MicroserviceJoined {
+            session_id: "s1".to_string(),
+            service_id: service_id.to_string(),
+        },
+    }
+}
+
+#[tokio::test]
+async fn test_history_keeps_the_latest_events_up_to_capacity() {
+    let storage = MemoryStorage::new();
+    for service_id in ["asr", "tts", "llm"] {
+        storage
+            .append_event("s1", recorded(service_id), 2)
+            .await
+            .unwrap();
+    }
+
+    let services: Vec<String> = storage
+        .get_events("s1")
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|recorded| match recorded.event {
+            SessionEvent::MicroserviceJoined { service_id, .. } => service_id,
+            other => panic!("unexpected event {:?}", other),
+        })
+        .collect();
+    assert_eq!(services, ["tts", "llm"]);
+    assert!(storage.get_events("other").await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_history_outlives_the_session_for_the_retention_period() {
+    let storage = MemoryStorage::with_history_retention(Duration::from_millis(200));
+    let mut session = Session::new("s1".to_string(), "room-1".to_string(), HashMap::new());
+    session.owner = Some("alice".to_string());
+    storage.save_session(&session).await.unwrap();
+    storage
+        .append_event("s1", recorded("asr"), 10)
+        .await
+        .unwrap();
+
+    storage.delete_session("s1").await.unwrap();
+    assert!(storage.get_session("s1").await.unwrap().is_none());
+    assert_eq!(storage.get_events("s1").await.unwrap().len(), 1);
+    let ended = storage.get_ended_session("s1").await.unwrap().unwrap();
+    assert_eq!(ended.owner.as_deref(), Some("alice"));
+
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert!(storage.get_events("s1").await.unwrap().is_empty());
+    assert!(storage.get_ended_session("s1").await.unwrap().is_none());
+
+    // Without retention the history goes with the session
+    let storage = MemoryStorage::new();
+    storage.save_session(&session).await.unwrap();
+    storage
+        .append_event("s1", recorded("asr"), 10)
+        .await
+        .unwrap();
+    storage.delete_session("s1").await.unwrap();
+    assert!(storage.get_events("s1").await.unwrap().is_empty());
+    assert!(storage.get_ended_session("s1").await.unwrap().is_none());
+}
+
+#[test]
+fn test_recorded_event_serializes_flat() {
+    let value = serde_json::to_value(recorded("asr")).unwrap();
+    assert_eq!(value["type"], "MicroserviceJoined");
+    assert_eq!(value["service_id"], "asr");
+    assert!(value["timestamp"].is_string());
+    assert_eq!(value["seq"], 1);
+
+    let parsed: RecordedEvent = serde_json::from_value(value).unwrap();
+    assert_eq!(parsed.event.event_name(), "microservice_joined");
+}
+
+#[tokio::test]
+async fn test_history_of_a_completed_session() {
+    let mut config = common::test_config(8801);
+    config.livekit.server_url = common::spawn_mock_livekit().await;
+    let (base_url, server_handle) = common::start_server(config).await;
+    let client = Client::new();
+
+    let session = common::create_session(
+        &client,
+        &base_url,
+        json!({
+            "user_identity": "history-user",
+            "required_services": []
+        }),
+    )
+    .await;
+    let session_id = session["session_id"].as_str().unwrap();
+
+    let response = client
+        .patch(format!(
+            "{}/api/v1/sessions/{}/metadata",
+            base_url, session_id
+        ))
+        .json(&json!({ "robot": "arm-7" }))
+        .send()
+        .await
+        .unwrap();
+    assert!(response.status().is_success(), "{}", response.status());
+    let response = client
+        .delete(format!("{}/api/v1/sessions/{}", base_url, session_id))
+        .send()
+        .await
+        .unwrap();
+    assert!(response.status().is_success(), "{}", response.status());
+
+    // Events are written in the background
+    let history_url = format!("{}/api/v1/sessions/{}/events/history", base_url, session_id);
+    let mut types = Vec::new();
+    for _ in 0..50 {
+        let history: Value = client
+            .get(&history_url)
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(history["session_id"], session_id);
+        types = history["events"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|event| {
+                assert!(event["timestamp"].is_string());
+                match event["status"].as_str() {
+                    Some(status) => format!("{}:{}", event["type"].as_str().unwrap(), status),
+                    None => event["type"].as_str().unwrap().to_string(),
+                }
+            })
+            .collect();
+        if types.len() >= 4 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+
+    let position = |name: &str| {
+        types
+            .iter()
+            .position(|t| t == name)
+            .unwrap_or_else(|| panic!("{} missing from {:?}", name, types))
+    };
+    assert!(position("SessionCreated") < position("MetadataChanged"));
+    assert!(position("MetadataChanged") < position("SessionStatusChanged:Terminating"));
+    assert!(
+        position("SessionStatusChanged:Terminating") < position("SessionStatusChanged:Terminated")
+    );
+
+    let response = client
+        .get(format!(
+            "{}/api/v1/sessions/missing/events/history",
+            base_url
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+
+    server_handle.abort();
+}
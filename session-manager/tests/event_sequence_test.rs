@@ -0,0 +1,145 @@
+use futures::StreamExt;
+use reqwest::Client;
+use serde_json::json;
+use session_manager::events::{EventBus, EventReceiver, SequencedEvent, SessionEvent};
+use std::collections::HashMap;
+use std::time::Duration;
+
+mod common;
+
+fn joined(session_id: &str, i: usize) -> SessionEvent {
+    SessionEvent::MicroserviceJoined {
+        session_id: session_id.to_string(),
+        service_id: format!("service-{}", i),
+    }
+}
+
+fn drain(receiver: &mut EventReceiver) -> Vec<SequencedEvent> {
+    std::iter::from_fn(|| receiver.try_recv().ok()).collect()
+}
+
+#[tokio::test]
+async fn test_sequence_numbers_increase_across_a_burst() {
+    let event_bus = EventBus::with_capacity(10_000, 10_000);
+    let mut s1 = event_bus.create_session_stream("s1".to_string());
+    let mut s2 = event_bus.create_session_stream("s2".to_string());
+    let mut global = event_bus.subscribe_global();
+
+    // Concurrent publishers interleaving events of two sessions
+    let publishers: Vec<_> = (0..8)
+        .map(|publisher| {
+            let event_bus = event_bus.clone();
+            tokio::spawn(async move {
+                for i in 0..250 {
+                    let session_id = if (publisher + i) % 2 == 0 { "s1" } else { "s2" };
+                    event_bus.publish_to_session(session_id, joined(session_id, i));
+                    if i % 50 == 0 {
+                        tokio::task::yield_now().await;
+                    }
+                }
+            })
+        })
+        .collect();
+    for publisher in publishers {
+        publisher.await.unwrap();
+    }
+
+    // Each session stream sees 1, 2, 3, ... without gaps
+    for (session_id, receiver) in [("s1", &mut s1), ("s2", &mut s2)] {
+        let seqs: Vec<u64> = drain(receiver)
+            .into_iter()
+            .map(|event| {
+                assert_eq!(event.event.session_id(), Some(session_id));
+                event.seq.unwrap()
+            })
+            .collect();
+        assert_eq!(seqs, (1..=1000).collect::<Vec<u64>>(), "{}", session_id);
+    }
+
+    // The global stream keeps every session's events in sequence order
+    let mut last_seq: HashMap<String, u64> = HashMap::new();
+    let events = drain(&mut global);
+    assert_eq!(events.len(), 2000);
+    for event in events {
+        let session_id = event.event.session_id().unwrap().to_string();
+        let seq = event.seq.unwrap();
+        let last = last_seq.insert(session_id.clone(), seq).unwrap_or(0);
+        assert_eq!(
+            seq,
+            last + 1,
+            "{} jumped from {} to {}",
+            session_id,
+            last,
+            seq
+        );
+    }
+}
+
+#[test]
+fn test_unsequenced_events_and_wire_format() {
+    let event_bus = EventBus::new();
+    let mut global = event_bus.subscribe_global();
+    event_bus.create_session_stream("s1".to_string());
+
+    event_bus.publish_to_session("unknown", joined("unknown", 0));
+    event_bus.publish_global(joined("s1", 1));
+    event_bus.publish_to_session("s1", joined("s1", 2));
+
+    let events = drain(&mut global);
+    let seqs: Vec<Option<u64>> = events.iter().map(|event| event.seq).collect();
+    assert_eq!(seqs, [None, None, Some(1)]);
+
+    // The sequence number sits next to the event's own fields
+    let value = serde_json::to_value(&events[2]).unwrap();
+    assert_eq!(value["seq"], 1);
+    assert_eq!(value["type"], "MicroserviceJoined");
+    assert!(serde_json::to_value(&events[0])
+        .unwrap()
+        .get("seq")
+        .is_none());
+    let parsed: SequencedEvent = serde_json::from_value(value).unwrap();
+    assert_eq!(parsed.seq, Some(1));
+}
+
+#[tokio::test]
+async fn test_session_sse_events_carry_their_sequence_as_id() {
+    let mut config = common::test_config(8805);
+    config.livekit.server_url = common::spawn_mock_livekit().await;
+    let (base_url, server_handle) = common::start_server(config).await;
+    let client = Client::new();
+
+    let session = common::create_session(
+        &client,
+        &base_url,
+        json!({ "user_identity": "sequence-user", "required_services": [] }),
+    )
+    .await;
+    let session_id = session["session_id"].as_str().unwrap();
+
+    let response = client
+        .get(format!("{}/sessions/{}/events", base_url, session_id))
+        .send()
+        .await
+        .expect("Subscribe failed");
+    assert!(response.status().is_success());
+
+    let mut body = response.bytes_stream();
+    let mut received = String::new();
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+    while !received.contains("event: session_created") {
+        let chunk = tokio::time::timeout_at(deadline, body.next())
+            .await
+            .expect("session_created not delivered")
+            .expect("stream ended")
+            .expect("stream error");
+        received.push_str(&String::from_utf8_lossy(&chunk));
+    }
+    let created = received
+        .split("\n\n")
+        .find(|event| event.contains("event: session_created"))
+        .unwrap();
+    assert!(created.lines().any(|line| line == "id: 1"), "{}", created);
+    assert!(created.contains(r#""seq":1"#), "{}", created);
+
+    server_handle.abort();
+}
@@ -0,0 +1,161 @@
+use async_trait::async_trait;
+use serde_json::Value;
+use session_manager::{
+    events::{EventBus, RecordedEvent, SessionEvent},
+    services::{event_subject, EventSink},
+    Result,
+};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Notify;
+
+/// Keeps every event it receives
+#[derive(Debug, Default)]
+struct MemorySink {
+    events: Mutex<Vec<RecordedEvent>>,
+}
+
+impl MemorySink {
+    fn events(&self) -> Vec<Value> {
+        self.events
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|recorded| json(&recorded.event))
+            .collect()
+    }
+}
+
+fn json(event: &SessionEvent) -> Value {
+    serde_json::to_value(event).unwrap()
+}
+
+#[async_trait]
+impl EventSink for MemorySink {
+    fn name(&self) -> &str {
+        "memory"
+    }
+
+    async fn send(&self, event: &RecordedEvent) -> Result<()> {
+        self.events.lock().unwrap().push(event.clone());
+        Ok(())
+    }
+}
+
+/// Never finishes delivering until released
+#[derive(Debug, Default)]
+struct StuckSink {
+    release: Notify,
+}
+
+#[async_trait]
+impl EventSink for StuckSink {
+    fn name(&self) -> &str {
+        "stuck"
+    }
+
+    async fn send(&self, _event: &RecordedEvent) -> Result<()> {
+        self.release.notified().await;
+        Ok(())
+    }
+}
+
+fn session_created(session_id: &str) -> SessionEvent {
+    SessionEvent::SessionCreated {
+        session_id: session_id.to_string(),
+        room_name: format!("room-{}", session_id),
+        access_token: "token".to_string(),
+        livekit_url: "ws://localhost:7880".to_string(),
+    }
+}
+
+async fn wait_for_events(sink: &MemorySink, count: usize) -> Vec<Value> {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            let events = sink.events();
+            if events.len() >= count {
+                return events;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("sink did not receive all events")
+}
+
+#[tokio::test]
+async fn test_all_published_events_are_forwarded_to_sinks() {
+    let first = Arc::new(MemorySink::default());
+    let second = Arc::new(MemorySink::default());
+    let event_bus = EventBus::new()
+        .with_sink(first.clone(), 100)
+        .with_sink(second.clone(), 100);
+    let mut subscriber = event_bus.create_session_stream("session-1".to_string());
+
+    let mut published = Vec::new();
+    for i in 0..20 {
+        let event = SessionEvent::MicroserviceJoined {
+            session_id: "session-1".to_string(),
+            service_id: format!("service-{}", i),
+        };
+        event_bus.publish_to_session("session-1", event.clone());
+        published.push(json(&event));
+    }
+    // Events for sessions without a stream and global events are forwarded too
+    event_bus.publish_to_session("unknown-session", session_created("unknown-session"));
+    published.push(json(&session_created("unknown-session")));
+    event_bus.publish_global(session_created("session-2"));
+    published.push(json(&session_created("session-2")));
+
+    assert_eq!(wait_for_events(&first, published.len()).await, published);
+    assert_eq!(wait_for_events(&second, published.len()).await, published);
+    // Browser streams still get their events
+    assert_eq!(json(&subscriber.recv().await.unwrap().event), published[0]);
+}
+
+#[tokio::test]
+async fn test_slow_sink_does_not_stall_publishers() {
+    let stuck = Arc::new(StuckSink::default());
+    let memory = Arc::new(MemorySink::default());
+    let event_bus = EventBus::new()
+        .with_sink(stuck.clone(), 4)
+        .with_sink(memory.clone(), 100);
+    event_bus.create_session_stream("session-1".to_string());
+
+    tokio::time::timeout(Duration::from_secs(1), async {
+        for i in 0..50 {
+            event_bus.publish_to_session("session-1", session_created(&i.to_string()));
+        }
+    })
+    .await
+    .expect("publishing blocked on a stuck sink");
+
+    // The stuck sink's queue overflowed, the other sink got everything
+    assert!(
+        event_bus.dropped_events() >= 45,
+        "{}",
+        event_bus.dropped_events()
+    );
+    assert_eq!(wait_for_events(&memory, 50).await.len(), 50);
+    stuck.release.notify_waiters();
+}
+
+#[test]
+fn test_nats_subjects() {
+    let prefix = "session-manager.events";
+
+    assert_eq!(
+        event_subject(prefix, &session_created("abc")),
+        "session-manager.events.abc.session_created"
+    );
+    assert_eq!(
+        event_subject(
+            prefix,
+            &SessionEvent::SessionReady {
+                session_id: "abc".to_string(),
+                all_participants_joined: true,
+            }
+        ),
+        "session-manager.events.abc.session_ready"
+    );
+}
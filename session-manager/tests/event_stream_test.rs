@@ -0,0 +1,62 @@
+use futures_util::StreamExt;
+use reqwest::Client;
+use serde_json::json;
+use std::time::Duration;
+use tokio_tungstenite::{
+    connect_async,
+    tungstenite::{client::IntoClientRequest, Message},
+};
+
+mod common;
+
+const ADMIN_TOKEN: &str = "ws-admin";
+
+#[tokio::test]
+async fn test_websocket_receives_session_created_frame() {
+    common::wait_for_livekit().await;
+    let mut config = common::test_config(8767);
+    config.auth.admin_token = Some(ADMIN_TOKEN.to_string());
+    let (base_url, server_handle) = common::start_server(config).await;
+
+    // Subscribe before the session exists so the creation event is observed
+    let mut request = "ws://127.0.0.1:8767/ws".into_client_request().unwrap();
+    request.headers_mut().insert(
+        "Authorization",
+        format!("Bearer {}", ADMIN_TOKEN).parse().unwrap(),
+    );
+    let (mut socket, _) = connect_async(request)
+        .await
+        .expect("WebSocket connection failed");
+
+    let client = Client::new();
+    let session = common::create_session(
+        &client,
+        &base_url,
+        json!({
+            "user_identity": "ws-test-user",
+            "required_services": []
+        }),
+    )
+    .await;
+    let session_id = session["session_id"].as_str().expect("session_id");
+
+    let frame = tokio::time::timeout(Duration::from_secs(10), async {
+        while let Some(message) = socket.next().await {
+            if let Message::Text(text) = message.expect("WebSocket error") {
+                let event: serde_json::Value =
+                    serde_json::from_str(&text).expect("Frame should be JSON");
+                if event["type"] == "SessionCreated" {
+                    return event;
+                }
+            }
+        }
+        panic!("WebSocket closed before session_created frame");
+    })
+    .await
+    .expect("Timed out waiting for session_created frame");
+
+    assert_eq!(frame["session_id"], session_id);
+    assert_eq!(frame["room_name"], session["room_name"]);
+
+    server_handle.abort();
+}
@@ -0,0 +1,121 @@
+use session_manager::{
+    domain::{
+        IdentityScheme, LifecycleObserver, MicroserviceInfo, NotifyRetryPolicy,
+        ParticipantTimeouts, Session, SessionObserver, SessionStatus,
+    },
+    events::{EventBus, EventReceiver},
+    services::{MicroserviceRegistry, SessionService, SessionServiceImpl},
+    storage::{memory::MemoryStorage, SessionStorage},
+};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+mod common;
+
+const SERVICES: [&str; 2] = ["asr-service", "tts-service"];
+
+/// An observed session still waiting for its services, with its event stream open
+async fn seed_observed_session(storage: &MemoryStorage, event_bus: &EventBus) -> EventReceiver {
+    let mut session = Session::new(
+        "session-ack".to_string(),
+        "room-ack".to_string(),
+        HashMap::new(),
+    );
+    for service_id in SERVICES {
+        session.add_microservice(MicroserviceInfo::new(
+            service_id.to_string(),
+            format!("http://{}.local", service_id),
+            HashMap::new(),
+        ));
+    }
+    session.update_status(SessionStatus::WaitingForServices);
+    storage.save_session(&session).await.unwrap();
+    event_bus.create_session_stream(session.id.clone())
+}
+
+fn session_service(storage: Arc<MemoryStorage>, event_bus: EventBus) -> SessionServiceImpl {
+    let config = common::test_config(0);
+    SessionServiceImpl::new(
+        storage,
+        Arc::new(MicroserviceRegistry::new()),
+        config.livekit.clone(),
+        config.livekit.server_url.clone(),
+        event_bus,
+        SessionServiceImpl::build_http_client().unwrap(),
+        NotifyRetryPolicy::from(&config.microservices),
+    )
+}
+
+fn published(events: &mut EventReceiver) -> Vec<&'static str> {
+    std::iter::from_fn(|| events.try_recv().ok())
+        .map(|event| event.event.event_name())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_explicit_ack_proves_join_when_room_event_is_missed() {
+    let storage = Arc::new(MemoryStorage::new());
+    let event_bus = EventBus::new();
+    let mut events = seed_observed_session(&storage, &event_bus).await;
+    let service = session_service(storage, event_bus.clone());
+
+    // The observer never sees asr-service connect; its ready call is the only proof
+    service
+        .mark_service_ready("session-ack", "asr-service")
+        .await
+        .unwrap();
+    assert_eq!(published(&mut events), ["microservice_joined"]);
+
+    let mut observer = LifecycleObserver::new(
+        "session-ack".to_string(),
+        HashSet::from(SERVICES.map(str::to_string)),
+        Arc::new(event_bus),
+        IdentityScheme::default(),
+        ParticipantTimeouts::default(),
+        50,
+    );
+    assert!(observer
+        .on_participant_joined("tts-service", "")
+        .is_continue());
+    assert_eq!(
+        published(&mut events),
+        ["microservice_joined", "session_ready"]
+    );
+}
+
+#[tokio::test]
+async fn test_room_event_and_ack_are_announced_once() {
+    let storage = Arc::new(MemoryStorage::new());
+    let event_bus = EventBus::new();
+    let mut events = seed_observed_session(&storage, &event_bus).await;
+    let service = session_service(storage, event_bus.clone());
+    let mut observer = LifecycleObserver::new(
+        "session-ack".to_string(),
+        HashSet::from(SERVICES.map(str::to_string)),
+        Arc::new(event_bus),
+        IdentityScheme::default(),
+        ParticipantTimeouts::default(),
+        50,
+    );
+
+    for service_id in SERVICES {
+        assert!(observer.on_participant_joined(service_id, "").is_continue());
+    }
+    assert_eq!(
+        published(&mut events),
+        [
+            "microservice_joined",
+            "microservice_joined",
+            "session_ready"
+        ]
+    );
+
+    // Late acknowledgements only update the stored session
+    for service_id in SERVICES {
+        service
+            .mark_service_ready("session-ack", service_id)
+            .await
+            .unwrap();
+    }
+    assert_eq!(published(&mut events), ["session_status_changed"]);
+}
@@ -0,0 +1,108 @@
+use async_trait::async_trait;
+use microservice_sdk::{
+    JoinRoomRequest, LeaveReason, LeaveRoomRequest, MicroserviceConfig, MicroserviceHandler,
+    MicroserviceRunner, Result as SdkResult,
+};
+use reqwest::{Client, StatusCode};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::sleep;
+
+mod common;
+
+const ADMIN_TOKEN: &str = "leave-admin";
+
+/// Joins without connecting and records every leave request it gets
+#[derive(Default)]
+struct RecordingService {
+    leaves: Mutex<Vec<LeaveRoomRequest>>,
+}
+
+#[async_trait]
+impl MicroserviceHandler for RecordingService {
+    async fn handle_join_room(&self, _request: JoinRoomRequest) -> SdkResult<()> {
+        Ok(())
+    }
+
+    async fn handle_leave_room(&self, request: LeaveRoomRequest) -> SdkResult<()> {
+        self.leaves.lock().unwrap().push(request);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_admin_termination_reason_reaches_service() {
+    let mut config = common::test_config(8794);
+    config.livekit.server_url = common::spawn_mock_livekit().await;
+    config.auth.admin_token = Some(ADMIN_TOKEN.to_string());
+    let (base_url, server_handle) = common::start_server(config).await;
+
+    let service = Arc::new(RecordingService::default());
+    let runner = MicroserviceRunner::new(
+        MicroserviceConfig::new(
+            base_url.clone(),
+            "leave-service".to_string(),
+            "http://127.0.0.1:8795".to_string(),
+        ),
+        service.clone(),
+    )
+    .expect("runner");
+    let runner_handle = tokio::spawn(async move { runner.start().await });
+
+    // The runner registers before it starts serving
+    let client = Client::new();
+    for _ in 0..50 {
+        if client
+            .get("http://127.0.0.1:8795/health")
+            .send()
+            .await
+            .is_ok()
+        {
+            break;
+        }
+        sleep(Duration::from_millis(100)).await;
+    }
+
+    let created = common::create_session(
+        &client,
+        &base_url,
+        json!({
+            "user_identity": "leave-user",
+            "required_services": ["leave-service"],
+            "observe": false
+        }),
+    )
+    .await;
+    let session_id = created["session_id"].as_str().unwrap().to_string();
+
+    let response = client
+        .post(format!("{}/api/v1/admin/terminate-all", base_url))
+        .bearer_auth(ADMIN_TOKEN)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let leaves = service.leaves.lock().unwrap();
+    assert_eq!(leaves.len(), 1);
+    assert_eq!(leaves[0].session_id, session_id);
+    assert_eq!(leaves[0].service_identity, "leave-service");
+    assert_eq!(leaves[0].reason, LeaveReason::AdminTerminate);
+    drop(leaves);
+
+    runner_handle.abort();
+    server_handle.abort();
+}
+
+#[test]
+fn test_unknown_leave_reason_is_tolerated() {
+    let request: LeaveRoomRequest = serde_json::from_value(json!({
+        "room_name": "room-1",
+        "session_id": "session-1",
+        "service_identity": "asr",
+        "reason": "Migrated"
+    }))
+    .unwrap();
+    assert_eq!(request.reason, LeaveReason::Unknown);
+}
@@ -0,0 +1,41 @@
+use std::net::SocketAddr;
+use std::time::Duration;
+use tokio::io::AsyncReadExt;
+use tokio::net::{TcpListener, TcpStream};
+
+/// Accept one connection and close it from the server side, leaving the port in TIME_WAIT
+async fn serve_one_connection(listener: &TcpListener) {
+    let addr = listener.local_addr().unwrap();
+    let mut client = TcpStream::connect(addr).await.unwrap();
+    let (accepted, _) = listener.accept().await.unwrap();
+    drop(accepted);
+
+    // Wait for the close to reach the client before it closes its end
+    let mut buf = [0u8; 1];
+    let read = tokio::time::timeout(Duration::from_secs(2), client.read(&mut buf)).await;
+    assert!(matches!(read, Ok(Ok(0))));
+}
+
+#[tokio::test]
+async fn test_server_listener_rebinds_immediately() {
+    let listener =
+        session_manager::server::bind_listener(SocketAddr::from(([127, 0, 0, 1], 0)), 16).unwrap();
+    let addr = listener.local_addr().unwrap();
+    serve_one_connection(&listener).await;
+    drop(listener);
+
+    let rebound = session_manager::server::bind_listener(addr, 16).expect("port rebinds");
+    assert_eq!(rebound.local_addr().unwrap(), addr);
+}
+
+#[tokio::test]
+async fn test_runner_listener_rebinds_immediately() {
+    let listener =
+        microservice_sdk::client::bind_listener(SocketAddr::from(([127, 0, 0, 1], 0)), 16).unwrap();
+    let addr = listener.local_addr().unwrap();
+    serve_one_connection(&listener).await;
+    drop(listener);
+
+    let rebound = microservice_sdk::client::bind_listener(addr, 16).expect("port rebinds");
+    assert_eq!(rebound.local_addr().unwrap(), addr);
+}
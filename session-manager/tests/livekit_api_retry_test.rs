@@ -0,0 +1,116 @@
+use axum::{http::StatusCode, Router};
+use livekit_api::services::{ServiceError, TwirpError, TwirpErrorCode};
+use session_manager::{config::LiveKitConfig, domain::Session, utils::errors::SessionManagerError};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+use tokio::net::TcpListener;
+
+mod common;
+
+/// Mock LiveKit RoomService; `respond` receives the 0-based call index
+async fn spawn_mock_livekit<F, Fut>(respond: F) -> (LiveKitConfig, Arc<AtomicUsize>)
+where
+    F: Fn(usize) -> Fut + Clone + Send + Sync + 'static,
+    Fut: std::future::Future<Output = (StatusCode, Vec<u8>)> + Send,
+{
+    let calls = Arc::new(AtomicUsize::new(0));
+    let calls_clone = calls.clone();
+    // The twirp client percent-encodes the method path, so answer every request
+    let app = Router::new().fallback(move || {
+        let respond = respond.clone();
+        let call = calls_clone.fetch_add(1, Ordering::SeqCst);
+        async move { respond(call).await }
+    });
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+
+    let mut config = common::test_config(0).livekit;
+    config.server_url = format!("ws://{}", addr);
+    config.api_timeout_ms = 200;
+    config.api_max_attempts = 2;
+    (config, calls)
+}
+
+fn twirp_error(code: &str) -> (StatusCode, Vec<u8>) {
+    let body = serde_json::json!({ "code": code, "msg": "mock" });
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        serde_json::to_vec(&body).unwrap(),
+    )
+}
+
+fn error_code(error: SessionManagerError) -> String {
+    match error {
+        SessionManagerError::LiveKit(ServiceError::Twirp(TwirpError::Twirp(code))) => code.code,
+        other => panic!("expected LiveKit twirp error, got {:?}", other),
+    }
+}
+
+fn session() -> Session {
+    Session::new(
+        "session-api".to_string(),
+        "room-api".to_string(),
+        HashMap::new(),
+    )
+}
+
+#[tokio::test]
+async fn test_slow_livekit_times_out_after_bounded_retries() {
+    let (config, calls) = spawn_mock_livekit(|_| async {
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        (StatusCode::OK, Vec::new())
+    })
+    .await;
+
+    let started = Instant::now();
+    let result = session().create_livekit_room(&config).await;
+
+    assert_eq!(
+        error_code(result.unwrap_err()),
+        TwirpErrorCode::DEADLINE_EXCEEDED
+    );
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    assert!(started.elapsed() < Duration::from_secs(2));
+}
+
+#[tokio::test]
+async fn test_transient_livekit_error_is_retried() {
+    let (config, calls) = spawn_mock_livekit(|call| async move {
+        if call == 0 {
+            twirp_error(TwirpErrorCode::UNAVAILABLE)
+        } else {
+            // An empty protobuf body decodes to a default Room
+            (StatusCode::OK, Vec::new())
+        }
+    })
+    .await;
+
+    assert!(session()
+        .create_livekit_room(&config)
+        .await
+        .expect("room created"));
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_permanent_livekit_error_is_not_retried() {
+    let (config, calls) =
+        spawn_mock_livekit(|_| async { twirp_error(TwirpErrorCode::PERMISSION_DENIED) }).await;
+
+    let result = session().create_livekit_room(&config).await;
+    assert_eq!(
+        error_code(result.unwrap_err()),
+        TwirpErrorCode::PERMISSION_DENIED
+    );
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
@@ -1,4 +1,5 @@
 use livekit::prelude::*;
+use microservice_sdk::{protocol::Request, Envelope};
 use reqwest::Client;
 use serde_json::json;
 use session_manager::{config::AppConfig, server::Server};
@@ -17,7 +18,7 @@ const LIVEKIT_API_SECRET: &str = "devkey_secret_that_is_at_least_32_characters_l
 async fn test_session_creation_with_microservice_integration() {
     // Initialize detailed logging with Vector support
     use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
-    
+
     let env_filter = tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| {
         "session_manager=debug,microservice_sdk=debug,livekit=info,livekit_api=info,tower_http=info".into()
     });
@@ -29,19 +30,25 @@ async fn test_session_creation_with_microservice_integration() {
         .with_level(true);
 
     // Check if vector logging is enabled
-    let vector_enabled = std::env::var("VECTOR_LOG_ENABLED").unwrap_or_else(|_| "true".to_string()) == "true";
-    
+    let vector_enabled =
+        std::env::var("VECTOR_LOG_ENABLED").unwrap_or_else(|_| "true".to_string()) == "true";
+
     if vector_enabled {
-        let vector_endpoint = std::env::var("VECTOR_LOG_ENDPOINT").unwrap_or_else(|_| "localhost:9000".to_string());
-        let vector_layer = tracing_vector::VectorLayer::new("session-manager-integration-test", &vector_endpoint);
-        
+        let vector_endpoint =
+            std::env::var("VECTOR_LOG_ENDPOINT").unwrap_or_else(|_| "localhost:9000".to_string());
+        let vector_layer =
+            tracing_vector::VectorLayer::new("session-manager-integration-test", &vector_endpoint);
+
         tracing_subscriber::registry()
             .with(env_filter)
             .with(fmt_layer)
             .with(vector_layer)
             .init();
-            
-        tracing::info!("Vector logging initialized for integration test to: {}", vector_endpoint);
+
+        tracing::info!(
+            "Vector logging initialized for integration test to: {}",
+            vector_endpoint
+        );
     } else {
         tracing_subscriber::registry()
             .with(env_filter)
@@ -270,8 +277,11 @@ async fn test_client_with_microservice_communication(
                     // Store the received message
                     received_messages_clone.lock().await.push(message.clone());
 
-                    // Check if it's a pong response
-                    if message.to_lowercase().contains("pong") {
+                    // Check if it's the response to our ping
+                    if matches!(
+                        Envelope::decode(&payload),
+                        Ok(Envelope::Response(response)) if response.id == "ping-1"
+                    ) {
                         received_pong_clone.store(true, std::sync::atomic::Ordering::Relaxed);
                         tracing::info!("✓ Received pong response from microservice!");
                     }
@@ -320,12 +330,8 @@ async fn test_client_with_microservice_communication(
         tracing::info!("📡 Testing ping-pong communication with microservice...");
 
         // Send ping message
-        let ping_data = DataPacket {
-            payload: "ping".to_string().into_bytes(),
-            topic: Some("test-ping".to_string()),
-            reliable: true,
-            destination_identities: vec![],
-        };
+        let ping_data =
+            Envelope::from(Request::new("ping-1", "ping", json!(null))).to_packet(true, &[])?;
 
         tracing::info!("Sending ping message to microservice...");
         room.local_participant().publish_data(ping_data).await?;
@@ -354,7 +360,7 @@ async fn test_client_with_microservice_communication(
                     service_messages
                 );
 
-                if service_messages.iter().any(|msg| msg.contains("ping")) {
+                if service_messages.iter().any(|msg| msg.contains("\"ping\"")) {
                     tracing::info!("✓ Microservice confirmed receiving ping message");
                 } else {
                     tracing::warn!("⚠ Microservice did not receive ping message");
@@ -434,24 +440,50 @@ fn create_test_config() -> AppConfig {
             host: "127.0.0.1".to_string(),
             port: 8765,
             workers: Some(1),
+            backlog: 1024,
+            max_sessions: 0,
         },
         livekit: session_manager::config::LiveKitConfig {
             server_url: LIVEKIT_URL.to_string(),
             api_key: LIVEKIT_API_KEY.to_string(),
             api_secret: LIVEKIT_API_SECRET.to_string(),
+            api_timeout_ms: 10_000,
+            api_max_attempts: 3,
+            identity: Default::default(),
+            room_name: Default::default(),
+            room_metadata_keys: Vec::new(),
+            participant_timeouts: Default::default(),
+            max_observer_connections: 256,
+            max_participants: 50,
+            verify_service_identities: true,
+            hidden_observer: true,
+            room_stats_interval_secs: 30,
+            token_audit: Default::default(),
+            token_provider: Default::default(),
         },
         microservices: session_manager::config::MicroserviceConfig {
             registration_timeout: 30,
             join_timeout: 60,
+            notify_max_attempts: 3,
+            notify_retry_base_delay_ms: 500,
         },
         logging: session_manager::config::LoggingConfig {
             level: "debug".to_string(),
             format: "json".to_string(),
         },
         vector_log: session_manager::config::VectorLogConfig {
-            enabled: std::env::var("VECTOR_LOG_ENABLED").unwrap_or_else(|_| "true".to_string()) == "true",
-            endpoint: std::env::var("VECTOR_LOG_ENDPOINT").unwrap_or_else(|_| "localhost:9000".to_string()),
+            enabled: std::env::var("VECTOR_LOG_ENABLED").unwrap_or_else(|_| "true".to_string())
+                == "true",
+            endpoint: std::env::var("VECTOR_LOG_ENDPOINT")
+                .unwrap_or_else(|_| "localhost:9000".to_string()),
             source_name: "session-manager-livekit-test".to_string(),
         },
+        rate_limit: Default::default(),
+        auth: Default::default(),
+        sweeper: Default::default(),
+        streams: Default::default(),
+        metadata_limits: Default::default(),
+        discovery: Default::default(),
+        nats: Default::default(),
     }
 }
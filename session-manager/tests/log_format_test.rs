@@ -0,0 +1,74 @@
+use session_manager::utils::logging::{fmt_layer, LogFormat};
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use tracing_subscriber::{layer::SubscriberExt, Registry};
+
+/// Collects everything the layer writes
+#[derive(Clone, Default)]
+struct Captured(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Captured {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn log_with(format: LogFormat) -> String {
+    let captured = Captured::default();
+    let writer = captured.clone();
+    let subscriber = Registry::default().with(fmt_layer(format, move || writer.clone()));
+
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::info!(session_id = "s1", "session created");
+    });
+
+    let output = captured.0.lock().unwrap().clone();
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn test_json_format_emits_one_object_per_line() {
+    let output = log_with(LogFormat::Json);
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines.len(), 1);
+
+    let entry: serde_json::Value = serde_json::from_str(lines[0]).expect("JSON log line");
+    assert_eq!(entry["level"], "INFO");
+    assert_eq!(entry["fields"]["message"], "session created");
+    assert_eq!(entry["fields"]["session_id"], "s1");
+    assert!(entry["target"]
+        .as_str()
+        .unwrap()
+        .contains("log_format_test"));
+}
+
+#[test]
+fn test_text_formats_differ_in_shape() {
+    let full = log_with(LogFormat::Full);
+    assert_eq!(full.lines().count(), 1);
+    assert!(full.contains("session created"));
+    assert!(serde_json::from_str::<serde_json::Value>(&full).is_err());
+
+    let compact = log_with(LogFormat::Compact);
+    assert_eq!(compact.lines().count(), 1);
+    assert!(compact.contains("session created"));
+
+    // Pretty puts the message, fields and source location on separate lines
+    let pretty = log_with(LogFormat::Pretty);
+    assert!(pretty.lines().count() > 1);
+    assert!(pretty.contains("session created"));
+}
+
+#[test]
+fn test_format_names_parse() {
+    assert_eq!("json".parse::<LogFormat>().unwrap(), LogFormat::Json);
+    assert_eq!("Pretty".parse::<LogFormat>().unwrap(), LogFormat::Pretty);
+    assert_eq!("compact".parse::<LogFormat>().unwrap(), LogFormat::Compact);
+    assert_eq!("full".parse::<LogFormat>().unwrap(), LogFormat::Full);
+    assert!("xml".parse::<LogFormat>().is_err());
+}
@@ -0,0 +1,71 @@
+use reqwest::{Client, StatusCode};
+use serde_json::json;
+use session_manager::utils::logging::LogLevelHandle;
+use tracing::Level;
+use tracing_subscriber::layer::SubscriberExt;
+
+mod common;
+
+const ADMIN_TOKEN: &str = "log-secret";
+
+#[tokio::test]
+async fn test_set_log_level_via_admin_endpoint() {
+    // The reload layer only takes effect once installed in a subscriber
+    let (filter, log_level) = LogLevelHandle::new("session_manager=info").unwrap();
+    tracing::subscriber::set_global_default(tracing_subscriber::registry().with(filter))
+        .expect("global subscriber");
+
+    let mut config = common::test_config(8778);
+    config.livekit.server_url = "ws://127.0.0.1:1".to_string();
+    config.auth.admin_token = Some(ADMIN_TOKEN.to_string());
+    let base_url = format!("http://{}:{}", config.server.host, config.server.port);
+    let server = session_manager::Server::with_log_level(config, Some(log_level.clone()))
+        .await
+        .expect("Failed to create server");
+    let server_handle = tokio::spawn(async move {
+        server.run().await.expect("Server failed to run");
+    });
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+
+    let client = Client::new();
+    let url = format!("{}/api/v1/admin/log-level", base_url);
+
+    assert!(!tracing::enabled!(target: "session_manager", Level::DEBUG));
+
+    // Requires the admin token
+    let response = client
+        .post(&url)
+        .json(&json!({ "directive": "debug" }))
+        .send()
+        .await
+        .expect("Request failed");
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // Invalid directives are rejected and leave the filter untouched
+    let response = client
+        .post(&url)
+        .bearer_auth(ADMIN_TOKEN)
+        .json(&json!({ "directive": "session_manager=loud" }))
+        .send()
+        .await
+        .expect("Request failed");
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(log_level.current(), "session_manager=info");
+
+    let response = client
+        .post(&url)
+        .bearer_auth(ADMIN_TOKEN)
+        .json(&json!({ "directive": "debug" }))
+        .send()
+        .await
+        .expect("Request failed");
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["previous"], "session_manager=info");
+    assert_eq!(body["current"], "debug");
+
+    assert!(tracing::enabled!(target: "session_manager", Level::DEBUG));
+    assert!(!tracing::enabled!(target: "session_manager", Level::TRACE));
+
+    server_handle.abort();
+}
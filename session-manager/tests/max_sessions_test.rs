@@ -0,0 +1,59 @@
+use reqwest::{Client, StatusCode};
+use serde_json::{json, Value};
+
+mod common;
+
+#[tokio::test]
+async fn test_creates_beyond_max_sessions_are_rejected_until_one_ends() {
+    let mut config = common::test_config(8804);
+    config.livekit.server_url = common::spawn_mock_livekit().await;
+    config.server.max_sessions = 2;
+    let (base_url, server_handle) = common::start_server(config).await;
+    let client = Client::new();
+    let create = |user: &str| {
+        client
+            .post(format!("{}/api/v1/create-session", base_url))
+            .json(&json!({ "user_identity": user, "required_services": [] }))
+            .send()
+    };
+
+    let first = common::create_session(
+        &client,
+        &base_url,
+        json!({ "user_identity": "capacity-1", "required_services": [] }),
+    )
+    .await;
+    common::create_session(
+        &client,
+        &base_url,
+        json!({ "user_identity": "capacity-2", "required_services": [] }),
+    )
+    .await;
+
+    let response = create("capacity-3").await.unwrap();
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["error"], "AtCapacity");
+    assert!(
+        body["message"].as_str().unwrap().contains("limit of 2"),
+        "{}",
+        body
+    );
+
+    // A terminated session no longer counts
+    let response = client
+        .delete(format!(
+            "{}/api/v1/sessions/{}",
+            base_url,
+            first["session_id"].as_str().unwrap()
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert!(response.status().is_success(), "{}", response.status());
+
+    let response = create("capacity-3").await.unwrap();
+    assert!(response.status().is_success(), "{}", response.status());
+
+    server_handle.abort();
+}
@@ -0,0 +1,92 @@
+use reqwest::{Client, StatusCode};
+use serde_json::{json, Value};
+use session_manager::{
+    config::MetadataLimitConfig, utils::validation::validate_metadata, SessionManagerError,
+};
+use std::collections::HashMap;
+
+mod common;
+
+fn limits(max_keys: usize, max_bytes: usize) -> MetadataLimitConfig {
+    MetadataLimitConfig {
+        max_keys,
+        max_bytes,
+    }
+}
+
+/// Metadata with one key whose value makes the serialized JSON exactly `size` bytes
+fn metadata_of_size(size: usize) -> HashMap<String, String> {
+    // `{"a":""}` is 8 bytes
+    HashMap::from([("a".to_string(), "x".repeat(size - 8))])
+}
+
+fn metadata_with_keys(count: usize) -> HashMap<String, String> {
+    (0..count)
+        .map(|i| (format!("key-{}", i), "v".to_string()))
+        .collect()
+}
+
+#[test]
+fn test_key_count_boundary() {
+    let limits = limits(3, 1024);
+
+    assert!(validate_metadata("metadata", &metadata_with_keys(3), &limits).is_ok());
+    let error = validate_metadata("metadata", &metadata_with_keys(4), &limits).unwrap_err();
+    assert!(matches!(error, SessionManagerError::InvalidRequest(_)));
+    assert!(error.to_string().contains("at most 3 keys"), "{}", error);
+}
+
+#[test]
+fn test_serialized_size_boundary() {
+    let limits = limits(64, 100);
+
+    assert!(validate_metadata("metadata", &metadata_of_size(100), &limits).is_ok());
+    let error = validate_metadata("metadata", &metadata_of_size(101), &limits).unwrap_err();
+    assert!(matches!(error, SessionManagerError::InvalidRequest(_)));
+    assert!(error.to_string().contains("at most 100 bytes"), "{}", error);
+
+    assert!(validate_metadata("metadata", &HashMap::new(), &limits).is_ok());
+}
+
+#[tokio::test]
+async fn test_oversized_metadata_is_rejected_on_create_and_register() {
+    let mut config = common::test_config(8799);
+    config.metadata_limits = limits(4, 256);
+    let (base_url, server_handle) = common::start_server(config).await;
+    let client = Client::new();
+
+    let response = client
+        .post(format!("{}/api/v1/create-session", base_url))
+        .json(&json!({
+            "user_identity": "metadata-user",
+            "metadata": metadata_of_size(257)
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["error"], "InvalidRequest");
+
+    let register = |metadata: HashMap<String, String>| {
+        client
+            .post(format!("{}/api/v1/microservices/register", base_url))
+            .json(&json!({
+                "service_id": "metadata-service",
+                "endpoint": "http://127.0.0.1:9",
+                "metadata": metadata
+            }))
+            .send()
+    };
+
+    let response = register(metadata_with_keys(5)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let response = register(metadata_of_size(257)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // At the limit the registration goes through
+    let response = register(metadata_with_keys(4)).await.unwrap();
+    assert!(response.status().is_success(), "{}", response.status());
+
+    server_handle.abort();
+}
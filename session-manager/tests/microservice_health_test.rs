@@ -0,0 +1,123 @@
+use axum::{http::StatusCode, routing::get, Router};
+use reqwest::Client;
+use serde_json::{json, Value};
+use session_manager::{
+    domain::MicroserviceInfo,
+    services::{MicroserviceRegistry, ServiceHealthStatus},
+};
+use std::collections::HashMap;
+use std::time::Duration;
+
+mod common;
+
+/// Microservice stand-in whose `/health` answers with `status` after `delay`
+async fn spawn_service(status: StatusCode, delay: Duration) -> String {
+    let app = Router::new().route(
+        "/health",
+        get(move || async move {
+            tokio::time::sleep(delay).await;
+            status
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    format!("http://{}", addr)
+}
+
+/// An endpoint nothing listens on
+async fn dead_endpoint() -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    drop(listener);
+    format!("http://{}", addr)
+}
+
+async fn register(registry: &MicroserviceRegistry, service_id: &str, endpoint: String) {
+    registry
+        .register_service(MicroserviceInfo::new(
+            service_id.to_string(),
+            endpoint,
+            HashMap::new(),
+        ))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_health_summary_classifies_every_service() {
+    let registry = MicroserviceRegistry::new();
+    register(
+        &registry,
+        "asr",
+        spawn_service(StatusCode::OK, Duration::ZERO).await,
+    )
+    .await;
+    register(
+        &registry,
+        "tts",
+        spawn_service(StatusCode::SERVICE_UNAVAILABLE, Duration::ZERO).await,
+    )
+    .await;
+    register(&registry, "llm", dead_endpoint().await).await;
+    register(
+        &registry,
+        "vad",
+        spawn_service(StatusCode::OK, Duration::from_secs(5)).await,
+    )
+    .await;
+
+    let health = registry.check_health(Duration::from_millis(300), 2).await;
+
+    assert_eq!(health.len(), 4);
+    assert_eq!(health["asr"].status, ServiceHealthStatus::Healthy);
+    assert!(health["asr"].message.is_none());
+    assert_eq!(health["tts"].status, ServiceHealthStatus::Unhealthy);
+    assert!(health["tts"].message.as_deref().unwrap().contains("503"));
+    assert_eq!(health["llm"].status, ServiceHealthStatus::Unreachable);
+    // A hanging service is given up on after the timeout
+    assert_eq!(health["vad"].status, ServiceHealthStatus::Unreachable);
+    assert!(health["vad"].latency_ms >= 300);
+    assert!(health["vad"].latency_ms < 5000);
+}
+
+#[tokio::test]
+async fn test_health_endpoint_reports_registered_services() {
+    let config = common::test_config(8802);
+    let (base_url, server_handle) = common::start_server(config).await;
+    let client = Client::new();
+
+    for (service_id, endpoint) in [
+        (
+            "healthy-service",
+            spawn_service(StatusCode::OK, Duration::ZERO).await,
+        ),
+        ("dead-service", dead_endpoint().await),
+    ] {
+        let response = client
+            .post(format!("{}/api/v1/microservices/register", base_url))
+            .json(&json!({ "service_id": service_id, "endpoint": endpoint }))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success(), "{}", response.status());
+    }
+
+    let summary: Value = client
+        .get(format!("{}/api/v1/microservices/health", base_url))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+
+    assert_eq!(summary["services"]["healthy-service"]["status"], "healthy");
+    assert!(summary["services"]["healthy-service"]["latency_ms"].is_u64());
+    assert_eq!(summary["services"]["dead-service"]["status"], "unreachable");
+    assert!(summary["services"]["dead-service"]["message"].is_string());
+
+    server_handle.abort();
+}
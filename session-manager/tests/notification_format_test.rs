@@ -0,0 +1,301 @@
+use async_trait::async_trait;
+use axum::{
+    body::Bytes,
+    http::{header, HeaderMap},
+    routing::post,
+    Router,
+};
+use microservice_sdk::{
+    Capabilities, JoinRoomRequest, JoinRoomResponse, LeaveReason, LeaveRoomRequest,
+    MicroserviceConfig, MicroserviceHandler, MicroserviceRunner, PayloadFormat,
+    Result as SdkResult, SessionManagerClient, MSGPACK_CONTENT_TYPE,
+};
+use reqwest::Client;
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::{net::TcpListener, time::sleep};
+
+mod common;
+
+/// Records the join requests it gets and reports a fixed capability
+#[derive(Default)]
+struct RecordingService {
+    joins: Mutex<Vec<JoinRoomRequest>>,
+}
+
+#[async_trait]
+impl MicroserviceHandler for RecordingService {
+    async fn handle_join_room(&self, request: JoinRoomRequest) -> SdkResult<()> {
+        self.joins.lock().unwrap().push(request);
+        Ok(())
+    }
+
+    async fn handle_leave_room(&self, _request: LeaveRoomRequest) -> SdkResult<()> {
+        Ok(())
+    }
+
+    async fn capabilities(&self, _request: &JoinRoomRequest) -> Capabilities {
+        Capabilities::from([("codec".to_string(), json!("opus"))])
+    }
+}
+
+fn join_request() -> JoinRoomRequest {
+    JoinRoomRequest {
+        room_name: "room-1".to_string(),
+        session_id: "session-1".to_string(),
+        service_identity: "format-service".to_string(),
+        access_token: "token".to_string(),
+        livekit_url: "ws://localhost:7880".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_runner_handles_msgpack_join_request() {
+    let (base_url, server_handle) = common::start_server(common::test_config(8806)).await;
+    let service = Arc::new(RecordingService::default());
+    let runner = MicroserviceRunner::new(
+        MicroserviceConfig::new(
+            base_url,
+            "format-service".to_string(),
+            "http://127.0.0.1:8807".to_string(),
+        ),
+        service.clone(),
+    )
+    .expect("runner");
+    let runner_handle = tokio::spawn(async move { runner.start().await });
+
+    // The runner registers before it starts serving
+    let client = Client::new();
+    for _ in 0..50 {
+        if client
+            .get("http://127.0.0.1:8807/health")
+            .send()
+            .await
+            .is_ok()
+        {
+            break;
+        }
+        sleep(Duration::from_millis(100)).await;
+    }
+
+    let response = client
+        .post("http://127.0.0.1:8807/join-room")
+        .header(header::CONTENT_TYPE, MSGPACK_CONTENT_TYPE)
+        .body(PayloadFormat::Msgpack.encode(&join_request()).unwrap())
+        .send()
+        .await
+        .unwrap();
+    assert!(response.status().is_success(), "{}", response.status());
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        MSGPACK_CONTENT_TYPE
+    );
+    let body: JoinRoomResponse = PayloadFormat::Msgpack
+        .decode(&response.bytes().await.unwrap())
+        .unwrap();
+    assert!(body.success);
+    assert_eq!(body.session_id, "session-1");
+    assert_eq!(body.service_id, "format-service");
+    assert_eq!(body.capabilities["codec"], "opus");
+
+    // The handler got the request exactly as sent
+    let joins = service.joins.lock().unwrap().clone();
+    assert_eq!(joins.len(), 1);
+    assert_eq!(
+        serde_json::to_value(&joins[0]).unwrap(),
+        serde_json::to_value(join_request()).unwrap()
+    );
+
+    // Bodies without a known content type are still read as JSON
+    let response = client
+        .post("http://127.0.0.1:8807/join-room")
+        .body(serde_json::to_vec(&join_request()).unwrap())
+        .send()
+        .await
+        .unwrap();
+    assert!(response.status().is_success(), "{}", response.status());
+    let body: JoinRoomResponse = response.json().await.unwrap();
+    assert_eq!(body.capabilities["codec"], "opus");
+    assert_eq!(service.joins.lock().unwrap().len(), 2);
+
+    // A body that is not in the announced format is rejected
+    let response = client
+        .post("http://127.0.0.1:8807/join-room")
+        .header(header::CONTENT_TYPE, MSGPACK_CONTENT_TYPE)
+        .body(serde_json::to_vec(&join_request()).unwrap())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+
+    runner_handle.abort();
+    server_handle.abort();
+}
+
+/// Content type and decoded body of each notification a service received
+#[derive(Default)]
+struct Received {
+    joins: Mutex<Vec<(String, JoinRoomRequest)>>,
+    leaves: Mutex<Vec<(String, LeaveRoomRequest)>>,
+}
+
+fn content_type(headers: &HeaderMap) -> String {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// A service that only speaks msgpack and answers joins with a capability
+async fn spawn_msgpack_service(received: Arc<Received>) -> String {
+    let (joins, leaves) = (received.clone(), received);
+    let app = Router::new()
+        .route(
+            "/join-room",
+            post(move |headers: HeaderMap, body: Bytes| async move {
+                let request: JoinRoomRequest = PayloadFormat::Msgpack.decode(&body).unwrap();
+                let response = JoinRoomResponse {
+                    success: true,
+                    message: "joined".to_string(),
+                    session_id: request.session_id.clone(),
+                    service_id: request.service_identity.clone(),
+                    capabilities: Capabilities::from([("model".to_string(), json!("v2"))]),
+                };
+                joins
+                    .joins
+                    .lock()
+                    .unwrap()
+                    .push((content_type(&headers), request));
+                (
+                    [(header::CONTENT_TYPE, MSGPACK_CONTENT_TYPE)],
+                    PayloadFormat::Msgpack.encode(&response).unwrap(),
+                )
+            }),
+        )
+        .route(
+            "/leave-room",
+            post(move |headers: HeaderMap, body: Bytes| async move {
+                let request: LeaveRoomRequest = PayloadFormat::Msgpack.decode(&body).unwrap();
+                leaves
+                    .leaves
+                    .lock()
+                    .unwrap()
+                    .push((content_type(&headers), request));
+            }),
+        );
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    format!("http://{}", addr)
+}
+
+#[tokio::test]
+async fn test_session_manager_sends_msgpack_to_services_that_ask_for_it() {
+    let mut config = common::test_config(8808);
+    config.livekit.server_url = common::spawn_mock_livekit().await;
+    let (base_url, server_handle) = common::start_server(config).await;
+    let received = Arc::new(Received::default());
+    let endpoint = spawn_msgpack_service(received.clone()).await;
+
+    SessionManagerClient::new(
+        MicroserviceConfig::new(base_url.clone(), "msgpack-service".to_string(), endpoint)
+            .with_notification_format(PayloadFormat::Msgpack),
+    )
+    .unwrap()
+    .register()
+    .await
+    .unwrap();
+
+    let client = Client::new();
+    let created = common::create_session(
+        &client,
+        &base_url,
+        json!({
+            "user_identity": "msgpack-user",
+            "required_services": ["msgpack-service"],
+            "observe": false
+        }),
+    )
+    .await;
+    let session_id = created["session_id"].as_str().unwrap().to_string();
+
+    // The msgpack response's capabilities make it into the session status
+    let status_url = format!("{}/api/v1/sessions/{}", base_url, session_id);
+    let mut status = serde_json::Value::Null;
+    for _ in 0..50 {
+        status = client
+            .get(&status_url)
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        if status["status"] == "Ready" {
+            break;
+        }
+        sleep(Duration::from_millis(100)).await;
+    }
+    assert_eq!(status["status"], "Ready", "{}", status);
+    assert_eq!(
+        status["service_capabilities"]["msgpack-service"]["model"],
+        "v2"
+    );
+
+    {
+        let joins = received.joins.lock().unwrap();
+        assert_eq!(joins.len(), 1);
+        assert_eq!(joins[0].0, MSGPACK_CONTENT_TYPE);
+        assert_eq!(joins[0].1.session_id, session_id);
+        assert_eq!(joins[0].1.service_identity, "msgpack-service");
+    }
+
+    let response = client.delete(&status_url).send().await.unwrap();
+    assert!(response.status().is_success(), "{}", response.status());
+    let leaves = received.leaves.lock().unwrap();
+    assert_eq!(leaves.len(), 1);
+    assert_eq!(leaves[0].0, MSGPACK_CONTENT_TYPE);
+    assert_eq!(leaves[0].1.session_id, session_id);
+    assert_eq!(leaves[0].1.reason, LeaveReason::SessionDeleted);
+    drop(leaves);
+
+    server_handle.abort();
+}
+
+#[test]
+fn test_payload_formats() {
+    assert_eq!(
+        PayloadFormat::from_content_type("application/msgpack"),
+        Some(PayloadFormat::Msgpack)
+    );
+    assert_eq!(
+        PayloadFormat::from_content_type("Application/JSON; charset=utf-8"),
+        Some(PayloadFormat::Json)
+    );
+    assert_eq!(PayloadFormat::from_content_type("text/plain"), None);
+    assert_eq!(
+        PayloadFormat::from_metadata(Some("msgpack")),
+        PayloadFormat::Msgpack
+    );
+    assert_eq!(
+        PayloadFormat::from_metadata(Some("cbor")),
+        PayloadFormat::Json
+    );
+    assert_eq!(PayloadFormat::from_metadata(None), PayloadFormat::Json);
+
+    // Unknown leave reasons are tolerated in msgpack just like in JSON
+    let body = PayloadFormat::Msgpack
+        .encode(&json!({
+            "room_name": "room-1",
+            "session_id": "session-1",
+            "service_identity": "asr",
+            "reason": "Migrated"
+        }))
+        .unwrap();
+    let request: LeaveRoomRequest = PayloadFormat::Msgpack.decode(&body).unwrap();
+    assert_eq!(request.reason, LeaveReason::Unknown);
+}
@@ -0,0 +1,141 @@
+use axum::{extract::ConnectInfo, http::StatusCode, routing::post, Json, Router};
+use session_manager::{
+    domain::{JoinRoomRequest, NotifyRetryPolicy, Session},
+    services::SessionServiceImpl,
+};
+use std::{
+    collections::HashSet,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+use tokio::net::TcpListener;
+
+fn join_request(session_id: &str) -> JoinRoomRequest {
+    JoinRoomRequest {
+        room_name: format!("room-{}", session_id),
+        session_id: session_id.to_string(),
+        service_identity: "keepalive-service".to_string(),
+        access_token: "token".to_string(),
+        livekit_url: "ws://localhost:7880".to_string(),
+    }
+}
+
+fn retry_policy() -> NotifyRetryPolicy {
+    NotifyRetryPolicy {
+        max_attempts: 3,
+        base_delay: Duration::from_millis(10),
+    }
+}
+
+/// Serve `/join-room` with the given status for the first `failures` requests, 200 afterwards
+async fn spawn_flaky_service(
+    failure_status: StatusCode,
+    failures: usize,
+) -> (String, Arc<AtomicUsize>) {
+    let attempts = Arc::new(AtomicUsize::new(0));
+    let attempts_clone = attempts.clone();
+    let app = Router::new().route(
+        "/join-room",
+        post(move || {
+            let attempts = attempts_clone.clone();
+            async move {
+                if attempts.fetch_add(1, Ordering::SeqCst) < failures {
+                    (
+                        failure_status,
+                        Json(serde_json::json!({ "success": false })),
+                    )
+                } else {
+                    (StatusCode::OK, Json(serde_json::json!({ "success": true })))
+                }
+            }
+        }),
+    );
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let endpoint = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+
+    (endpoint, attempts)
+}
+
+#[tokio::test]
+async fn test_repeated_notifications_reuse_connection() {
+    // Record the peer address of every request; one address means one TCP connection
+    let peers = Arc::new(Mutex::new(HashSet::new()));
+    let peers_clone = peers.clone();
+    let app = Router::new().route(
+        "/join-room",
+        post(move |ConnectInfo(peer): ConnectInfo<SocketAddr>| {
+            let peers = peers_clone.clone();
+            async move {
+                peers.lock().unwrap().insert(peer);
+                Json(serde_json::json!({ "success": true, "message": "ok" }))
+            }
+        }),
+    );
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let endpoint = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        axum::serve(
+            listener,
+            app.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .await
+        .unwrap();
+    });
+
+    let client = SessionServiceImpl::build_http_client().expect("client");
+    for i in 0..5 {
+        Session::notify_service_join(
+            &client,
+            endpoint.clone(),
+            join_request(&format!("session-{}", i)),
+            &retry_policy(),
+        )
+        .await
+        .expect("notification should succeed");
+    }
+
+    assert_eq!(peers.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_notification_retries_server_errors() {
+    let (endpoint, attempts) = spawn_flaky_service(StatusCode::SERVICE_UNAVAILABLE, 2).await;
+
+    let client = SessionServiceImpl::build_http_client().expect("client");
+    Session::notify_service_join(
+        &client,
+        endpoint,
+        join_request("session-retry"),
+        &retry_policy(),
+    )
+    .await
+    .expect("notification should succeed after retries");
+
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_notification_does_not_retry_client_errors() {
+    let (endpoint, attempts) = spawn_flaky_service(StatusCode::BAD_REQUEST, 1).await;
+
+    let client = SessionServiceImpl::build_http_client().expect("client");
+    let result = Session::notify_service_join(
+        &client,
+        endpoint,
+        join_request("session-rejected"),
+        &retry_policy(),
+    )
+    .await;
+
+    assert!(result.is_err());
+    assert_eq!(attempts.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,108 @@
+use session_manager::services::ObserverPool;
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+#[tokio::test]
+async fn test_observer_connections_wait_for_a_free_slot() {
+    let pool = ObserverPool::new(1);
+    let first = pool.try_acquire().expect("slot available");
+    assert!(pool.try_acquire().is_none());
+    assert_eq!(pool.in_use(), 1);
+
+    let waiter = tokio::spawn({
+        let pool = pool.clone();
+        async move { pool.acquire().await }
+    });
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(!waiter.is_finished(), "second connection must queue");
+
+    drop(first);
+    let second = tokio::time::timeout(Duration::from_secs(1), waiter)
+        .await
+        .expect("queued connection proceeds once the slot frees")
+        .unwrap();
+    assert_eq!(pool.in_use(), 1);
+
+    drop(second);
+    assert_eq!(pool.in_use(), 0);
+}
+
+#[tokio::test]
+async fn test_observer_connections_never_exceed_limit() {
+    let pool = ObserverPool::new(2);
+    let active = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+
+    let tasks: Vec<_> = (0..8)
+        .map(|_| {
+            let pool = pool.clone();
+            let active = active.clone();
+            let peak = peak.clone();
+            tokio::spawn(async move {
+                let _permit = pool.acquire().await;
+                let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                active.fetch_sub(1, Ordering::SeqCst);
+            })
+        })
+        .collect();
+
+    for task in tasks {
+        task.await.unwrap();
+    }
+    assert_eq!(peak.load(Ordering::SeqCst), 2);
+    assert_eq!(pool.in_use(), 0);
+}
+
+#[test]
+fn test_zero_limit_still_allows_one_observer() {
+    let pool = ObserverPool::new(0);
+    assert_eq!(pool.limit(), 1);
+    assert!(pool.try_acquire().is_some());
+}
+
+#[tokio::test]
+async fn test_concurrent_connects_open_one_observer_per_session() {
+    let pool = ObserverPool::new(4);
+    let connections = Arc::new(AtomicUsize::new(0));
+    let barrier = Arc::new(tokio::sync::Barrier::new(2));
+
+    // Two code paths racing to observe the same session
+    let attempts: Vec<_> = (0..2)
+        .map(|_| {
+            let pool = pool.clone();
+            let connections = connections.clone();
+            let barrier = barrier.clone();
+            tokio::spawn(async move {
+                barrier.wait().await;
+                let claim = pool.claim("session-race")?;
+                let permit = pool.acquire().await.with_claim(claim);
+                connections.fetch_add(1, Ordering::SeqCst);
+                Some(permit)
+            })
+        })
+        .collect();
+
+    let mut permits = Vec::new();
+    for attempt in attempts {
+        permits.extend(attempt.await.unwrap());
+    }
+    assert_eq!(connections.load(Ordering::SeqCst), 1);
+    assert_eq!(permits.len(), 1);
+    assert_eq!(pool.in_use(), 1);
+    assert!(pool.is_observed("session-race"));
+
+    // Other sessions are not blocked
+    assert!(pool.claim("session-other").is_some());
+
+    // Once the connection closes the session can be observed again
+    drop(permits);
+    assert!(!pool.is_observed("session-race"));
+    assert!(pool.claim("session-race").is_some());
+}
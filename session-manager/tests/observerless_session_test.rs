@@ -0,0 +1,95 @@
+use axum::{routing::post, Json, Router};
+use microservice_sdk::{
+    CreateSessionRequest, MicroserviceConfig, SessionClient, SessionManagerClient, SessionStatus,
+};
+use serde_json::json;
+use std::time::Duration;
+use tokio::net::TcpListener;
+
+mod common;
+
+/// Register a stand-in microservice that acknowledges join requests after `ack_delay`
+async fn register_service(
+    base_url: &str,
+    service_id: &str,
+    ack_delay: Duration,
+) -> SessionManagerClient {
+    let app = Router::new().route(
+        "/join-room",
+        post(move || async move {
+            tokio::time::sleep(ack_delay).await;
+            Json(json!({ "success": true, "message": "joined" }))
+        }),
+    );
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let service_endpoint = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+
+    let client = SessionManagerClient::new(MicroserviceConfig::new(
+        base_url.to_string(),
+        service_id.to_string(),
+        service_endpoint,
+    ))
+    .expect("SDK client");
+    client.register().await.expect("registration");
+    client
+}
+
+#[tokio::test]
+async fn test_observerless_session_becomes_ready_without_room_connection() {
+    // The mock only answers room API calls; connecting an observer to it would fail
+    let mut config = common::test_config(8786);
+    config.livekit.server_url = common::spawn_mock_livekit().await;
+    let (base_url, server_handle) = common::start_server(config).await;
+
+    register_service(&base_url, "ack-service", Duration::ZERO).await;
+    let loading_service =
+        register_service(&base_url, "loading-service", Duration::from_secs(30)).await;
+
+    let session_client = SessionClient::new(base_url, 10).expect("session client");
+    let mut request = CreateSessionRequest::new("observerless-user");
+    request.required_services = Some(vec![
+        "ack-service".to_string(),
+        "loading-service".to_string(),
+    ]);
+    request.observe = Some(false);
+    let created = session_client
+        .create_session(&request)
+        .await
+        .expect("create session");
+    assert_eq!(created.status, SessionStatus::WaitingForServices);
+
+    // The acknowledged join notification marks the first service ready
+    let mut status = None;
+    for _ in 0..50 {
+        let current = session_client
+            .get_session(&created.session_id)
+            .await
+            .expect("session status");
+        if current.ready_services == vec!["ack-service"] {
+            status = Some(current);
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    let status = status.expect("ack-service becomes ready");
+    assert_eq!(status.status, SessionStatus::WaitingForServices);
+    assert_eq!(status.pending_services, vec!["loading-service"]);
+
+    // The other service is still answering the join request and reports ready explicitly
+    loading_service
+        .notify_ready("loading-service", &created.session_id)
+        .await
+        .expect("notify_ready");
+
+    let status = session_client
+        .get_session(&created.session_id)
+        .await
+        .expect("session status");
+    assert_eq!(status.status, SessionStatus::Ready);
+    assert!(status.pending_services.is_empty());
+
+    server_handle.abort();
+}
@@ -0,0 +1,117 @@
+use session_manager::{
+    domain::{IdentityScheme, ParticipantRole},
+    events::{EventBus, SessionEvent},
+};
+use std::collections::HashSet;
+
+fn services(ids: &[&str]) -> HashSet<String> {
+    ids.iter().map(|id| id.to_string()).collect()
+}
+
+fn classify(identity: &str, session_id: &str, service_ids: &HashSet<String>) -> ParticipantRole {
+    ParticipantRole::classify(
+        identity,
+        session_id,
+        service_ids,
+        &IdentityScheme::default(),
+    )
+}
+
+fn eu_identities() -> IdentityScheme {
+    IdentityScheme {
+        manager_prefix: "sm-eu-".to_string(),
+        client_prefix: "user-eu-".to_string(),
+    }
+}
+
+#[test]
+fn test_identities_use_configured_prefixes() {
+    let identities = eu_identities();
+    assert_eq!(identities.manager("s1"), "sm-eu-s1");
+    assert_eq!(identities.client("s1"), "user-eu-s1");
+
+    let defaults = IdentityScheme::default();
+    assert_eq!(defaults.manager("s1"), "session-manager-s1");
+    assert_eq!(defaults.client("s1"), "client-s1");
+}
+
+#[test]
+fn test_classifier_uses_configured_manager_prefix() {
+    let identities = eu_identities();
+    let no_services = services(&[]);
+
+    assert_eq!(
+        ParticipantRole::classify("sm-eu-s1", "s1", &no_services, &identities),
+        ParticipantRole::Manager
+    );
+    // Another deployment's observer in a shared room is not our manager
+    assert_eq!(
+        ParticipantRole::classify("session-manager-s1", "s1", &no_services, &identities),
+        ParticipantRole::Client
+    );
+}
+
+#[test]
+fn test_service_without_service_in_its_id_is_a_service() {
+    let service_ids = services(&["gpu-worker-7"]);
+    assert_eq!(
+        classify("gpu-worker-7", "s1", &service_ids),
+        ParticipantRole::Service
+    );
+}
+
+#[test]
+fn test_user_with_service_in_its_id_is_a_client() {
+    let service_ids = services(&["gpu-worker-7"]);
+    assert_eq!(
+        classify("customer-service-rep", "s1", &service_ids),
+        ParticipantRole::Client
+    );
+    assert_eq!(
+        classify(&IdentityScheme::default().client("s1"), "s1", &service_ids),
+        ParticipantRole::Client
+    );
+}
+
+#[test]
+fn test_only_this_sessions_manager_is_the_manager() {
+    let service_ids = services(&[]);
+    assert_eq!(
+        classify(&IdentityScheme::default().manager("s1"), "s1", &service_ids),
+        ParticipantRole::Manager
+    );
+    assert_eq!(
+        classify(&IdentityScheme::default().manager("s2"), "s1", &service_ids),
+        ParticipantRole::Client
+    );
+}
+
+#[tokio::test]
+async fn test_participant_joined_events_follow_role() {
+    let event_bus = EventBus::new();
+    let mut receiver = event_bus.create_session_stream("s1".to_string());
+    let service_ids = services(&["gpu-worker-7"]);
+
+    for identity in [
+        "gpu-worker-7",
+        "customer-service-rep",
+        &IdentityScheme::default().manager("s1"),
+    ] {
+        let role = classify(identity, "s1", &service_ids);
+        event_bus
+            .publish_participant_joined("s1", identity, role)
+            .await
+            .unwrap();
+    }
+
+    assert!(matches!(
+        receiver.recv().await.unwrap().event,
+        SessionEvent::MicroserviceJoined { service_id, .. } if service_id == "gpu-worker-7"
+    ));
+    assert!(matches!(
+        receiver.recv().await.unwrap().event,
+        SessionEvent::ClientJoined { user_identity, .. } if user_identity == "customer-service-rep"
+    ));
+    // The manager's own join is not published
+    assert!(receiver.try_recv().is_err());
+}
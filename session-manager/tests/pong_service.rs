@@ -8,8 +8,8 @@
 use async_trait::async_trait;
 use livekit::prelude::*;
 use microservice_sdk::{
-    JoinRoomRequest, MicroserviceConfig, MicroserviceHandler, MicroserviceRunner,
-    Result as SdkResult,
+    protocol::Response, Envelope, JoinRoomRequest, MicroserviceConfig, MicroserviceHandler,
+    MicroserviceRunner, Result as SdkResult, RoomSession,
 };
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -42,14 +42,9 @@ impl MicroserviceHandler for PongService {
             self.service_name, request.room_name, request.session_id
         );
 
-        // Use the LiveKit URL provided in the request
-        let livekit_url = &request.livekit_url;
-
         // Connect to LiveKit room
-        let room_options = RoomOptions::default();
-
-        match Room::connect(livekit_url, &request.access_token, room_options).await {
-            Ok((room, mut event_rx)) => {
+        match RoomSession::connect_with_events(&request).await {
+            Ok((room_session, mut event_rx)) => {
                 info!(
                     "PongService {} successfully connected to room {}",
                     self.service_name, request.room_name
@@ -88,31 +83,24 @@ impl MicroserviceHandler for PongService {
                                 // Store the received message for testing verification
                                 received_messages.lock().await.push(message.to_string());
 
-                                // Check if message contains "ping"
-                                if message.to_lowercase().contains("ping") {
-                                    info!(
-                                        "PongService {} detected ping, sending pong!",
-                                        service_name
-                                    );
-
-                                    // Send pong response
-                                    let pong_data = DataPacket {
-                                        payload: "pong".to_string().into_bytes(),
-                                        topic: Some("test-response".to_string()),
-                                        reliable: true,
-                                        destination_identities: vec![], // Send to all participants
-                                    };
-
-                                    if let Err(e) =
-                                        room.local_participant().publish_data(pong_data).await
-                                    {
-                                        error!(
-                                            "PongService {} failed to send pong: {}",
-                                            service_name, e
-                                        );
-                                    } else {
-                                        info!("PongService {} sent pong response!", service_name);
+                                // Answer ping requests
+                                let ping = match Envelope::decode(&payload) {
+                                    Ok(Envelope::Request(request)) if request.method == "ping" => {
+                                        request
                                     }
+                                    _ => continue,
+                                };
+                                info!("PongService {} detected ping, sending pong!", service_name);
+
+                                // Send pong response to all participants
+                                let pong = Response::ok(ping.id, serde_json::json!("pong"));
+                                if let Err(e) = room_session.publish_envelope(&pong.into()).await {
+                                    error!(
+                                        "PongService {} failed to send pong: {}",
+                                        service_name, e
+                                    );
+                                } else {
+                                    info!("PongService {} sent pong response!", service_name);
                                 }
                             }
                             RoomEvent::ParticipantConnected(participant) => {
@@ -171,9 +159,7 @@ impl MicroserviceHandler for PongService {
                     "PongService {} failed to connect to room {}: {}",
                     self.service_name, request.room_name, e
                 );
-                Err(microservice_sdk::MicroserviceError::JoinRoomFailed(
-                    format!("LiveKit connection failed: {}", e),
-                ))
+                Err(e)
             }
         }
     }
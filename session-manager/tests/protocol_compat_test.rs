@@ -0,0 +1,107 @@
+use serde_json::json;
+use std::collections::HashMap;
+
+#[test]
+fn test_server_events_deserialize_in_sdk() {
+    let events = vec![
+        session_manager::events::SessionEvent::SessionStatusChanged {
+            session_id: "s1".to_string(),
+            status: session_manager::domain::SessionStatus::WaitingForServices,
+            reason: None,
+        },
+        session_manager::events::SessionEvent::MetadataChanged {
+            session_id: "s1".to_string(),
+            metadata: HashMap::from([("topic".to_string(), "demo".to_string())]),
+        },
+        session_manager::events::SessionEvent::ServiceTimedOut {
+            session_id: "s1".to_string(),
+            service_id: "asr".to_string(),
+        },
+    ];
+
+    for event in events {
+        let json = serde_json::to_string(&event).unwrap();
+        let received: microservice_sdk::SessionEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(received.event_name(), event.event_name());
+        assert_eq!(serde_json::to_string(&received).unwrap(), json);
+    }
+}
+
+#[test]
+fn test_sdk_status_matches_server_wire_names() {
+    let json = serde_json::to_string(&session_manager::domain::SessionStatus::Ready).unwrap();
+    assert_eq!(json, "\"Ready\"");
+    assert_eq!(
+        serde_json::from_str::<microservice_sdk::SessionStatus>(&json).unwrap(),
+        microservice_sdk::SessionStatus::Ready
+    );
+}
+
+#[test]
+fn test_join_room_round_trip() {
+    let request = session_manager::domain::JoinRoomRequest {
+        room_name: "room-1".to_string(),
+        session_id: "s1".to_string(),
+        service_identity: "asr".to_string(),
+        access_token: "token".to_string(),
+        livekit_url: "ws://localhost:7880".to_string(),
+    };
+    let received: microservice_sdk::JoinRoomRequest =
+        serde_json::from_value(serde_json::to_value(&request).unwrap()).unwrap();
+    assert_eq!(received.service_identity, "asr");
+    assert_eq!(received.livekit_url, "ws://localhost:7880");
+
+    let response = microservice_sdk::JoinRoomResponse {
+        success: true,
+        message: "Successfully joined room".to_string(),
+        session_id: "s1".to_string(),
+        service_id: "asr".to_string(),
+        capabilities: HashMap::from([("topics".to_string(), json!(["transcript"]))]),
+    };
+    let received: session_manager::domain::JoinRoomResponse =
+        serde_json::from_value(serde_json::to_value(&response).unwrap()).unwrap();
+    assert!(received.success);
+    assert_eq!(received.capabilities["topics"], json!(["transcript"]));
+
+    // Services outside the SDK may only acknowledge the request
+    let bare: session_manager::domain::JoinRoomResponse =
+        serde_json::from_value(json!({ "success": true })).unwrap();
+    assert!(bare.capabilities.is_empty());
+}
+
+#[test]
+fn test_join_room_request_wire_format() {
+    let request = session_manager::domain::JoinRoomRequest {
+        room_name: "room-1".to_string(),
+        session_id: "s1".to_string(),
+        service_identity: "asr".to_string(),
+        access_token: "token".to_string(),
+        livekit_url: "ws://localhost:7880".to_string(),
+    };
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        json!({
+            "room_name": "room-1",
+            "session_id": "s1",
+            "service_identity": "asr",
+            "access_token": "token",
+            "livekit_url": "ws://localhost:7880"
+        })
+    );
+
+    // A request without the LiveKit URL cannot be joined and is rejected by the SDK
+    let stale = json!({
+        "room_name": "room-1",
+        "session_id": "s1",
+        "service_identity": "asr",
+        "access_token": "token"
+    });
+    assert!(serde_json::from_value::<microservice_sdk::JoinRoomRequest>(stale).is_err());
+}
+
+#[test]
+fn test_sdk_tolerates_events_from_newer_server() {
+    let event: microservice_sdk::SessionEvent =
+        serde_json::from_value(json!({ "type": "SessionMigrated", "session_id": "s1" })).unwrap();
+    assert!(matches!(event, microservice_sdk::SessionEvent::Unknown));
+}
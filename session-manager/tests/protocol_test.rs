@@ -0,0 +1,77 @@
+use microservice_sdk::{
+    protocol::{topics, Event, Request, Response},
+    Envelope,
+};
+use serde_json::json;
+
+fn round_trip(envelope: Envelope) -> Envelope {
+    let payload = envelope.encode().expect("encodes");
+    Envelope::decode(&payload).expect("decodes")
+}
+
+#[test]
+fn test_request_round_trips_through_bytes() {
+    let request: Envelope = Request::new("req-1", "transcribe", json!({ "lang": "zh-CN" })).into();
+
+    assert_eq!(round_trip(request.clone()), request);
+    assert_eq!(request.topic(), topics::REQUEST);
+}
+
+#[test]
+fn test_response_round_trips_through_bytes() {
+    let ok: Envelope = Response::ok("req-1", json!({ "text": "你好" })).into();
+    let failed: Envelope = Response::err("req-2", "model not loaded").into();
+
+    assert_eq!(round_trip(ok.clone()), ok);
+    assert_eq!(round_trip(failed.clone()), failed);
+    assert_eq!(ok.topic(), topics::RESPONSE);
+
+    let Envelope::Response(failed) = failed else {
+        unreachable!()
+    };
+    assert_eq!(failed.into_result(), Err("model not loaded".to_string()));
+}
+
+#[test]
+fn test_event_round_trips_through_bytes() {
+    let event: Envelope = Event::new("speech_started", json!({ "at_ms": 1200 })).into();
+
+    assert_eq!(round_trip(event.clone()), event);
+    assert_eq!(event.topic(), topics::EVENT);
+}
+
+#[test]
+fn test_envelope_is_tagged_with_kind() {
+    let payload = Envelope::from(Request::new("req-1", "ping", json!(null)))
+        .encode()
+        .unwrap();
+    let value: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+
+    assert_eq!(
+        value,
+        json!({ "kind": "request", "id": "req-1", "method": "ping", "params": null })
+    );
+
+    // Optional fields may be left out by other implementations
+    let decoded = Envelope::decode(br#"{"kind":"event","name":"ready"}"#).unwrap();
+    assert_eq!(decoded, Event::new("ready", json!(null)).into());
+}
+
+#[test]
+fn test_invalid_payloads_are_rejected() {
+    assert!(Envelope::decode(b"ping").is_err());
+    assert!(Envelope::decode(br#"{"kind":"unknown","id":"1"}"#).is_err());
+    assert!(Envelope::decode(br#"{"id":"1","method":"ping"}"#).is_err());
+}
+
+#[test]
+fn test_packet_uses_kind_topic() {
+    let response: Envelope = Response::ok("req-1", json!("pong")).into();
+    let packet = response
+        .to_packet(true, &["client-abc".to_string()])
+        .unwrap();
+
+    assert_eq!(packet.topic.as_deref(), Some(topics::RESPONSE));
+    assert_eq!(Envelope::decode(&packet.payload).unwrap(), response);
+    assert_eq!(packet.destination_identities.len(), 1);
+}
@@ -0,0 +1,54 @@
+use microservice_sdk::{ExcessPolicy, PublishLimit, PublishRateLimiter};
+use std::time::{Duration, Instant};
+
+#[tokio::test]
+async fn test_delay_caps_send_rate() {
+    let limiter = PublishRateLimiter::new(Some(PublishLimit::new(20, ExcessPolicy::Delay)));
+
+    let started = Instant::now();
+    for _ in 0..40 {
+        assert!(limiter.acquire(Some("pong")).await);
+    }
+    let elapsed = started.elapsed();
+
+    // A one-second burst, then 20 more at 20 per second
+    assert!(elapsed >= Duration::from_millis(900), "{:?}", elapsed);
+    assert!(elapsed < Duration::from_millis(1500), "{:?}", elapsed);
+    assert_eq!(limiter.dropped(), 0);
+}
+
+#[tokio::test]
+async fn test_drop_discards_excess_per_topic() {
+    let limiter = PublishRateLimiter::new(Some(PublishLimit::new(10, ExcessPolicy::Drop)));
+
+    let mut sent = 0;
+    for _ in 0..50 {
+        if limiter.acquire(Some("pong")).await {
+            sent += 1;
+        }
+    }
+    assert_eq!(sent, 10);
+    assert_eq!(limiter.dropped(), 40);
+
+    // Other topics have their own budget
+    assert!(limiter.acquire(Some("status")).await);
+    assert!(limiter.acquire(None).await);
+
+    tokio::time::sleep(Duration::from_millis(250)).await;
+    assert!(limiter.acquire(Some("pong")).await);
+}
+
+#[tokio::test]
+async fn test_unlimited_until_a_limit_is_set() {
+    let limiter = PublishRateLimiter::default();
+    for _ in 0..1000 {
+        assert!(limiter.acquire(Some("pong")).await);
+    }
+
+    limiter.set_limit(Some(PublishLimit::new(1, ExcessPolicy::Drop)));
+    assert!(limiter.acquire(Some("pong")).await);
+    assert!(!limiter.acquire(Some("pong")).await);
+
+    limiter.set_limit(None);
+    assert!(limiter.acquire(Some("pong")).await);
+}
@@ -0,0 +1,48 @@
+use reqwest::{header::RETRY_AFTER, Client, StatusCode};
+use serde_json::json;
+
+mod common;
+
+#[tokio::test]
+async fn test_create_session_is_rate_limited_per_identity() {
+    let mut config = common::test_config(8768);
+    // Room creation is allowed to fail; only the limiter decision matters here
+    config.livekit.server_url = "ws://127.0.0.1:1".to_string();
+    config.rate_limit.requests_per_second = 0.1;
+    config.rate_limit.burst = 2;
+    let (base_url, server_handle) = common::start_server(config).await;
+
+    let client = Client::new();
+    let create = |identity: &'static str| {
+        client
+            .post(format!("{}/api/v1/create-session", base_url))
+            .json(&json!({ "user_identity": identity }))
+            .send()
+    };
+
+    for _ in 0..2 {
+        let response = create("rate-limited-user").await.expect("request failed");
+        assert_ne!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    let response = create("rate-limited-user").await.expect("request failed");
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    let retry_after: u64 = response
+        .headers()
+        .get(RETRY_AFTER)
+        .expect("Retry-After header")
+        .to_str()
+        .unwrap()
+        .parse()
+        .expect("Retry-After should be whole seconds");
+    assert!(retry_after >= 1);
+
+    let body: serde_json::Value = response.json().await.expect("ErrorResponse body");
+    assert_eq!(body["error"], "RateLimited");
+
+    // Other identities have their own bucket
+    let response = create("another-user").await.expect("request failed");
+    assert_ne!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    server_handle.abort();
+}
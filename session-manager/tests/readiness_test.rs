@@ -0,0 +1,52 @@
+use reqwest::Client;
+
+mod common;
+
+#[tokio::test]
+async fn test_ready_reports_unavailable_livekit_while_health_stays_ok() {
+    // Point LiveKit at an address nothing is listening on
+    let mut config = common::test_config(8766);
+    config.livekit.server_url = "ws://127.0.0.1:1".to_string();
+    let (base_url, server_handle) = common::start_server(config).await;
+
+    let client = Client::new();
+
+    // Liveness is independent of dependencies
+    let health_response = client
+        .get(format!("{}/health", base_url))
+        .send()
+        .await
+        .expect("Health check request failed");
+    assert_eq!(health_response.status(), reqwest::StatusCode::OK);
+
+    // Readiness reflects the unreachable LiveKit backend
+    let ready_response = client
+        .get(format!("{}/ready", base_url))
+        .send()
+        .await
+        .expect("Readiness request failed");
+    assert_eq!(
+        ready_response.status(),
+        reqwest::StatusCode::SERVICE_UNAVAILABLE
+    );
+
+    let body: serde_json::Value = ready_response
+        .json()
+        .await
+        .expect("Failed to parse readiness response");
+    assert_eq!(body["status"], "not_ready");
+
+    let dependencies = body["dependencies"].as_array().expect("dependencies array");
+    let livekit = dependencies
+        .iter()
+        .find(|d| d["name"] == "livekit")
+        .expect("livekit dependency reported");
+    assert_eq!(livekit["healthy"], false);
+    let storage = dependencies
+        .iter()
+        .find(|d| d["name"] == "storage")
+        .expect("storage dependency reported");
+    assert_eq!(storage["healthy"], true);
+
+    server_handle.abort();
+}
@@ -0,0 +1,129 @@
+use session_manager::{
+    domain::{
+        IdentityScheme, LifecycleObserver, MicroserviceInfo, NotifyRetryPolicy,
+        ParticipantTimeouts, Session, SessionObserver, SessionStatus,
+    },
+    events::{EventBus, EventReceiver, SessionEvent},
+    services::{MicroserviceRegistry, SessionService, SessionServiceImpl},
+    storage::{memory::MemoryStorage, SessionStorage},
+};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+mod common;
+
+const SERVICES: [&str; 3] = ["asr", "tts", "llm"];
+
+fn session(min_ready_services: Option<usize>) -> Session {
+    let mut session = Session::new(
+        "quorum-session".to_string(),
+        "quorum-room".to_string(),
+        HashMap::new(),
+    );
+    for service_id in SERVICES {
+        session.add_microservice(MicroserviceInfo::new(
+            service_id.to_string(),
+            format!("http://{}.local", service_id),
+            HashMap::new(),
+        ));
+    }
+    session.min_ready_services = min_ready_services;
+    session.update_status(SessionStatus::WaitingForServices);
+    session
+}
+
+fn ready_events(events: &mut EventReceiver) -> usize {
+    std::iter::from_fn(|| events.try_recv().ok())
+        .filter(|event| matches!(event.event, SessionEvent::SessionReady { .. }))
+        .count()
+}
+
+#[test]
+fn test_quorum_defaults_to_all_services_and_is_capped() {
+    assert_eq!(session(None).ready_quorum(), 3);
+    assert_eq!(session(Some(2)).ready_quorum(), 2);
+    assert_eq!(session(Some(10)).ready_quorum(), 3);
+
+    let mut all = session(None);
+    all.mark_service_ready("asr");
+    all.mark_service_ready("tts");
+    assert_eq!(all.status, SessionStatus::WaitingForServices);
+    all.mark_service_ready("llm");
+    assert_eq!(all.status, SessionStatus::Ready);
+
+    // Readiness recorded before the quorum was lowered still counts
+    let mut lowered = session(None);
+    lowered.mark_service_ready("asr");
+    lowered.mark_service_ready("tts");
+    lowered.min_ready_services = Some(1);
+    lowered.mark_service_ready("llm");
+    assert_eq!(lowered.status, SessionStatus::Ready);
+}
+
+#[tokio::test]
+async fn test_two_of_three_services_make_the_session_ready() {
+    let storage = Arc::new(MemoryStorage::new());
+    let session = session(Some(2));
+    storage.save_session(&session).await.unwrap();
+
+    let config = common::test_config(0);
+    let event_bus = EventBus::new();
+    let mut events = event_bus.create_session_stream(session.id.clone());
+    let service = SessionServiceImpl::new(
+        storage,
+        Arc::new(MicroserviceRegistry::new()),
+        config.livekit.clone(),
+        config.livekit.server_url.clone(),
+        event_bus,
+        SessionServiceImpl::build_http_client().unwrap(),
+        NotifyRetryPolicy::from(&config.microservices),
+    );
+
+    let first = service
+        .mark_service_ready(&session.id, "asr")
+        .await
+        .unwrap();
+    assert_eq!(first.status, SessionStatus::WaitingForServices);
+    assert_eq!(ready_events(&mut events), 0);
+
+    let second = service
+        .mark_service_ready(&session.id, "tts")
+        .await
+        .unwrap();
+    assert_eq!(second.status, SessionStatus::Ready);
+    assert_eq!(second.get_pending_services(), vec!["llm"]);
+    assert_eq!(ready_events(&mut events), 1);
+
+    // The straggler joining later does not announce readiness again
+    let third = service
+        .mark_service_ready(&session.id, "llm")
+        .await
+        .unwrap();
+    assert_eq!(third.status, SessionStatus::Ready);
+    assert_eq!(ready_events(&mut events), 0);
+}
+
+#[test]
+fn test_observer_announces_ready_at_quorum() {
+    let event_bus = Arc::new(EventBus::new());
+    let mut events = event_bus.create_session_stream("quorum-session".to_string());
+    let mut observer = LifecycleObserver::new(
+        "quorum-session".to_string(),
+        SERVICES
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<HashSet<_>>(),
+        event_bus,
+        IdentityScheme::default(),
+        ParticipantTimeouts::default(),
+        0,
+    )
+    .with_ready_quorum(2);
+
+    assert!(observer.on_participant_joined("asr", "").is_continue());
+    assert_eq!(ready_events(&mut events), 0);
+    assert!(observer.on_participant_joined("tts", "").is_continue());
+    assert_eq!(ready_events(&mut events), 1);
+    assert!(observer.on_participant_joined("llm", "").is_continue());
+    assert_eq!(ready_events(&mut events), 0);
+}
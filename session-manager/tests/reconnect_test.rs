@@ -0,0 +1,70 @@
+use microservice_sdk::{MicroserviceError, ReconnectPolicy};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{Duration, Instant};
+
+fn policy(max_attempts: u32) -> ReconnectPolicy {
+    ReconnectPolicy {
+        max_attempts,
+        base_delay: Duration::from_millis(20),
+        max_delay: Duration::from_millis(50),
+    }
+}
+
+#[test]
+fn test_backoff_doubles_up_to_max_delay() {
+    let policy = policy(10);
+
+    assert_eq!(policy.delay(1), Duration::from_millis(20));
+    assert_eq!(policy.delay(2), Duration::from_millis(40));
+    assert_eq!(policy.delay(3), Duration::from_millis(50));
+    assert_eq!(policy.delay(40), Duration::from_millis(50));
+}
+
+#[tokio::test]
+async fn test_reconnects_once_the_room_is_reachable_again() {
+    // The dropped room accepts connections again from the third attempt
+    let attempts = AtomicU32::new(0);
+    let started = Instant::now();
+
+    let connected = policy(5)
+        .retry(|attempt| {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            async move {
+                if attempt < 3 {
+                    Err(MicroserviceError::JoinRoomFailed("room unreachable".into()))
+                } else {
+                    Ok(format!("connection-{}", attempt))
+                }
+            }
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(connected, "connection-3");
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    // 20ms + 40ms + 50ms of backoff
+    assert!(started.elapsed() >= Duration::from_millis(110));
+}
+
+#[tokio::test]
+async fn test_gives_up_after_max_attempts() {
+    let attempts = AtomicU32::new(0);
+
+    let result: Result<(), _> = policy(2)
+        .retry(|attempt| {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            async move {
+                Err(MicroserviceError::JoinRoomFailed(format!(
+                    "attempt {} failed",
+                    attempt
+                )))
+            }
+        })
+        .await;
+
+    assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    assert!(matches!(
+        result,
+        Err(MicroserviceError::JoinRoomFailed(message)) if message == "attempt 2 failed"
+    ));
+}
@@ -0,0 +1,71 @@
+use livekit_api::access_token::{Claims, TokenVerifier};
+use reqwest::{Client, StatusCode};
+use serde_json::json;
+use std::time::Duration;
+
+mod common;
+
+fn claims(token: &str) -> Claims {
+    let config = common::test_config(0).livekit;
+    TokenVerifier::with_api_key(&config.api_key, &config.api_secret)
+        .verify(token)
+        .expect("token verifies")
+}
+
+async fn refresh(client: &Client, base_url: &str, session_id: &str) -> reqwest::Response {
+    client
+        .post(format!(
+            "{}/api/v1/sessions/{}/refresh-token",
+            base_url, session_id
+        ))
+        .send()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_refresh_token_extends_expiry() {
+    let mut config = common::test_config(8792);
+    config.livekit.server_url = common::spawn_mock_livekit().await;
+    let (base_url, server_handle) = common::start_server(config).await;
+
+    let client = Client::new();
+    let created = common::create_session(
+        &client,
+        &base_url,
+        json!({ "user_identity": "refresh-user", "observe": false }),
+    )
+    .await;
+    let session_id = created["session_id"].as_str().unwrap();
+    let original = claims(created["access_token"].as_str().unwrap());
+
+    // Expiry has second resolution
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+
+    let response = refresh(&client, &base_url, session_id).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["session_id"], session_id);
+    let refreshed = claims(body["access_token"].as_str().unwrap());
+
+    assert!(refreshed.exp > original.exp);
+    assert_eq!(refreshed.sub, original.sub);
+    assert_eq!(refreshed.video.room, created["room_name"].as_str().unwrap());
+    assert!(refreshed.video.room_join);
+    assert!(refreshed.video.can_publish);
+    assert!(refreshed.video.can_subscribe);
+
+    // Terminated sessions can no longer be rejoined
+    client
+        .delete(format!("{}/api/v1/sessions/{}", base_url, session_id))
+        .send()
+        .await
+        .unwrap();
+    let response = refresh(&client, &base_url, session_id).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = refresh(&client, &base_url, "missing-session").await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    server_handle.abort();
+}
@@ -0,0 +1,71 @@
+use axum::{
+    http::{StatusCode, Uri},
+    Router,
+};
+use reqwest::Client;
+use serde_json::json;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+mod common;
+
+/// LiveKit stand-in that creates the room once and reports `already_exists` afterwards
+///
+/// Only `CreateRoom` calls are counted; other room API calls get an empty room.
+async fn spawn_livekit_rejecting_duplicates() -> (String, Arc<AtomicUsize>) {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let calls_clone = calls.clone();
+    let app = Router::new().fallback(move |uri: Uri| {
+        let create = uri.path().contains("CreateRoom");
+        let call = if create {
+            calls_clone.fetch_add(1, Ordering::SeqCst)
+        } else {
+            0
+        };
+        async move {
+            if !create || call == 0 {
+                (StatusCode::OK, String::new())
+            } else {
+                (
+                    StatusCode::CONFLICT,
+                    json!({ "code": "already_exists", "msg": "room already exists" }).to_string(),
+                )
+            }
+        }
+    });
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    (format!("ws://{}", addr), calls)
+}
+
+#[tokio::test]
+async fn test_second_session_with_same_room_name_reuses_room() {
+    let (livekit_url, calls) = spawn_livekit_rejecting_duplicates().await;
+    let mut config = common::test_config(8781);
+    config.livekit.server_url = livekit_url;
+    let (base_url, server_handle) = common::start_server(config).await;
+    let client = Client::new();
+
+    let body = json!({
+        "user_identity": "retrying-user",
+        "room_name": "support-desk",
+        "required_services": []
+    });
+
+    let first = common::create_session(&client, &base_url, body.clone()).await;
+    let second = common::create_session(&client, &base_url, body).await;
+
+    assert_eq!(first["room_name"], "support-desk");
+    assert_eq!(second["room_name"], "support-desk");
+    assert_ne!(first["session_id"], second["session_id"]);
+    // The duplicate create was not retried as if it were transient
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+    server_handle.abort();
+}
@@ -0,0 +1,87 @@
+use axum::{body::Bytes, extract::Request, http::StatusCode, response::IntoResponse, Router};
+use reqwest::Client;
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use tokio::net::TcpListener;
+
+mod common;
+
+/// Protobuf `ListParticipantsResponse` with one `ParticipantInfo { identity }` per entry
+fn list_participants_response(identities: &[String]) -> Vec<u8> {
+    let mut body = Vec::new();
+    for identity in identities {
+        // ParticipantInfo.identity is field 2, ListParticipantsResponse.participants field 1
+        let mut participant = vec![0x12, identity.len() as u8];
+        participant.extend_from_slice(identity.as_bytes());
+        body.extend_from_slice(&[0x0a, participant.len() as u8]);
+        body.extend_from_slice(&participant);
+    }
+    body
+}
+
+/// Stand-in LiveKit whose rooms all hold the given participants
+async fn spawn_occupied_livekit() -> (String, Arc<Mutex<Vec<String>>>) {
+    let participants = Arc::new(Mutex::new(Vec::new()));
+    let room = participants.clone();
+    let app = Router::new().fallback(move |request: Request| {
+        let room = room.clone();
+        async move {
+            if request.uri().path().ends_with("ListParticipants") {
+                let body = list_participants_response(&room.lock().unwrap());
+                return Bytes::from(body).into_response();
+            }
+            StatusCode::OK.into_response()
+        }
+    });
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    (format!("ws://{}", addr), participants)
+}
+
+#[tokio::test]
+async fn test_full_room_rejects_new_client_tokens() {
+    let (livekit_url, participants) = spawn_occupied_livekit().await;
+    let mut config = common::test_config(8796);
+    config.livekit.server_url = livekit_url;
+    config.livekit.max_participants = 2;
+    let (base_url, server_handle) = common::start_server(config).await;
+
+    let client = Client::new();
+    let created = common::create_session(
+        &client,
+        &base_url,
+        json!({ "user_identity": "crowded-user", "observe": false }),
+    )
+    .await;
+    let session_id = created["session_id"].as_str().unwrap();
+    let refresh_url = format!("{}/api/v1/sessions/{}/refresh-token", base_url, session_id);
+
+    // The session's own client reconnecting does not count against the limit
+    *participants.lock().unwrap() = vec![format!("client-{}", session_id), "asr".to_string()];
+    let response = client.post(&refresh_url).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    *participants.lock().unwrap() = vec!["asr".to_string(), "guest".to_string()];
+    let response = client.post(&refresh_url).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["error"], "RoomFull");
+
+    // Joining a shared room that is already full fails the same way
+    let response = client
+        .post(format!("{}/api/v1/create-session", base_url))
+        .json(&json!({
+            "user_identity": "late-user",
+            "room_name": created["room_name"],
+            "observe": false
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    server_handle.abort();
+}
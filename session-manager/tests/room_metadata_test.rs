@@ -0,0 +1,132 @@
+use axum::{body::Bytes, http::StatusCode, http::Uri, Router};
+use reqwest::Client;
+use serde_json::json;
+use session_manager::domain::Session;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+use tokio::net::TcpListener;
+
+mod common;
+
+type RoomCalls = Arc<Mutex<Vec<(String, Vec<u8>)>>>;
+
+/// Stand-in LiveKit that records the method and raw body of every room API call
+async fn spawn_recording_livekit() -> (String, RoomCalls) {
+    let calls: RoomCalls = Arc::default();
+    let recorded = calls.clone();
+    let app = Router::new().fallback(move |uri: Uri, body: Bytes| {
+        let recorded = recorded.clone();
+        async move {
+            recorded
+                .lock()
+                .unwrap()
+                .push((uri.path().to_string(), body.to_vec()));
+            StatusCode::OK
+        }
+    });
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    (format!("ws://{}", addr), calls)
+}
+
+/// Body of the last recorded call to `method`
+fn last_call(calls: &RoomCalls, method: &str) -> Vec<u8> {
+    calls
+        .lock()
+        .unwrap()
+        .iter()
+        .rev()
+        .find(|(path, _)| path.ends_with(method))
+        .map(|(_, body)| body.clone())
+        .unwrap_or_else(|| panic!("no {} call recorded", method))
+}
+
+/// Room requests are protobuf, where the metadata string is embedded verbatim
+fn contains(body: &[u8], text: &str) -> bool {
+    body.windows(text.len())
+        .any(|window| window == text.as_bytes())
+}
+
+fn session_with_metadata() -> Session {
+    Session::new(
+        "session-room-metadata".to_string(),
+        "room-metadata".to_string(),
+        HashMap::from([
+            ("tier".to_string(), "gold".to_string()),
+            ("language".to_string(), "zh-CN".to_string()),
+            ("billing_id".to_string(), "secret".to_string()),
+        ]),
+    )
+}
+
+#[test]
+fn test_room_metadata_only_exposes_allowlisted_keys() {
+    let keys = vec![
+        "tier".to_string(),
+        "language".to_string(),
+        "missing".to_string(),
+    ];
+
+    assert_eq!(
+        session_with_metadata().room_metadata(&keys),
+        r#"{"language":"zh-CN","tier":"gold"}"#
+    );
+}
+
+#[test]
+fn test_room_metadata_is_empty_without_allowlisted_keys() {
+    assert_eq!(session_with_metadata().room_metadata(&[]), "");
+    assert_eq!(
+        session_with_metadata().room_metadata(&["missing".to_string()]),
+        ""
+    );
+}
+
+#[tokio::test]
+async fn test_session_metadata_is_propagated_to_room() {
+    let (livekit_url, calls) = spawn_recording_livekit().await;
+    let mut config = common::test_config(8791);
+    config.livekit.server_url = livekit_url;
+    config.livekit.room_metadata_keys = vec!["tier".to_string()];
+    let (base_url, server_handle) = common::start_server(config).await;
+
+    let client = Client::new();
+    let created = common::create_session(
+        &client,
+        &base_url,
+        json!({
+            "user_identity": "metadata-user",
+            "observe": false,
+            "metadata": { "tier": "gold", "billing_id": "secret" }
+        }),
+    )
+    .await;
+
+    let create_room = last_call(&calls, "CreateRoom");
+    assert!(contains(&create_room, r#"{"tier":"gold"}"#));
+    assert!(!contains(&create_room, "secret"));
+
+    // Changing an exposed key updates the room as well
+    let response = client
+        .patch(format!(
+            "{}/api/v1/sessions/{}/metadata",
+            base_url,
+            created["session_id"].as_str().unwrap()
+        ))
+        .json(&json!({ "tier": "platinum" }))
+        .send()
+        .await
+        .unwrap();
+    assert!(response.status().is_success());
+    assert!(contains(
+        &last_call(&calls, "UpdateRoomMetadata"),
+        r#"{"tier":"platinum"}"#
+    ));
+
+    server_handle.abort();
+}
@@ -0,0 +1,102 @@
+use reqwest::Client;
+use serde_json::json;
+use session_manager::{domain::RoomNameTemplate, server::Server};
+
+mod common;
+
+const SESSION_ID: &str = "3f2b8c1e-0000-4000-8000-000000000000";
+
+fn template(template: &str) -> RoomNameTemplate {
+    RoomNameTemplate {
+        template: template.to_string(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_default_template_matches_legacy_room_names() {
+    assert_eq!(
+        RoomNameTemplate::default().render(SESSION_ID, "alice"),
+        format!("room-{}", SESSION_ID)
+    );
+}
+
+#[test]
+fn test_template_substitutes_placeholders() {
+    let mut room_name = template("{prefix}_{user_identity}.{short_id}");
+    room_name.prefix = "eu".to_string();
+
+    assert_eq!(room_name.render(SESSION_ID, "alice"), "eu_alice.3f2b8c1e");
+    assert!(room_name.validate().is_ok());
+}
+
+#[test]
+fn test_template_sanitizes_placeholder_values() {
+    let room_name = template("{user_identity}");
+
+    assert_eq!(
+        room_name.render(SESSION_ID, "alice@example com"),
+        "alice-example-com"
+    );
+}
+
+#[test]
+fn test_template_truncates_to_max_room_name_length() {
+    let room_name = template("{user_identity}-{session_id}");
+
+    assert_eq!(room_name.render(SESSION_ID, &"a".repeat(200)).len(), 128);
+}
+
+#[test]
+fn test_template_validation_rejects_bad_templates() {
+    assert!(template("{prefix}-{tenant}").validate().is_err());
+    assert!(template("{prefix}-{session_id").validate().is_err());
+    assert!(template("room {session_id}").validate().is_err());
+    assert!(template("").validate().is_err());
+}
+
+#[tokio::test]
+async fn test_server_rejects_invalid_room_name_template() {
+    let mut config = common::test_config(0);
+    config.livekit.room_name = template("{unknown}");
+
+    assert!(Server::new(config).await.is_err());
+}
+
+#[tokio::test]
+async fn test_generated_room_names_do_not_collide() {
+    // Observer-less sessions only need the room API, which the mock answers
+    let mut config = common::test_config(8789);
+    config.livekit.server_url = common::spawn_mock_livekit().await;
+    config.livekit.room_name = template("{prefix}-{user_identity}");
+    config.livekit.room_name.prefix = "lab".to_string();
+    let (base_url, server_handle) = common::start_server(config).await;
+
+    let client = Client::new();
+    let body = json!({
+        "user_identity": "collide-user",
+        "observe": false
+    });
+    let first = common::create_session(&client, &base_url, body.clone()).await;
+    let second = common::create_session(&client, &base_url, body.clone()).await;
+    let third = common::create_session(&client, &base_url, body).await;
+
+    assert_eq!(first["room_name"], "lab-collide-user");
+    assert_eq!(second["room_name"], "lab-collide-user-2");
+    assert_eq!(third["room_name"], "lab-collide-user-3");
+
+    // Explicit room names are still shared on purpose
+    let explicit = common::create_session(
+        &client,
+        &base_url,
+        json!({
+            "user_identity": "other-user",
+            "room_name": "lab-collide-user",
+            "observe": false
+        }),
+    )
+    .await;
+    assert_eq!(explicit["room_name"], "lab-collide-user");
+
+    server_handle.abort();
+}
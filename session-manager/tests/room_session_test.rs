@@ -0,0 +1,35 @@
+use livekit::prelude::*;
+use microservice_sdk::data_packet;
+
+#[test]
+fn test_reliable_broadcast_packet() {
+    let packet = data_packet(Some("chat"), b"hello".to_vec(), true, &[]);
+
+    assert_eq!(packet.payload, b"hello");
+    assert_eq!(packet.topic.as_deref(), Some("chat"));
+    assert!(packet.reliable);
+    assert!(packet.destination_identities.is_empty());
+}
+
+#[test]
+fn test_lossy_packet_without_topic() {
+    let packet = data_packet(None, vec![1, 2, 3], false, &[]);
+
+    assert_eq!(packet.payload, vec![1, 2, 3]);
+    assert_eq!(packet.topic, None);
+    assert!(!packet.reliable);
+}
+
+#[test]
+fn test_targeted_packet_destinations() {
+    let destinations = vec!["client-abc".to_string(), "pong-service-1".to_string()];
+    let packet = data_packet(Some("control"), b"stop".to_vec(), true, &destinations);
+
+    assert_eq!(
+        packet.destination_identities,
+        vec![
+            ParticipantIdentity::from("client-abc".to_string()),
+            ParticipantIdentity::from("pong-service-1".to_string()),
+        ]
+    );
+}
@@ -0,0 +1,166 @@
+use async_trait::async_trait;
+use session_manager::{
+    api::models::SessionStatusResponse,
+    domain::{Session, SessionStatus},
+    events::{EventBus, SessionEvent},
+    services::{ConnectionQuality, ParticipantStats, RoomStats, RoomStatsPoller, RoomStatsSource},
+    storage::{memory::MemoryStorage, SessionStorage},
+    Result,
+};
+use std::collections::HashMap;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+use std::time::Duration;
+
+/// Stands in for the observer's view of the room; tests change what it reports
+#[derive(Default)]
+struct FakeRoom {
+    participants: Mutex<Vec<ParticipantStats>>,
+    polls: AtomicUsize,
+}
+
+impl FakeRoom {
+    fn set(&self, participants: Vec<ParticipantStats>) {
+        *self.participants.lock().unwrap() = participants;
+    }
+}
+
+#[async_trait]
+impl RoomStatsSource for FakeRoom {
+    async fn room_stats(&self) -> Result<RoomStats> {
+        self.polls.fetch_add(1, Ordering::SeqCst);
+        Ok(RoomStats::from_participants(
+            self.participants.lock().unwrap().clone(),
+        ))
+    }
+}
+
+fn participant(identity: &str, track_count: u32, quality: ConnectionQuality) -> ParticipantStats {
+    ParticipantStats {
+        identity: identity.to_string(),
+        track_count,
+        connection_quality: quality,
+    }
+}
+
+async fn seed_session(storage: &MemoryStorage, session_id: &str) {
+    let mut session = Session::new(
+        session_id.to_string(),
+        format!("room-{}", session_id),
+        HashMap::new(),
+    );
+    session.update_status(SessionStatus::Ready);
+    storage.save_session(&session).await.unwrap();
+}
+
+async fn status(storage: &MemoryStorage, session_id: &str) -> SessionStatusResponse {
+    storage
+        .get_session(session_id)
+        .await
+        .unwrap()
+        .unwrap()
+        .into()
+}
+
+#[tokio::test]
+async fn test_room_stats_propagate_to_status_and_events() {
+    let storage = Arc::new(MemoryStorage::new());
+    let event_bus = EventBus::new();
+    let mut events = event_bus.create_session_stream("session-1".to_string());
+    seed_session(&storage, "session-1").await;
+    let poller = RoomStatsPoller::new(storage.clone(), event_bus, Duration::from_millis(20));
+    let room = Arc::new(FakeRoom::default());
+
+    // Nothing is reported before the first poll
+    let response = status(&storage, "session-1").await;
+    assert!(response.room_stats.is_none());
+    assert!(serde_json::to_value(&response)
+        .unwrap()
+        .get("room_stats")
+        .is_none());
+
+    room.set(vec![
+        participant("user", 2, ConnectionQuality::Good),
+        participant("asr-service", 1, ConnectionQuality::Excellent),
+    ]);
+    assert!(poller.poll_once("session-1", room.as_ref()).await.unwrap());
+
+    let expected = RoomStats {
+        participant_count: 2,
+        track_count: 3,
+        participants: vec![
+            participant("asr-service", 1, ConnectionQuality::Excellent),
+            participant("user", 2, ConnectionQuality::Good),
+        ],
+    };
+    let response = status(&storage, "session-1").await;
+    assert_eq!(response.room_stats.as_ref(), Some(&expected));
+    let value = serde_json::to_value(&response).unwrap();
+    assert_eq!(value["room_stats"]["track_count"], 3);
+    assert_eq!(
+        value["room_stats"]["participants"][1]["connection_quality"],
+        "good"
+    );
+
+    let event = events.try_recv().unwrap().event;
+    match event {
+        SessionEvent::Stats { session_id, stats } => {
+            assert_eq!(session_id, "session-1");
+            assert_eq!(stats, expected);
+        }
+        other => panic!("expected stats event, got {:?}", other),
+    }
+
+    // The background task keeps the cached stats current
+    room.set(vec![participant("user", 0, ConnectionQuality::Poor)]);
+    let handle = poller.spawn("session-1".to_string(), room.clone());
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while status(&storage, "session-1")
+            .await
+            .room_stats
+            .map(|s| s.track_count)
+            != Some(0)
+        {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("stats were not refreshed");
+    let stats = status(&storage, "session-1").await.room_stats.unwrap();
+    assert_eq!(stats.participant_count, 1);
+    assert_eq!(
+        stats.participants[0].connection_quality,
+        ConnectionQuality::Poor
+    );
+    handle.abort();
+}
+
+#[tokio::test]
+async fn test_polling_stops_when_the_session_ends() {
+    let storage = Arc::new(MemoryStorage::new());
+    let event_bus = EventBus::new();
+    seed_session(&storage, "session-1").await;
+    let poller = RoomStatsPoller::new(storage.clone(), event_bus, Duration::from_millis(20));
+    let room = Arc::new(FakeRoom::default());
+
+    let handle = poller.spawn("session-1".to_string(), room.clone());
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while room.polls.load(Ordering::SeqCst) == 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("room was never polled");
+
+    let mut session = storage.get_session("session-1").await.unwrap().unwrap();
+    session.update_status(SessionStatus::Terminated);
+    storage.update_session(&session).await.unwrap();
+
+    tokio::time::timeout(Duration::from_secs(5), handle)
+        .await
+        .expect("poller kept running after termination")
+        .unwrap();
+    assert!(!poller.poll_once("unknown", room.as_ref()).await.unwrap());
+}
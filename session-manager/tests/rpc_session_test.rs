@@ -0,0 +1,54 @@
+use microservice_sdk::{protocol::Response, MicroserviceError, PendingRequests};
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_response_resolves_matching_request() {
+    let pending = Arc::new(PendingRequests::new());
+    let (first, first_rx) = pending.start("ping", json!(null));
+    let (second, second_rx) = pending.start("ping", json!(null));
+    assert_ne!(first.id, second.id);
+    assert_eq!(pending.len(), 2);
+
+    // Answer the second request only, as the remote side would after a round trip
+    let responder = pending.clone();
+    let answered = second.id.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(responder.resolve(Response::ok(answered, json!("pong"))));
+    });
+
+    let response = pending
+        .wait(&second.id, second_rx, Duration::from_secs(2))
+        .await
+        .unwrap();
+    assert_eq!(response.id, second.id);
+    assert_eq!(response.into_result(), Ok(json!("pong")));
+
+    // The first request is untouched by the other response
+    assert_eq!(pending.len(), 1);
+    assert!(pending.resolve(Response::err(first.id.clone(), "busy")));
+    let response = pending
+        .wait(&first.id, first_rx, Duration::from_secs(2))
+        .await
+        .unwrap();
+    assert_eq!(response.into_result(), Err("busy".to_string()));
+    assert!(pending.is_empty());
+}
+
+#[tokio::test]
+async fn test_unanswered_request_times_out() {
+    let pending = PendingRequests::new();
+    let (request, response) = pending.start("ping", json!(null));
+
+    let error = pending
+        .wait(&request.id, response, Duration::from_millis(50))
+        .await
+        .unwrap_err();
+    assert!(matches!(error, MicroserviceError::RpcTimeout(ref id) if *id == request.id));
+    assert!(pending.is_empty());
+
+    // A response arriving after the timeout is not delivered to anyone
+    assert!(!pending.resolve(Response::ok(request.id, json!("pong"))));
+}
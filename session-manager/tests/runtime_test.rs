@@ -0,0 +1,31 @@
+use session_manager::{config::ServerConfig, server::build_runtime};
+
+fn server_config(workers: Option<usize>) -> ServerConfig {
+    ServerConfig {
+        host: "127.0.0.1".to_string(),
+        port: 0,
+        workers,
+        backlog: 1024,
+        max_sessions: 0,
+    }
+}
+
+#[test]
+fn test_runtime_uses_configured_worker_count() {
+    let runtime = build_runtime(&server_config(Some(2))).expect("runtime");
+    assert_eq!(runtime.metrics().num_workers(), 2);
+
+    // Timers and IO drivers are enabled
+    runtime.block_on(async { tokio::time::sleep(std::time::Duration::from_millis(1)).await });
+}
+
+#[test]
+fn test_runtime_falls_back_to_default_worker_count() {
+    let runtime = build_runtime(&server_config(None)).expect("runtime");
+    assert!(runtime.metrics().num_workers() >= 1);
+}
+
+#[test]
+fn test_zero_workers_is_rejected() {
+    assert!(build_runtime(&server_config(Some(0))).is_err());
+}
@@ -0,0 +1,71 @@
+use async_trait::async_trait;
+use microservice_sdk::{
+    MicroserviceConfig, MicroserviceHandler, MicroserviceRunner, RoomConnectionState,
+    RoomConnections,
+};
+use reqwest::StatusCode;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::sleep;
+
+mod common;
+
+struct IdleService;
+
+#[async_trait]
+impl MicroserviceHandler for IdleService {}
+
+async fn health_status(url: &str) -> Option<StatusCode> {
+    reqwest::get(url)
+        .await
+        .ok()
+        .map(|response| response.status())
+}
+
+#[tokio::test]
+async fn test_health_fails_after_room_disconnects() {
+    let (base_url, server_handle) = common::start_server(common::test_config(8782)).await;
+
+    let connections = RoomConnections::new();
+    let runner = MicroserviceRunner::new(
+        MicroserviceConfig::new(
+            base_url,
+            "health-service".to_string(),
+            "http://127.0.0.1:8783".to_string(),
+        ),
+        Arc::new(IdleService),
+    )
+    .expect("runner")
+    .with_room_connections(connections.clone());
+    let runner_handle = tokio::spawn(async move { runner.start().await });
+
+    let health_url = "http://127.0.0.1:8783/health";
+    let mut status = None;
+    for _ in 0..50 {
+        status = health_status(health_url).await;
+        if status.is_some() {
+            break;
+        }
+        sleep(Duration::from_millis(100)).await;
+    }
+    assert_eq!(status, Some(StatusCode::OK));
+
+    connections.set("session-health", RoomConnectionState::Connected);
+    assert_eq!(health_status(health_url).await, Some(StatusCode::OK));
+
+    // Reconnecting is still healthy; only a lost connection counts
+    connections.set("session-health", RoomConnectionState::Reconnecting);
+    assert_eq!(health_status(health_url).await, Some(StatusCode::OK));
+
+    connections.set("session-health", RoomConnectionState::Disconnected);
+    let response = reqwest::get(health_url).await.unwrap();
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert!(response.text().await.unwrap().contains("session-health"));
+
+    // Leaving the room on purpose stops it counting against the service
+    connections.remove("session-health");
+    assert_eq!(health_status(health_url).await, Some(StatusCode::OK));
+
+    runner_handle.abort();
+    server_handle.abort();
+}
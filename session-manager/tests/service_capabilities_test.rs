@@ -0,0 +1,119 @@
+use axum::{routing::post, Json, Router};
+use microservice_sdk::{
+    CreateSessionRequest, MicroserviceConfig, SessionClient, SessionManagerClient,
+};
+use serde_json::json;
+use session_manager::{
+    domain::{JoinRoomRequest, NotifyRetryPolicy, Session},
+    services::SessionServiceImpl,
+};
+use std::time::Duration;
+use tokio::net::TcpListener;
+
+mod common;
+
+/// Serve `/join-room`, answering every request with `response`
+async fn spawn_service(response: serde_json::Value) -> String {
+    let app = Router::new().route(
+        "/join-room",
+        post(move || {
+            let response = response.clone();
+            async move { Json(response) }
+        }),
+    );
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let endpoint = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    endpoint
+}
+
+fn join_request(session_id: &str) -> JoinRoomRequest {
+    JoinRoomRequest {
+        room_name: format!("room-{}", session_id),
+        session_id: session_id.to_string(),
+        service_identity: "capable-service".to_string(),
+        access_token: "token".to_string(),
+        livekit_url: "ws://localhost:7880".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_join_notification_returns_reported_capabilities() {
+    let endpoint = spawn_service(json!({
+        "success": true,
+        "message": "joined",
+        "capabilities": { "topics": ["asr"], "model_version": "2.1" }
+    }))
+    .await;
+    let client = SessionServiceImpl::build_http_client().expect("client");
+    let retry = NotifyRetryPolicy {
+        max_attempts: 1,
+        base_delay: Duration::from_millis(10),
+    };
+
+    let capabilities =
+        Session::notify_service_join(&client, endpoint, join_request("session-caps"), &retry)
+            .await
+            .expect("notification should succeed");
+
+    assert_eq!(capabilities["topics"], json!(["asr"]));
+    assert_eq!(capabilities["model_version"], "2.1");
+
+    // Services that report nothing are still accepted
+    let endpoint = spawn_service(json!({ "success": true })).await;
+    let capabilities =
+        Session::notify_service_join(&client, endpoint, join_request("session-plain"), &retry)
+            .await
+            .expect("notification should succeed");
+    assert!(capabilities.is_empty());
+}
+
+#[tokio::test]
+async fn test_reported_capabilities_surface_in_session_status() {
+    common::wait_for_livekit().await;
+    let (base_url, server_handle) = common::start_server(common::test_config(8783)).await;
+
+    let service_endpoint = spawn_service(json!({
+        "success": true,
+        "message": "joined",
+        "capabilities": { "codecs": ["opus"] }
+    }))
+    .await;
+    SessionManagerClient::new(MicroserviceConfig::new(
+        base_url.clone(),
+        "codec-service".to_string(),
+        service_endpoint,
+    ))
+    .expect("SDK client")
+    .register()
+    .await
+    .expect("registration");
+
+    let session_client = SessionClient::new(base_url, 10).expect("session client");
+    let mut request = CreateSessionRequest::new("capability-user");
+    request.required_services = Some(vec!["codec-service".to_string()]);
+    let created = session_client
+        .create_session(&request)
+        .await
+        .expect("create session");
+
+    let mut capabilities = None;
+    for _ in 0..50 {
+        let status = session_client
+            .get_session(&created.session_id)
+            .await
+            .expect("session status");
+        if let Some(reported) = status.service_capabilities.get("codec-service") {
+            capabilities = Some(reported.clone());
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    let capabilities = capabilities.expect("capabilities are reported");
+    assert_eq!(capabilities["codecs"], json!(["opus"]));
+
+    server_handle.abort();
+}
@@ -0,0 +1,99 @@
+use session_manager::{
+    config::{DiscoveryConfig, StaticServiceConfig},
+    domain::{MicroserviceInfo, ServiceStatus},
+    services::{MicroserviceRegistry, StaticDiscovery},
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+fn static_service(service_id: &str, service_type: &str) -> StaticServiceConfig {
+    StaticServiceConfig {
+        service_id: service_id.to_string(),
+        endpoint: format!("http://{}.robots.svc.cluster.local:3000", service_id),
+        metadata: HashMap::from([("type".to_string(), service_type.to_string())]),
+    }
+}
+
+async fn registry() -> MicroserviceRegistry {
+    let config = DiscoveryConfig {
+        static_services: vec![
+            static_service("asr-static", "ASR"),
+            static_service("tts-static", "TTS"),
+        ],
+    };
+    let registry = MicroserviceRegistry::new()
+        .with_discovery_source(Arc::new(StaticDiscovery::from_config(&config)));
+
+    registry
+        .register_service(MicroserviceInfo::new(
+            "llm-registered".to_string(),
+            "http://127.0.0.1:9".to_string(),
+            HashMap::new(),
+        ))
+        .await
+        .unwrap();
+    registry
+}
+
+fn ids(services: &[MicroserviceInfo]) -> Vec<&str> {
+    let mut ids: Vec<&str> = services
+        .iter()
+        .map(|service| service.service_id.as_str())
+        .collect();
+    ids.sort();
+    ids
+}
+
+#[tokio::test]
+async fn test_static_services_are_available_alongside_registered_ones() {
+    let registry = registry().await;
+
+    let available = registry.get_all_available_services().await.unwrap();
+    assert_eq!(
+        ids(&available),
+        vec!["asr-static", "llm-registered", "tts-static"]
+    );
+    let asr = available
+        .iter()
+        .find(|service| service.service_id == "asr-static")
+        .unwrap();
+    assert_eq!(
+        asr.endpoint,
+        "http://asr-static.robots.svc.cluster.local:3000"
+    );
+
+    // Discovered services can be required by id and by type
+    let by_id = registry
+        .get_services_by_ids(&["tts-static".to_string(), "missing".to_string()])
+        .await
+        .unwrap();
+    assert_eq!(ids(&by_id), vec!["tts-static"]);
+    let by_type = registry
+        .pick_service_by_type("ASR", &Default::default())
+        .await
+        .unwrap()
+        .expect("static ASR instance");
+    assert_eq!(by_type.service_id, "asr-static");
+}
+
+#[tokio::test]
+async fn test_registration_overrides_discovered_entry() {
+    let registry = registry().await;
+
+    // Once the service registers itself, its own reported status counts
+    registry
+        .register_service(MicroserviceInfo::new(
+            "asr-static".to_string(),
+            "http://10.0.0.7:3000".to_string(),
+            HashMap::new(),
+        ))
+        .await
+        .unwrap();
+    registry
+        .update_service_status("asr-static", ServiceStatus::Disconnected)
+        .await
+        .unwrap();
+
+    let available = registry.get_all_available_services().await.unwrap();
+    assert_eq!(ids(&available), vec!["llm-registered", "tts-static"]);
+}
@@ -0,0 +1,127 @@
+use axum::{routing::post, Json, Router};
+use microservice_sdk::{MicroserviceConfig, SessionManagerClient};
+use reqwest::Client;
+use serde_json::json;
+use session_manager::{
+    domain::{MicroserviceInfo, Session, SessionStatus},
+    services::SessionService,
+    storage::{memory::MemoryStorage, SessionStorage},
+    utils::errors::SessionManagerError,
+};
+use std::{collections::HashMap, sync::Arc};
+use tokio::net::TcpListener;
+
+mod common;
+
+async fn seed_session(storage: &MemoryStorage, services: &[&str]) -> Session {
+    let mut session = Session::new(
+        "session-ready".to_string(),
+        "room-ready".to_string(),
+        HashMap::new(),
+    );
+    for service_id in services {
+        session.add_microservice(MicroserviceInfo::new(
+            service_id.to_string(),
+            format!("http://{}.local", service_id),
+            HashMap::new(),
+        ));
+    }
+    session.update_status(SessionStatus::WaitingForServices);
+    storage.save_session(&session).await.unwrap();
+    session
+}
+
+#[tokio::test]
+async fn test_session_ready_once_all_services_signal_ready() {
+    let storage = Arc::new(MemoryStorage::new());
+    let session = seed_session(&storage, &["asr-service", "tts-service"]).await;
+    let service = common::session_service(storage);
+
+    let after_first = service
+        .mark_service_ready(&session.id, "asr-service")
+        .await
+        .unwrap();
+    assert_eq!(after_first.status, SessionStatus::WaitingForServices);
+    assert_eq!(after_first.get_pending_services(), vec!["tts-service"]);
+
+    let after_second = service
+        .mark_service_ready(&session.id, "tts-service")
+        .await
+        .unwrap();
+    assert_eq!(after_second.status, SessionStatus::Ready);
+
+    let stored = service.get_session(&session.id).await.unwrap().unwrap();
+    assert_eq!(stored.status, SessionStatus::Ready);
+}
+
+#[tokio::test]
+async fn test_unknown_service_cannot_signal_ready() {
+    let storage = Arc::new(MemoryStorage::new());
+    let session = seed_session(&storage, &["asr-service"]).await;
+    let service = common::session_service(storage);
+
+    let result = service.mark_service_ready(&session.id, "intruder").await;
+    assert!(matches!(
+        result,
+        Err(SessionManagerError::InvalidRequest(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_sdk_notify_ready_flips_session_status() {
+    common::wait_for_livekit().await;
+    let (base_url, server_handle) = common::start_server(common::test_config(8771)).await;
+
+    // Stand-in microservice that accepts every join request
+    let app = Router::new().route(
+        "/join-room",
+        post(|| async { Json(json!({ "success": true, "message": "joined" })) }),
+    );
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let service_endpoint = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+
+    let sdk_client = SessionManagerClient::new(MicroserviceConfig::new(
+        base_url.clone(),
+        "ready-service".to_string(),
+        service_endpoint,
+    ))
+    .expect("SDK client");
+    sdk_client.register().await.expect("registration");
+
+    let client = Client::new();
+    let session = common::create_session(
+        &client,
+        &base_url,
+        json!({
+            "user_identity": "ready-user",
+            "required_services": ["ready-service"]
+        }),
+    )
+    .await;
+    let session_id = session["session_id"].as_str().expect("session_id");
+
+    let response = sdk_client
+        .notify_ready("ready-service", session_id)
+        .await
+        .expect("notify_ready");
+    assert!(response.all_services_ready);
+
+    let body: serde_json::Value = client
+        .get(format!("{}/api/v1/sessions?status=Ready", base_url))
+        .send()
+        .await
+        .expect("List request failed")
+        .json()
+        .await
+        .expect("list response");
+    assert!(body["sessions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|s| s["session_id"] == session_id));
+
+    server_handle.abort();
+}
@@ -0,0 +1,116 @@
+use session_manager::{
+    domain::{MicroserviceInfo, NotifyRetryPolicy, ServiceStatus},
+    events::EventBus,
+    services::{
+        session_service::CreateSessionRequest, MicroserviceRegistry, SessionService,
+        SessionServiceImpl,
+    },
+    storage::memory::MemoryStorage,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+mod common;
+
+async fn register(registry: &MicroserviceRegistry, service_id: &str, service_type: Option<&str>) {
+    let metadata = service_type
+        .map(|service_type| HashMap::from([("type".to_string(), service_type.to_string())]))
+        .unwrap_or_default();
+    registry
+        .register_service(MicroserviceInfo::new(
+            service_id.to_string(),
+            "http://127.0.0.1:9".to_string(),
+            metadata,
+        ))
+        .await
+        .unwrap();
+}
+
+async fn service_with_pool() -> SessionServiceImpl {
+    let registry = Arc::new(MicroserviceRegistry::new());
+    register(&registry, "asr-a", Some("ASR")).await;
+    register(&registry, "asr-b", Some("ASR")).await;
+    register(&registry, "asr-down", Some("ASR")).await;
+    registry
+        .update_service_status("asr-down", ServiceStatus::Disconnected)
+        .await
+        .unwrap();
+    register(&registry, "tts-1", Some("TTS")).await;
+    register(&registry, "untyped", None).await;
+
+    let mut config = common::test_config(0);
+    config.livekit.server_url = common::spawn_mock_livekit().await;
+    SessionServiceImpl::new(
+        Arc::new(MemoryStorage::new()),
+        registry,
+        config.livekit.clone(),
+        config.livekit.server_url.clone(),
+        EventBus::new(),
+        SessionServiceImpl::build_http_client().unwrap(),
+        NotifyRetryPolicy::from(&config.microservices),
+    )
+}
+
+fn request(required_service_types: &[&str]) -> CreateSessionRequest {
+    CreateSessionRequest {
+        user_identity: "typed-user".to_string(),
+        user_name: None,
+        room_name: None,
+        metadata: None,
+        required_services: None,
+        required_service_types: Some(
+            required_service_types
+                .iter()
+                .map(|service_type| service_type.to_string())
+                .collect(),
+        ),
+        reuse_existing: false,
+        observe: false,
+        owner: None,
+        max_lifetime_secs: None,
+        min_ready_services: None,
+    }
+}
+
+fn service_ids(session: &session_manager::domain::Session) -> Vec<String> {
+    let mut ids: Vec<String> = session
+        .registered_microservices
+        .iter()
+        .map(|service| service.service_id.clone())
+        .collect();
+    ids.sort();
+    ids
+}
+
+#[tokio::test]
+async fn test_required_types_select_registered_instances() {
+    let service = service_with_pool().await;
+
+    let (first, _) = service
+        .create_session(request(&["ASR", "TTS"]))
+        .await
+        .unwrap();
+    let first_ids = service_ids(&first);
+    assert_eq!(first_ids.len(), 2);
+    assert!(["asr-a", "asr-b"].contains(&first_ids[0].as_str()));
+    assert_eq!(first_ids[1], "tts-1");
+
+    // Instances of a type are handed out in turn
+    let (second, _) = service.create_session(request(&["ASR"])).await.unwrap();
+    let second_ids = service_ids(&second);
+    assert_eq!(second_ids.len(), 1);
+    assert_ne!(second_ids[0], first_ids[0]);
+}
+
+#[tokio::test]
+async fn test_missing_type_is_reported_and_extra_slots_share_instances() {
+    let service = service_with_pool().await;
+
+    let mut validation = service
+        .validate_session(&request(&["ASR", "ASR", "ASR", "GPU"]))
+        .await
+        .unwrap();
+    validation.available_services.sort();
+    assert_eq!(validation.available_services, vec!["asr-a", "asr-b"]);
+    assert_eq!(validation.missing_services, vec!["GPU"]);
+}
@@ -0,0 +1,53 @@
+use reqwest::Client;
+use serde_json::json;
+
+mod common;
+
+#[tokio::test]
+async fn test_reuse_existing_returns_active_session() {
+    let mut config = common::test_config(8779);
+    config.livekit.server_url = common::spawn_mock_livekit().await;
+    let (base_url, server_handle) = common::start_server(config).await;
+    let client = Client::new();
+
+    let body = json!({
+        "user_identity": "mobile-user",
+        "required_services": [],
+        "reuse_existing": true
+    });
+
+    // Without microservices the session is immediately Ready
+    let first = common::create_session(&client, &base_url, body.clone()).await;
+    assert_eq!(first["status"], "Ready");
+
+    let second = common::create_session(&client, &base_url, body).await;
+    assert_eq!(second["session_id"], first["session_id"]);
+    assert_eq!(second["room_name"], first["room_name"]);
+    assert!(second["access_token"]
+        .as_str()
+        .is_some_and(|t| !t.is_empty()));
+
+    // Without the flag a new session is created as before
+    let third = common::create_session(
+        &client,
+        &base_url,
+        json!({ "user_identity": "mobile-user", "required_services": [] }),
+    )
+    .await;
+    assert_ne!(third["session_id"], first["session_id"]);
+
+    // Other users never get someone else's session
+    let other = common::create_session(
+        &client,
+        &base_url,
+        json!({
+            "user_identity": "other-user",
+            "required_services": [],
+            "reuse_existing": true
+        }),
+    )
+    .await;
+    assert_ne!(other["session_id"], first["session_id"]);
+
+    server_handle.abort();
+}
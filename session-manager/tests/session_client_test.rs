@@ -0,0 +1,161 @@
+use axum::{routing::post, Json, Router};
+use futures::StreamExt;
+use microservice_sdk::{
+    CreateSessionRequest, MicroserviceConfig, MicroserviceError, SessionClient, SessionEvent,
+    SessionManagerClient, SessionStatus,
+};
+use serde_json::json;
+use std::time::Duration;
+use tokio::net::TcpListener;
+
+mod common;
+
+/// Register a stand-in microservice that accepts every join request
+async fn register_stub_service(base_url: &str, service_id: &str) -> SessionManagerClient {
+    let app = Router::new().route(
+        "/join-room",
+        post(|| async { Json(json!({ "success": true, "message": "joined" })) }),
+    );
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let service_endpoint = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+
+    let service_client = SessionManagerClient::new(MicroserviceConfig::new(
+        base_url.to_string(),
+        service_id.to_string(),
+        service_endpoint,
+    ))
+    .expect("SDK client");
+    service_client.register().await.expect("registration");
+    service_client
+}
+
+#[tokio::test]
+async fn test_wait_until_ready_returns_once_services_are_ready() {
+    common::wait_for_livekit().await;
+    let (base_url, server_handle) = common::start_server(common::test_config(8774)).await;
+
+    let service_client = register_stub_service(&base_url, "slow-service").await;
+
+    let session_client = SessionClient::new(base_url.clone(), 10)
+        .expect("session client")
+        .with_poll_interval(Duration::from_millis(100));
+
+    let mut request = CreateSessionRequest::new("polling-user");
+    request.required_services = Some(vec!["slow-service".to_string()]);
+    let created = session_client
+        .create_session(&request)
+        .await
+        .expect("create session");
+    assert_ne!(created.status, SessionStatus::Ready);
+
+    // Still waiting on the service
+    let result = session_client
+        .wait_until_ready(&created.session_id, Duration::from_millis(300))
+        .await;
+    assert!(matches!(result, Err(MicroserviceError::Timeout)));
+
+    // The service finishes loading a moment later
+    let session_id = created.session_id.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        service_client
+            .notify_ready("slow-service", &session_id)
+            .await
+            .expect("notify_ready");
+    });
+
+    let status = session_client
+        .wait_until_ready(&created.session_id, Duration::from_secs(10))
+        .await
+        .expect("session becomes ready");
+    assert_eq!(status.status, SessionStatus::Ready);
+    assert_eq!(status.ready_services, vec!["slow-service"]);
+    assert!(status.pending_services.is_empty());
+
+    server_handle.abort();
+}
+
+#[tokio::test]
+async fn test_get_unknown_session_returns_not_found() {
+    let mut config = common::test_config(8775);
+    config.livekit.server_url = "ws://127.0.0.1:1".to_string();
+    let (base_url, server_handle) = common::start_server(config).await;
+
+    let session_client = SessionClient::new(base_url, 10).expect("session client");
+    let result = session_client.get_session("missing").await;
+    assert!(matches!(
+        result,
+        Err(MicroserviceError::SessionManagerError { status: 404, .. })
+    ));
+
+    server_handle.abort();
+}
+
+#[tokio::test]
+async fn test_subscribe_events_yields_session_ready() {
+    common::wait_for_livekit().await;
+    let (base_url, server_handle) = common::start_server(common::test_config(8776)).await;
+
+    let service_client = register_stub_service(&base_url, "event-service").await;
+    let session_client = SessionClient::new(base_url.clone(), 30).expect("session client");
+
+    let mut request = CreateSessionRequest::new("subscribing-user");
+    request.required_services = Some(vec!["event-service".to_string()]);
+    let created = session_client
+        .create_session(&request)
+        .await
+        .expect("create session");
+
+    let events = session_client
+        .subscribe_events(&created.session_id)
+        .expect("subscribe");
+    futures::pin_mut!(events);
+
+    // Give the subscription a moment to connect before the event is published
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    service_client
+        .notify_ready("event-service", &created.session_id)
+        .await
+        .expect("notify_ready");
+
+    let ready = tokio::time::timeout(Duration::from_secs(10), async {
+        while let Some(event) = events.next().await {
+            if let Ok(SessionEvent::SessionReady { session_id, .. }) = event {
+                return session_id;
+            }
+        }
+        panic!("event stream ended before SessionReady");
+    })
+    .await
+    .expect("SessionReady within timeout");
+    assert_eq!(ready, created.session_id);
+
+    server_handle.abort();
+}
+
+#[tokio::test]
+async fn test_subscribe_to_unknown_session_fails() {
+    let mut config = common::test_config(8777);
+    config.livekit.server_url = "ws://127.0.0.1:1".to_string();
+    let (base_url, server_handle) = common::start_server(config).await;
+
+    let session_client = SessionClient::new(base_url, 10).expect("session client");
+    let events = session_client
+        .subscribe_events("missing")
+        .expect("subscribe");
+    futures::pin_mut!(events);
+
+    assert!(matches!(
+        events.next().await,
+        Some(Err(MicroserviceError::SessionManagerError {
+            status: 404,
+            ..
+        }))
+    ));
+    assert!(events.next().await.is_none());
+
+    server_handle.abort();
+}
@@ -0,0 +1,91 @@
+use futures::TryStreamExt;
+use reqwest::{Client, StatusCode};
+use session_manager::{
+    domain::{MicroserviceInfo, Session, SessionStatus},
+    storage::{jsonl, memory::MemoryStorage, SessionStorage},
+};
+use std::{collections::HashMap, sync::Arc};
+
+mod common;
+
+#[tokio::test]
+async fn test_export_import_round_trip() {
+    let storage = Arc::new(MemoryStorage::new());
+    for i in 0..3 {
+        let mut session = Session::new(
+            format!("session-{}", i),
+            format!("room-{}", i),
+            HashMap::from([("index".to_string(), i.to_string())]),
+        );
+        session.user_identity = Some(format!("user-{}", i));
+        session.add_microservice(MicroserviceInfo::new(
+            "asr-service".to_string(),
+            "http://asr.local".to_string(),
+            HashMap::new(),
+        ));
+        session.mark_service_ready("asr-service");
+        session.update_status(SessionStatus::Active);
+        storage.save_session(&session).await.unwrap();
+    }
+
+    let dump: Vec<u8> = jsonl::export_sessions(storage.clone())
+        .try_concat()
+        .await
+        .expect("export");
+    assert_eq!(dump.iter().filter(|&&b| b == b'\n').count(), 3);
+
+    let restored = MemoryStorage::new();
+    let imported = jsonl::import_sessions(&restored, dump.as_slice())
+        .await
+        .expect("import");
+    assert_eq!(imported, 3);
+
+    for original in storage.list_sessions().await.unwrap() {
+        let copy = restored
+            .get_session(&original.id)
+            .await
+            .unwrap()
+            .expect("session restored");
+        assert_eq!(
+            serde_json::to_value(&copy).unwrap(),
+            serde_json::to_value(&original).unwrap()
+        );
+        assert!(copy.room_connection.is_none());
+    }
+}
+
+#[tokio::test]
+async fn test_debug_export_requires_admin_token() {
+    let mut config = common::test_config(8773);
+    config.livekit.server_url = "ws://127.0.0.1:1".to_string();
+    config.auth.admin_token = Some("debug-secret".to_string());
+    let (base_url, server_handle) = common::start_server(config).await;
+
+    let client = Client::new();
+    let export_url = format!("{}/api/v1/debug/sessions/export", base_url);
+
+    let response = client.get(&export_url).send().await.expect("Export failed");
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = client
+        .get(&export_url)
+        .bearer_auth("wrong-secret")
+        .send()
+        .await
+        .expect("Export failed");
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = client
+        .get(&export_url)
+        .bearer_auth("debug-secret")
+        .send()
+        .await
+        .expect("Export failed");
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()[reqwest::header::CONTENT_TYPE],
+        "application/x-ndjson"
+    );
+
+    server_handle.abort();
+}
@@ -0,0 +1,73 @@
+use reqwest::{Client, StatusCode};
+use serde_json::json;
+
+mod common;
+
+#[tokio::test]
+async fn test_client_leave_terminates_session_without_waiting_for_timeout() {
+    common::wait_for_livekit().await;
+    let (base_url, server_handle) = common::start_server(common::test_config(8769)).await;
+
+    let client = Client::new();
+    let session = common::create_session(
+        &client,
+        &base_url,
+        json!({
+            "user_identity": "leaving-user",
+            "required_services": []
+        }),
+    )
+    .await;
+    let session_id = session["session_id"].as_str().expect("session_id");
+    let leave_url = format!("{}/api/v1/sessions/{}/leave", base_url, session_id);
+
+    // Only the user the session was created for may leave it
+    let response = client
+        .post(&leave_url)
+        .json(&json!({ "user_identity": "someone-else" }))
+        .send()
+        .await
+        .expect("Leave request failed");
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let response = client
+        .post(&leave_url)
+        .json(&json!({ "user_identity": "leaving-user" }))
+        .send()
+        .await
+        .expect("Leave request failed");
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: serde_json::Value = response.json().await.expect("leave response");
+    assert_eq!(body["status"], "Terminated");
+
+    let response = client
+        .get(format!("{}/api/v1/sessions?status=Terminated", base_url))
+        .send()
+        .await
+        .expect("List request failed");
+    let body: serde_json::Value = response.json().await.expect("list response");
+    assert!(body["sessions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|s| s["session_id"] == session_id));
+
+    server_handle.abort();
+}
+
+#[tokio::test]
+async fn test_leave_unknown_session_returns_not_found() {
+    let mut config = common::test_config(8770);
+    config.livekit.server_url = "ws://127.0.0.1:1".to_string();
+    let (base_url, server_handle) = common::start_server(config).await;
+
+    let response = Client::new()
+        .post(format!("{}/api/v1/sessions/missing/leave", base_url))
+        .json(&json!({ "user_identity": "anyone" }))
+        .send()
+        .await
+        .expect("Leave request failed");
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    server_handle.abort();
+}
@@ -0,0 +1,155 @@
+use chrono::{Duration, Utc};
+use reqwest::{Client, StatusCode};
+use serde_json::json;
+use session_manager::{
+    config::SweeperConfig,
+    domain::{LeaveReason, Session, SessionStatus},
+    events::{EventBus, SessionEvent},
+    services::{SessionService, SessionServiceImpl, SessionSweeper},
+    storage::{memory::MemoryStorage, SessionStorage},
+};
+use std::{collections::HashMap, sync::Arc};
+
+mod common;
+
+async fn seed(storage: &MemoryStorage, id: &str, age: Duration, max_lifetime_secs: Option<u64>) {
+    let mut session = Session::new(id.to_string(), format!("room-{}", id), HashMap::new());
+    // A connected client keeps the session Active
+    session.status = SessionStatus::Active;
+    session.user_identity = Some(format!("user-{}", id));
+    session.created_at = Utc::now() - age;
+    session.max_lifetime_secs = max_lifetime_secs;
+    storage.save_session(&session).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_sweeper_terminates_sessions_past_max_lifetime() {
+    let mut config = common::test_config(0);
+    config.livekit.server_url = common::spawn_mock_livekit().await;
+    let storage = Arc::new(MemoryStorage::new());
+    seed(&storage, "overridden", Duration::seconds(2), Some(1)).await;
+    seed(&storage, "default", Duration::hours(2), None).await;
+    seed(&storage, "long-lived", Duration::hours(2), Some(86400)).await;
+    seed(&storage, "fresh", Duration::seconds(5), None).await;
+
+    let event_bus = EventBus::new();
+    let mut events = event_bus.subscribe_global();
+    let service: Arc<dyn SessionService> = Arc::new(SessionServiceImpl::new(
+        storage.clone(),
+        Arc::new(session_manager::services::MicroserviceRegistry::new()),
+        config.livekit.clone(),
+        config.livekit.server_url.clone(),
+        event_bus,
+        SessionServiceImpl::build_http_client().unwrap(),
+        session_manager::domain::NotifyRetryPolicy::from(&config.microservices),
+    ));
+    let sweeper = SessionSweeper::new(
+        service,
+        SweeperConfig {
+            enabled: true,
+            interval_secs: 1,
+            max_age_secs: 600,
+            max_lifetime_secs: 3600,
+            terminated_grace_secs: 300,
+        },
+    );
+
+    let mut swept = sweeper.sweep().await;
+    swept.sort();
+    assert_eq!(swept, vec!["default", "overridden"]);
+    assert!(storage.get_session("overridden").await.unwrap().is_none());
+    assert!(storage.get_session("default").await.unwrap().is_none());
+    assert!(storage.get_session("long-lived").await.unwrap().is_some());
+    assert!(storage.get_session("fresh").await.unwrap().is_some());
+
+    let mut reasons = Vec::new();
+    while let Ok(event) = events.try_recv() {
+        if let SessionEvent::SessionStatusChanged {
+            status: SessionStatus::Terminating,
+            reason,
+            ..
+        } = event.event
+        {
+            reasons.push(reason);
+        }
+    }
+    assert_eq!(reasons, vec![Some(LeaveReason::Expired); 2]);
+}
+
+#[tokio::test]
+async fn test_max_lifetime_override_must_be_positive() {
+    let mut config = common::test_config(8797);
+    config.livekit.server_url = common::spawn_mock_livekit().await;
+    let (base_url, server_handle) = common::start_server(config).await;
+    let client = Client::new();
+
+    let response = client
+        .post(format!("{}/api/v1/create-session", base_url))
+        .json(&json!({
+            "user_identity": "lifetime-user",
+            "max_lifetime_secs": 0
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = client
+        .post(format!("{}/api/v1/create-session", base_url))
+        .json(&json!({
+            "user_identity": "lifetime-user",
+            "max_lifetime_secs": u64::MAX
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let created = common::create_session(
+        &client,
+        &base_url,
+        json!({
+            "user_identity": "lifetime-user",
+            "max_lifetime_secs": 60,
+            "observe": false
+        }),
+    )
+    .await;
+    assert!(created["session_id"].is_string());
+
+    server_handle.abort();
+}
+
+#[tokio::test]
+async fn test_unrepresentable_lifetime_never_expires() {
+    let mut config = common::test_config(0);
+    config.livekit.server_url = common::spawn_mock_livekit().await;
+    let storage = Arc::new(MemoryStorage::new());
+    // Stored before the cap existed; the deadline overflows the calendar
+    seed(&storage, "unbounded", Duration::hours(2), Some(u64::MAX)).await;
+    seed(
+        &storage,
+        "huge",
+        Duration::hours(2),
+        Some(10_000_000_000_000),
+    )
+    .await;
+
+    let service = SessionServiceImpl::new(
+        storage.clone(),
+        Arc::new(session_manager::services::MicroserviceRegistry::new()),
+        config.livekit.clone(),
+        config.livekit.server_url.clone(),
+        EventBus::new(),
+        SessionServiceImpl::build_http_client().unwrap(),
+        session_manager::domain::NotifyRetryPolicy::from(&config.microservices),
+    );
+
+    let expired = service
+        .expire_sessions(Some(std::time::Duration::from_secs(u64::MAX)))
+        .await
+        .unwrap();
+    assert!(expired.is_empty());
+    assert!(storage.get_session("unbounded").await.unwrap().is_some());
+    assert!(storage.get_session("huge").await.unwrap().is_some());
+}
@@ -0,0 +1,99 @@
+use chrono::{Duration, Utc};
+use session_manager::{
+    domain::{Session, SessionStatus},
+    services::SessionService,
+    storage::{memory::MemoryStorage, SessionStorage},
+};
+use std::{collections::HashMap, sync::Arc};
+
+mod common;
+
+/// Store `count` sessions with increasing creation times, alternating Active/Ready
+async fn seed_sessions(storage: &MemoryStorage, count: usize) -> Vec<String> {
+    let base = Utc::now();
+    let mut ids = Vec::new();
+    for i in 0..count {
+        let mut session = Session::new(
+            format!("session-{:02}", i),
+            format!("room-{:02}", i),
+            HashMap::new(),
+        );
+        session.created_at = base + Duration::seconds(i as i64);
+        session.status = if i % 2 == 0 {
+            SessionStatus::Active
+        } else {
+            SessionStatus::Ready
+        };
+        storage.save_session(&session).await.unwrap();
+        ids.push(session.id);
+    }
+    ids
+}
+
+#[tokio::test]
+async fn test_list_sessions_paginates_in_creation_order() {
+    let storage = Arc::new(MemoryStorage::new());
+    let ids = seed_sessions(&storage, 5).await;
+    let service = common::session_service(storage);
+
+    let first = service.list_sessions(None, None, 2, None).await.unwrap();
+    let first_ids: Vec<_> = first.sessions.iter().map(|s| s.id.clone()).collect();
+    assert_eq!(first_ids, ids[0..2]);
+
+    let second = service
+        .list_sessions(None, None, 2, first.next_cursor.as_deref())
+        .await
+        .unwrap();
+    let second_ids: Vec<_> = second.sessions.iter().map(|s| s.id.clone()).collect();
+    assert_eq!(second_ids, ids[2..4]);
+
+    // Last page is partial and has no further cursor
+    let last = service
+        .list_sessions(None, None, 2, second.next_cursor.as_deref())
+        .await
+        .unwrap();
+    assert_eq!(last.sessions.len(), 1);
+    assert_eq!(last.sessions[0].id, ids[4]);
+    assert!(last.next_cursor.is_none());
+}
+
+#[tokio::test]
+async fn test_list_sessions_exact_page_has_no_next_cursor() {
+    let storage = Arc::new(MemoryStorage::new());
+    seed_sessions(&storage, 4).await;
+    let service = common::session_service(storage);
+
+    let page = service.list_sessions(None, None, 4, None).await.unwrap();
+    assert_eq!(page.sessions.len(), 4);
+    assert!(page.next_cursor.is_none());
+}
+
+#[tokio::test]
+async fn test_list_sessions_filters_by_status() {
+    let storage = Arc::new(MemoryStorage::new());
+    let ids = seed_sessions(&storage, 5).await;
+    let service = common::session_service(storage);
+
+    let page = service
+        .list_sessions(Some(SessionStatus::Active), None, 10, None)
+        .await
+        .unwrap();
+    let active_ids: Vec<_> = page.sessions.iter().map(|s| s.id.clone()).collect();
+    assert_eq!(
+        active_ids,
+        vec![ids[0].clone(), ids[2].clone(), ids[4].clone()]
+    );
+    assert!(page
+        .sessions
+        .iter()
+        .all(|s| s.status == SessionStatus::Active));
+}
+
+#[tokio::test]
+async fn test_list_sessions_rejects_malformed_cursor() {
+    let service = common::session_service(Arc::new(MemoryStorage::new()));
+    assert!(service
+        .list_sessions(None, None, 10, Some("not-a-cursor"))
+        .await
+        .is_err());
+}
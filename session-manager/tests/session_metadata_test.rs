@@ -0,0 +1,147 @@
+use reqwest::{Client, StatusCode};
+use serde_json::json;
+use session_manager::{
+    config::MetadataLimitConfig,
+    domain::Session,
+    services::SessionService,
+    storage::{memory::MemoryStorage, SessionStorage},
+    SessionManagerError,
+};
+use std::{collections::HashMap, sync::Arc};
+
+mod common;
+
+#[tokio::test]
+async fn test_update_metadata_merges_and_removes_keys() {
+    let storage = Arc::new(MemoryStorage::new());
+    let session = Session::new(
+        "session-metadata".to_string(),
+        "room-metadata".to_string(),
+        HashMap::from([
+            ("language".to_string(), "en".to_string()),
+            ("draft".to_string(), "true".to_string()),
+        ]),
+    );
+    storage.save_session(&session).await.unwrap();
+    let service = common::session_service(storage);
+
+    let updated = service
+        .update_metadata(
+            &session.id,
+            HashMap::from([
+                ("transcription_job".to_string(), Some("job-42".to_string())),
+                ("language".to_string(), Some("zh".to_string())),
+                ("draft".to_string(), None),
+            ]),
+        )
+        .await
+        .unwrap();
+
+    let expected = HashMap::from([
+        ("language".to_string(), "zh".to_string()),
+        ("transcription_job".to_string(), "job-42".to_string()),
+    ]);
+    assert_eq!(updated.metadata, expected);
+    assert!(updated.updated_at > session.updated_at);
+
+    let stored = service.get_session(&session.id).await.unwrap().unwrap();
+    assert_eq!(stored.metadata, expected);
+}
+
+#[tokio::test]
+async fn test_update_metadata_enforces_limits_on_the_merged_result() {
+    let storage = Arc::new(MemoryStorage::new());
+    let session = Session::new(
+        "session-limits".to_string(),
+        "room-limits".to_string(),
+        HashMap::from([
+            ("language".to_string(), "en".to_string()),
+            ("topic".to_string(), "support".to_string()),
+        ]),
+    );
+    storage.save_session(&session).await.unwrap();
+    let service = common::session_service(storage).with_metadata_limits(MetadataLimitConfig {
+        max_keys: 3,
+        max_bytes: 64,
+    });
+
+    // Each patch is small, but the merged metadata would exceed the limits
+    for patch in [
+        HashMap::from([
+            ("robot".to_string(), Some("arm-7".to_string())),
+            ("site".to_string(), Some("lab".to_string())),
+        ]),
+        HashMap::from([("notes".to_string(), Some("x".repeat(40)))]),
+    ] {
+        let error = service
+            .update_metadata(&session.id, patch)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(error, SessionManagerError::InvalidRequest(_)),
+            "{}",
+            error
+        );
+    }
+    let stored = service.get_session(&session.id).await.unwrap().unwrap();
+    assert_eq!(stored.metadata, session.metadata);
+
+    // Removing a key makes room for another
+    let updated = service
+        .update_metadata(
+            &session.id,
+            HashMap::from([
+                ("topic".to_string(), None),
+                ("robot".to_string(), Some("arm-7".to_string())),
+                ("site".to_string(), Some("lab".to_string())),
+            ]),
+        )
+        .await
+        .unwrap();
+    assert_eq!(updated.metadata.len(), 3);
+}
+
+#[tokio::test]
+async fn test_patch_metadata_is_visible_in_session_status() {
+    common::wait_for_livekit().await;
+    let (base_url, server_handle) = common::start_server(common::test_config(8772)).await;
+
+    let client = Client::new();
+    let session = common::create_session(
+        &client,
+        &base_url,
+        json!({
+            "user_identity": "metadata-user",
+            "metadata": { "language": "en", "draft": "true" },
+            "required_services": []
+        }),
+    )
+    .await;
+    let session_id = session["session_id"].as_str().expect("session_id");
+
+    let response = client
+        .patch(format!(
+            "{}/api/v1/sessions/{}/metadata",
+            base_url, session_id
+        ))
+        .json(&json!({ "transcription_job": "job-42", "draft": null }))
+        .send()
+        .await
+        .expect("Metadata request failed");
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let status: serde_json::Value = client
+        .get(format!("{}/api/v1/sessions/{}", base_url, session_id))
+        .send()
+        .await
+        .expect("Status request failed")
+        .json()
+        .await
+        .expect("status response");
+    assert_eq!(
+        status["metadata"],
+        json!({ "language": "en", "transcription_job": "job-42" })
+    );
+
+    server_handle.abort();
+}
@@ -0,0 +1,420 @@
+use futures::stream;
+use livekit::prelude::DisconnectReason;
+use session_manager::{
+    domain::{
+        disconnect_action, run_session_observer, DisconnectAction, IdentityScheme,
+        LifecycleObserver, ObservedEvent, ObserverExit, ParticipantMetadata, ParticipantRole,
+        ParticipantTimeouts, SessionObserver, SessionStatus,
+    },
+    events::{EventBus, EventReceiver, SessionEvent},
+};
+use std::collections::HashSet;
+use std::ops::ControlFlow;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+fn observer() -> (LifecycleObserver, EventReceiver) {
+    observer_with(ParticipantTimeouts::default())
+}
+
+fn observer_with(timeouts: ParticipantTimeouts) -> (LifecycleObserver, EventReceiver) {
+    let event_bus = Arc::new(EventBus::new());
+    let events = event_bus.create_session_stream("s1".to_string());
+    let observer = LifecycleObserver::new(
+        "s1".to_string(),
+        HashSet::from(["asr".to_string(), "tts".to_string()]),
+        event_bus,
+        IdentityScheme::default(),
+        timeouts,
+        50,
+    );
+    (observer, events)
+}
+
+fn published(events: &mut EventReceiver) -> Vec<&'static str> {
+    std::iter::from_fn(|| events.try_recv().ok())
+        .map(|event| event.event.event_name())
+        .collect()
+}
+
+#[test]
+fn test_session_ready_once_every_service_joined() {
+    let (mut observer, mut events) = observer();
+
+    assert!(observer.on_participant_joined("asr", "").is_continue());
+    assert_eq!(published(&mut events), ["microservice_joined"]);
+
+    // A service reconnecting is not announced again
+    assert!(observer.on_participant_joined("asr", "").is_continue());
+    assert!(published(&mut events).is_empty());
+
+    assert!(observer.on_participant_joined("tts", "").is_continue());
+    assert_eq!(
+        published(&mut events),
+        ["microservice_joined", "session_ready"]
+    );
+    assert_eq!(observer.joined_services().len(), 2);
+
+    // The manager's own observer is neither a service nor a client
+    assert!(observer
+        .on_participant_joined("session-manager-s1", "")
+        .is_continue());
+    assert!(published(&mut events).is_empty());
+    assert!(!observer.client_connected());
+}
+
+#[test]
+fn test_unexpected_service_identity_is_not_counted() {
+    let (mut observer, mut events) = observer();
+    let service_metadata = ParticipantMetadata::new(ParticipantRole::Service, "s1").to_json();
+
+    // Claims the service role with a valid-looking token but was never notified
+    assert!(observer
+        .on_participant_joined("impostor", &service_metadata)
+        .is_continue());
+    assert!(published(&mut events).is_empty());
+    assert!(observer.joined_services().is_empty());
+    assert!(observer.unexpected_services().contains("impostor"));
+
+    assert!(observer
+        .on_participant_joined("asr", &service_metadata)
+        .is_continue());
+    assert_eq!(published(&mut events), ["microservice_joined"]);
+    assert!(!observer.joined_services().contains("impostor"));
+}
+
+#[test]
+fn test_identity_verification_can_be_disabled() {
+    let (observer, mut events) = observer();
+    let mut observer = observer.with_identity_verification(false);
+    let service_metadata = ParticipantMetadata::new(ParticipantRole::Service, "s1").to_json();
+
+    assert!(observer
+        .on_participant_joined("impostor", &service_metadata)
+        .is_continue());
+    assert_eq!(published(&mut events), ["microservice_joined"]);
+    assert!(observer.unexpected_services().is_empty());
+}
+
+#[test]
+fn test_client_join_and_leave_tracked() {
+    let (mut observer, mut events) = observer();
+
+    assert!(observer
+        .on_participant_joined("client-s1", "")
+        .is_continue());
+    assert!(observer.client_connected());
+    assert_eq!(published(&mut events), ["client_joined"]);
+
+    assert!(observer.on_participant_left("client-s1", "").is_continue());
+    assert!(!observer.client_connected());
+
+    // A leaving service stays joined until it times out
+    assert!(observer.on_participant_joined("asr", "").is_continue());
+    assert!(observer.on_participant_left("asr", "").is_continue());
+    assert!(observer.joined_services().contains("asr"));
+}
+
+#[test]
+fn test_tick_terminates_after_client_timeout() {
+    let (mut observer, mut events) = observer();
+    assert!(observer
+        .on_participant_joined("client-s1", "")
+        .is_continue());
+    published(&mut events);
+
+    assert!(observer.on_tick(Instant::now()).is_continue());
+
+    let later = Instant::now() + Duration::from_secs(61);
+    assert!(observer.on_tick(later).is_break());
+    assert!(matches!(
+        events.try_recv().unwrap().event,
+        SessionEvent::ClientTimedOut { .. }
+    ));
+    assert!(matches!(
+        events.try_recv().unwrap().event,
+        SessionEvent::SessionStatusChanged {
+            status: SessionStatus::Terminating,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_client_timeout_is_configurable() {
+    let (mut observer, mut events) = observer_with(ParticipantTimeouts {
+        client_timeout_secs: 5,
+        service_timeout_secs: 600,
+    });
+    assert!(observer
+        .on_participant_joined("client-s1", "")
+        .is_continue());
+    published(&mut events);
+
+    assert!(observer
+        .on_tick(Instant::now() + Duration::from_secs(6))
+        .is_break());
+    assert_eq!(
+        published(&mut events),
+        ["client_timed_out", "session_status_changed"]
+    );
+}
+
+#[test]
+fn test_service_timeout_publishes_service_timed_out_once() {
+    let (mut observer, mut events) = observer_with(ParticipantTimeouts {
+        client_timeout_secs: 600,
+        service_timeout_secs: 10,
+    });
+    assert!(observer.on_participant_joined("asr", "").is_continue());
+    published(&mut events);
+
+    let now = Instant::now();
+    assert!(observer.on_tick(now + Duration::from_secs(5)).is_continue());
+    assert!(published(&mut events).is_empty());
+
+    assert!(observer
+        .on_tick(now + Duration::from_secs(11))
+        .is_continue());
+    match events.try_recv().unwrap().event {
+        SessionEvent::ServiceTimedOut { service_id, .. } => assert_eq!(service_id, "asr"),
+        other => panic!("unexpected event {:?}", other),
+    }
+
+    // Not reported again on later ticks
+    assert!(observer
+        .on_tick(now + Duration::from_secs(30))
+        .is_continue());
+    assert!(published(&mut events).is_empty());
+}
+
+#[test]
+fn test_tick_drops_silent_services() {
+    let (mut observer, _events) = observer();
+    assert!(observer.on_participant_joined("asr", "").is_continue());
+
+    assert!(observer
+        .on_tick(Instant::now() + Duration::from_secs(61))
+        .is_continue());
+    assert!(observer.joined_services().is_empty());
+
+    // Room activity keeps services alive
+    assert!(observer.on_participant_joined("asr", "").is_continue());
+    assert!(observer.on_activity().is_continue());
+    assert!(observer
+        .on_tick(Instant::now() + Duration::from_secs(59))
+        .is_continue());
+    assert!(observer.joined_services().contains("asr"));
+}
+
+/// Records the callbacks it receives
+#[derive(Default)]
+struct Recorder {
+    calls: Vec<String>,
+    stop_on: Option<String>,
+}
+
+impl SessionObserver for Recorder {
+    fn on_participant_joined(&mut self, identity: &str, _metadata: &str) -> ControlFlow<()> {
+        self.calls.push(format!("joined:{}", identity));
+        if self.stop_on.as_deref() == Some(identity) {
+            return ControlFlow::Break(());
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn on_participant_left(&mut self, identity: &str, _metadata: &str) -> ControlFlow<()> {
+        self.calls.push(format!("left:{}", identity));
+        ControlFlow::Continue(())
+    }
+
+    fn on_activity(&mut self) -> ControlFlow<()> {
+        self.calls.push("activity".to_string());
+        ControlFlow::Continue(())
+    }
+
+    fn on_tick(&mut self, _now: Instant) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+
+    fn on_closed(&mut self) {
+        self.calls.push("closed".to_string());
+    }
+}
+
+fn joined(identity: &str) -> ObservedEvent {
+    ObservedEvent::ParticipantJoined {
+        identity: identity.to_string(),
+        metadata: String::new(),
+    }
+}
+
+#[tokio::test]
+async fn test_driver_feeds_events_until_stream_ends() {
+    let mut recorder = Recorder::default();
+    let events = stream::iter(vec![
+        joined("asr"),
+        ObservedEvent::Activity,
+        ObservedEvent::ParticipantLeft {
+            identity: "asr".to_string(),
+            metadata: String::new(),
+        },
+    ]);
+
+    run_session_observer(&mut recorder, events, Duration::from_secs(3600)).await;
+
+    assert_eq!(
+        recorder.calls,
+        ["joined:asr", "activity", "left:asr", "closed"]
+    );
+}
+
+#[tokio::test]
+async fn test_driver_stops_when_observer_breaks() {
+    let mut recorder = Recorder {
+        stop_on: Some("tts".to_string()),
+        ..Default::default()
+    };
+    let events = stream::iter(vec![joined("asr"), joined("tts"), joined("llm")]);
+
+    run_session_observer(&mut recorder, events, Duration::from_secs(3600)).await;
+
+    assert_eq!(recorder.calls, ["joined:asr", "joined:tts"]);
+}
+
+#[test]
+fn test_room_full_published_once_per_time_limit_is_reached() {
+    let event_bus = Arc::new(EventBus::new());
+    let mut events = event_bus.create_session_stream("s1".to_string());
+    let mut observer = LifecycleObserver::new(
+        "s1".to_string(),
+        HashSet::from(["asr".to_string()]),
+        event_bus,
+        IdentityScheme::default(),
+        ParticipantTimeouts::default(),
+        3,
+    );
+
+    // The observer itself takes the first slot
+    assert!(observer.on_participant_joined("asr", "").is_continue());
+    published(&mut events);
+    assert!(observer
+        .on_participant_joined("client-s1", "")
+        .is_continue());
+    match events.try_recv().unwrap().event {
+        SessionEvent::RoomFull {
+            max_participants, ..
+        } => assert_eq!(max_participants, 3),
+        other => panic!("unexpected event {:?}", other),
+    }
+    published(&mut events);
+
+    // Not repeated while the room stays full
+    assert!(observer.on_participant_joined("asr", "").is_continue());
+    assert!(!published(&mut events).contains(&"room_full"));
+
+    assert!(observer.on_participant_left("client-s1", "").is_continue());
+    assert!(observer
+        .on_participant_joined("client-s1", "")
+        .is_continue());
+    assert!(published(&mut events).contains(&"room_full"));
+}
+
+#[test]
+fn test_disconnect_reasons_map_to_actions() {
+    for reason in [
+        DisconnectReason::RoomDeleted,
+        DisconnectReason::RoomClosed,
+        DisconnectReason::DuplicateIdentity,
+        DisconnectReason::ParticipantRemoved,
+    ] {
+        assert_eq!(
+            disconnect_action(reason),
+            DisconnectAction::Terminate,
+            "{:?}",
+            reason
+        );
+    }
+    for reason in [
+        DisconnectReason::ServerShutdown,
+        DisconnectReason::SignalClose,
+        DisconnectReason::Migration,
+        DisconnectReason::UnknownReason,
+    ] {
+        assert_eq!(
+            disconnect_action(reason),
+            DisconnectAction::Reconnect,
+            "{:?}",
+            reason
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_room_deleted_terminates_session() {
+    let (mut observer, mut events) = observer();
+    let feed = stream::iter(vec![
+        joined("asr"),
+        ObservedEvent::Disconnected {
+            reason: DisconnectReason::RoomDeleted,
+        },
+        joined("tts"),
+    ]);
+
+    let exit = run_session_observer(&mut observer, feed, Duration::from_secs(3600)).await;
+
+    assert_eq!(
+        exit,
+        ObserverExit::Disconnected(DisconnectAction::Terminate)
+    );
+    assert_eq!(
+        events.try_recv().unwrap().event.event_name(),
+        "microservice_joined"
+    );
+    match events.try_recv().unwrap().event {
+        SessionEvent::RoomDisconnected { reason, action, .. } => {
+            assert_eq!(reason, "RoomDeleted");
+            assert_eq!(action, DisconnectAction::Terminate);
+        }
+        other => panic!("unexpected event {:?}", other),
+    }
+    assert!(matches!(
+        events.try_recv().unwrap().event,
+        SessionEvent::SessionStatusChanged {
+            status: SessionStatus::Terminating,
+            ..
+        }
+    ));
+    // Nothing after the disconnect is observed
+    assert!(published(&mut events).is_empty());
+}
+
+#[tokio::test]
+async fn test_transient_disconnect_reconnects() {
+    let (mut observer, mut events) = observer();
+    let feed = stream::iter(vec![ObservedEvent::Disconnected {
+        reason: DisconnectReason::SignalClose,
+    }]);
+
+    let exit = run_session_observer(&mut observer, feed, Duration::from_secs(3600)).await;
+
+    assert_eq!(
+        exit,
+        ObserverExit::Disconnected(DisconnectAction::Reconnect)
+    );
+    match events.try_recv().unwrap().event {
+        SessionEvent::RoomDisconnected { reason, action, .. } => {
+            assert_eq!(reason, "SignalClose");
+            assert_eq!(action, DisconnectAction::Reconnect);
+        }
+        other => panic!("unexpected event {:?}", other),
+    }
+    // The session keeps going
+    assert!(published(&mut events).is_empty());
+
+    // Observing resumes on the rejoined room's events
+    let feed = stream::iter(vec![joined("asr")]);
+    let exit = run_session_observer(&mut observer, feed, Duration::from_secs(3600)).await;
+    assert_eq!(exit, ObserverExit::Closed);
+    assert_eq!(published(&mut events), ["microservice_joined"]);
+}
@@ -0,0 +1,207 @@
+use reqwest::{Client, Method, StatusCode};
+use serde_json::json;
+
+mod common;
+
+const ADMIN_TOKEN: &str = "ownership-admin";
+
+/// Send `method` to `url` with an optional bearer token and return the status
+async fn status_of(client: &Client, method: Method, url: &str, token: Option<&str>) -> StatusCode {
+    let mut request = client.request(method.clone(), url);
+    if method == Method::PATCH {
+        request = request.json(&json!({ "topic": "support" }));
+    } else if method == Method::POST {
+        request = request.json(&json!({ "user_identity": "owned-user" }));
+    }
+    if let Some(token) = token {
+        request = request.bearer_auth(token);
+    }
+    request.send().await.unwrap().status()
+}
+
+async fn create_owned_session(client: &Client, base_url: &str, token: &str) -> String {
+    let response = client
+        .post(format!("{}/api/v1/create-session", base_url))
+        .bearer_auth(token)
+        .json(&json!({ "user_identity": "owned-user", "required_services": [] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: serde_json::Value = response.json().await.unwrap();
+    body["session_id"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn test_only_owner_or_admin_can_access_session() {
+    let mut config = common::test_config(8787);
+    config.livekit.server_url = common::spawn_mock_livekit().await;
+    config.auth.admin_token = Some(ADMIN_TOKEN.to_string());
+    config
+        .auth
+        .api_tokens
+        .insert("alice-token".to_string(), "alice".to_string());
+    config
+        .auth
+        .api_tokens
+        .insert("bob-token".to_string(), "bob".to_string());
+    let (base_url, server_handle) = common::start_server(config).await;
+    let client = Client::new();
+
+    // Creating a session requires a caller token
+    let response = client
+        .post(format!("{}/api/v1/create-session", base_url))
+        .json(&json!({ "user_identity": "owned-user", "required_services": [] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let session_id = create_owned_session(&client, &base_url, "alice-token").await;
+    let session_url = format!("{}/api/v1/sessions/{}", base_url, session_id);
+    let metadata_url = format!("{}/metadata", session_url);
+    let leave_url = format!("{}/leave", session_url);
+    let events_url = format!("{}/sessions/{}/events", base_url, session_id);
+
+    // Owner access
+    let owner = Some("alice-token");
+    assert_eq!(
+        status_of(&client, Method::GET, &session_url, owner).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        status_of(&client, Method::PATCH, &metadata_url, owner).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        status_of(&client, Method::GET, &events_url, owner).await,
+        StatusCode::OK
+    );
+
+    // Other callers are denied, unauthenticated ones rejected
+    let other = Some("bob-token");
+    for (method, url) in [
+        (Method::GET, &session_url),
+        (Method::PATCH, &metadata_url),
+        (Method::DELETE, &session_url),
+        (Method::POST, &leave_url),
+        (Method::GET, &events_url),
+    ] {
+        assert_eq!(
+            status_of(&client, method.clone(), url, other).await,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            status_of(&client, method, url, None).await,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    // Listing only shows the caller's own sessions
+    let list_url = format!("{}/api/v1/sessions", base_url);
+    for (token, expected) in [
+        ("alice-token", vec![session_id.clone()]),
+        ("bob-token", vec![]),
+        (ADMIN_TOKEN, vec![session_id.clone()]),
+    ] {
+        let body: serde_json::Value = client
+            .get(&list_url)
+            .bearer_auth(token)
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        let listed: Vec<String> = body["sessions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|session| session["session_id"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(listed, expected, "listed for {}", token);
+    }
+
+    // The global streams carry every session's events and are for admins only
+    for url in [format!("{}/events", base_url), format!("{}/ws", base_url)] {
+        assert_eq!(
+            status_of(&client, Method::GET, &url, owner).await,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+    assert_eq!(
+        status_of(
+            &client,
+            Method::GET,
+            &format!("{}/events", base_url),
+            Some(ADMIN_TOKEN)
+        )
+        .await,
+        StatusCode::OK
+    );
+
+    // Admin override
+    assert_eq!(
+        status_of(&client, Method::GET, &session_url, Some(ADMIN_TOKEN)).await,
+        StatusCode::OK
+    );
+    let response = client
+        .delete(&session_url)
+        .bearer_auth(ADMIN_TOKEN)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["status"], "Terminated");
+
+    // Owners can delete their own sessions
+    let session_id = create_owned_session(&client, &base_url, "alice-token").await;
+    assert_eq!(
+        status_of(
+            &client,
+            Method::DELETE,
+            &format!("{}/api/v1/sessions/{}", base_url, session_id),
+            owner
+        )
+        .await,
+        StatusCode::OK
+    );
+
+    server_handle.abort();
+}
+
+#[tokio::test]
+async fn test_sessions_are_open_without_api_tokens() {
+    let mut config = common::test_config(8788);
+    config.livekit.server_url = common::spawn_mock_livekit().await;
+    let (base_url, server_handle) = common::start_server(config).await;
+    let client = Client::new();
+
+    let created = common::create_session(
+        &client,
+        &base_url,
+        json!({ "user_identity": "open-user", "required_services": [] }),
+    )
+    .await;
+    let session_url = format!(
+        "{}/api/v1/sessions/{}",
+        base_url,
+        created["session_id"].as_str().unwrap()
+    );
+
+    assert_eq!(
+        status_of(&client, Method::GET, &session_url, None).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        status_of(&client, Method::DELETE, &session_url, None).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        status_of(&client, Method::DELETE, &session_url, None).await,
+        StatusCode::OK
+    );
+
+    server_handle.abort();
+}
@@ -0,0 +1,76 @@
+use futures::StreamExt;
+use reqwest::Client;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+mod common;
+
+/// Read the first SSE event of `response` as its name and JSON data
+async fn first_event(response: reqwest::Response) -> (String, Value) {
+    let mut body = response.bytes_stream();
+    let mut received = String::new();
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+    while !received.contains("\n\n") {
+        let chunk = tokio::time::timeout_at(deadline, body.next())
+            .await
+            .expect("no event delivered")
+            .expect("stream ended")
+            .expect("stream error");
+        received.push_str(&String::from_utf8_lossy(&chunk));
+    }
+
+    let event = received.split("\n\n").next().unwrap();
+    let field = |name: &str| {
+        event
+            .lines()
+            .find_map(|line| line.strip_prefix(name))
+            .map(str::trim)
+            .unwrap_or_default()
+            .to_string()
+    };
+    let data = serde_json::from_str(&field("data:")).expect("snapshot data is JSON");
+    (field("event:"), data)
+}
+
+#[tokio::test]
+async fn test_first_sse_event_is_session_snapshot() {
+    let mut config = common::test_config(8800);
+    config.livekit.server_url = common::spawn_mock_livekit().await;
+    let (base_url, server_handle) = common::start_server(config).await;
+    let client = Client::new();
+
+    let session = common::create_session(
+        &client,
+        &base_url,
+        json!({
+            "user_identity": "snapshot-user",
+            "required_services": [],
+            "metadata": { "robot": "arm-7" }
+        }),
+    )
+    .await;
+    let session_id = session["session_id"].as_str().unwrap();
+
+    let response = client
+        .get(format!("{}/sessions/{}/events", base_url, session_id))
+        .send()
+        .await
+        .expect("Subscribe failed");
+    assert!(response.status().is_success());
+    let (event, snapshot) = first_event(response).await;
+
+    assert_eq!(event, "session_snapshot");
+    let status: Value = client
+        .get(format!("{}/api/v1/sessions/{}", base_url, session_id))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(snapshot, status);
+    assert_eq!(snapshot["status"], session["status"]);
+    assert_eq!(snapshot["metadata"]["robot"], "arm-7");
+
+    server_handle.abort();
+}
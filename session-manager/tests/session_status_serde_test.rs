@@ -0,0 +1,59 @@
+use session_manager::{
+    domain::{Session, SessionStatus},
+    storage::{jsonl, memory::MemoryStorage, SessionStorage},
+};
+use std::collections::HashMap;
+
+const ALL_STATUSES: [(SessionStatus, &str); 6] = [
+    (SessionStatus::Creating, "Creating"),
+    (SessionStatus::WaitingForServices, "WaitingForServices"),
+    (SessionStatus::Ready, "Ready"),
+    (SessionStatus::Active, "Active"),
+    (SessionStatus::Terminating, "Terminating"),
+    (SessionStatus::Terminated, "Terminated"),
+];
+
+#[test]
+fn test_every_status_round_trips_with_stable_name() {
+    for (status, name) in ALL_STATUSES {
+        let json = serde_json::to_string(&status).unwrap();
+        assert_eq!(json, format!("\"{}\"", name));
+        assert_eq!(
+            serde_json::from_str::<SessionStatus>(&json).unwrap(),
+            status
+        );
+    }
+}
+
+#[test]
+fn test_unknown_status_falls_back_to_terminated() {
+    assert_eq!(
+        serde_json::from_str::<SessionStatus>("\"Migrating\"").unwrap(),
+        SessionStatus::Terminated
+    );
+}
+
+#[tokio::test]
+async fn test_stored_session_with_unknown_status_still_imports() {
+    let session = Session::new(
+        "session-future".to_string(),
+        "room-future".to_string(),
+        HashMap::new(),
+    );
+    let mut stored = serde_json::to_value(&session).unwrap();
+    stored["status"] = "Hibernating".into();
+    let line = format!("{}\n", stored);
+
+    let storage = MemoryStorage::new();
+    let imported = jsonl::import_sessions(&storage, line.as_bytes())
+        .await
+        .expect("import");
+    assert_eq!(imported, 1);
+
+    let restored = storage
+        .get_session("session-future")
+        .await
+        .unwrap()
+        .expect("session restored");
+    assert_eq!(restored.status, SessionStatus::Terminated);
+}
@@ -0,0 +1,176 @@
+use chrono::{Duration, Utc};
+use session_manager::{
+    config::SweeperConfig,
+    domain::{Session, SessionStatus},
+    services::{SessionService, SessionSweeper},
+    storage::{memory::MemoryStorage, SessionStorage},
+};
+use std::{collections::HashMap, sync::Arc};
+
+mod common;
+
+async fn seed(storage: &MemoryStorage, id: &str, status: SessionStatus, age: Duration) {
+    let mut session = Session::new(id.to_string(), format!("room-{}", id), HashMap::new());
+    session.status = status;
+    session.created_at = Utc::now() - age;
+    storage.save_session(&session).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_sweep_removes_only_old_waiting_sessions() {
+    let storage = Arc::new(MemoryStorage::new());
+    seed(
+        &storage,
+        "abandoned",
+        SessionStatus::WaitingForServices,
+        Duration::minutes(10),
+    )
+    .await;
+    seed(
+        &storage,
+        "stuck-creating",
+        SessionStatus::Creating,
+        Duration::minutes(10),
+    )
+    .await;
+    seed(
+        &storage,
+        "fresh",
+        SessionStatus::WaitingForServices,
+        Duration::seconds(5),
+    )
+    .await;
+    seed(
+        &storage,
+        "active",
+        SessionStatus::Active,
+        Duration::minutes(10),
+    )
+    .await;
+    let service = common::session_service(storage.clone());
+
+    let mut swept = service
+        .sweep_abandoned_sessions(std::time::Duration::from_secs(60))
+        .await
+        .unwrap();
+    swept.sort();
+    assert_eq!(swept, vec!["abandoned", "stuck-creating"]);
+
+    assert!(storage.get_session("abandoned").await.unwrap().is_none());
+    assert!(storage
+        .get_session("stuck-creating")
+        .await
+        .unwrap()
+        .is_none());
+    assert!(storage.get_session("fresh").await.unwrap().is_some());
+    assert!(storage.get_session("active").await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn test_sweep_with_unrepresentable_max_age_is_a_no_op() {
+    let storage = Arc::new(MemoryStorage::new());
+    seed(
+        &storage,
+        "abandoned",
+        SessionStatus::WaitingForServices,
+        Duration::days(365),
+    )
+    .await;
+    let service = common::session_service(storage.clone());
+
+    let swept = service
+        .sweep_abandoned_sessions(std::time::Duration::from_secs(u64::MAX))
+        .await
+        .unwrap();
+    assert!(swept.is_empty());
+    assert!(storage.get_session("abandoned").await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn test_sweeper_task_removes_abandoned_session() {
+    let storage = Arc::new(MemoryStorage::new());
+    seed(
+        &storage,
+        "abandoned",
+        SessionStatus::WaitingForServices,
+        Duration::seconds(2),
+    )
+    .await;
+    let service: Arc<dyn SessionService> = Arc::new(common::session_service(storage.clone()));
+
+    let sweeper = Arc::new(SessionSweeper::new(
+        service,
+        SweeperConfig {
+            enabled: true,
+            interval_secs: 1,
+            max_age_secs: 1,
+            max_lifetime_secs: 0,
+            terminated_grace_secs: 300,
+        },
+    ));
+    let handle = sweeper.spawn_sweep_task();
+
+    // The first sweep runs immediately
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+    assert!(storage.get_session("abandoned").await.unwrap().is_none());
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn test_terminated_sessions_are_removed_after_the_grace_period() {
+    let storage = Arc::new(MemoryStorage::new());
+    for (id, status, ended) in [
+        (
+            "ended-long-ago",
+            SessionStatus::Terminated,
+            Duration::minutes(10),
+        ),
+        (
+            "just-ended",
+            SessionStatus::Terminated,
+            Duration::seconds(5),
+        ),
+        (
+            "terminating",
+            SessionStatus::Terminating,
+            Duration::minutes(10),
+        ),
+        ("active", SessionStatus::Active, Duration::minutes(10)),
+    ] {
+        seed(&storage, id, status, ended).await;
+        let mut session = storage.get_session(id).await.unwrap().unwrap();
+        session.updated_at = Utc::now() - ended;
+        storage.update_session(&session).await.unwrap();
+    }
+    let service: Arc<dyn SessionService> = Arc::new(common::session_service(storage.clone()));
+
+    let sweeper = SessionSweeper::new(
+        service,
+        SweeperConfig {
+            enabled: true,
+            interval_secs: 1,
+            max_age_secs: 600,
+            max_lifetime_secs: 0,
+            terminated_grace_secs: 60,
+        },
+    );
+    assert_eq!(sweeper.sweep().await, vec!["ended-long-ago"]);
+
+    assert!(storage
+        .get_session("ended-long-ago")
+        .await
+        .unwrap()
+        .is_none());
+    for id in ["just-ended", "terminating", "active"] {
+        assert!(storage.get_session(id).await.unwrap().is_some(), "{}", id);
+    }
+
+    // A grace period beyond the calendar keeps everything
+    let service = common::session_service(storage.clone());
+    let purged = service
+        .purge_terminated_sessions(std::time::Duration::from_secs(u64::MAX))
+        .await
+        .unwrap();
+    assert!(purged.is_empty());
+}
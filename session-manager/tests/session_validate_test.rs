@@ -0,0 +1,108 @@
+use axum::{extract::Request, http::StatusCode, Router};
+use microservice_sdk::{MicroserviceConfig, SessionManagerClient};
+use reqwest::Client;
+use serde_json::json;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+use tokio::net::TcpListener;
+
+mod common;
+
+/// Stand-in LiveKit that counts every room API call
+async fn spawn_counting_livekit() -> (String, Arc<AtomicUsize>) {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let app = Router::new().fallback(move |_request: Request| {
+        let counter = counter.clone();
+        async move {
+            counter.fetch_add(1, Ordering::SeqCst);
+            StatusCode::OK
+        }
+    });
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    (format!("ws://{}", addr), calls)
+}
+
+async fn validate(client: &Client, base_url: &str, body: serde_json::Value) -> reqwest::Response {
+    client
+        .post(format!("{}/api/v1/sessions/validate", base_url))
+        .json(&body)
+        .send()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_dry_run_reports_missing_services_without_creating_room() {
+    let (livekit_url, room_calls) = spawn_counting_livekit().await;
+    let mut config = common::test_config(8793);
+    config.livekit.server_url = livekit_url;
+    let (base_url, server_handle) = common::start_server(config).await;
+
+    // Registration only records the endpoint, nothing is notified during a dry run
+    SessionManagerClient::new(MicroserviceConfig::new(
+        base_url.clone(),
+        "asr-service".to_string(),
+        "http://127.0.0.1:9".to_string(),
+    ))
+    .expect("SDK client")
+    .register()
+    .await
+    .expect("registration");
+
+    let client = Client::new();
+    let response = validate(
+        &client,
+        &base_url,
+        json!({
+            "user_identity": "dry-run-user",
+            "required_services": ["asr-service", "tts-service"]
+        }),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(
+        body,
+        json!({
+            "valid": false,
+            "available_services": ["asr-service"],
+            "missing_services": ["tts-service"]
+        })
+    );
+
+    let body: serde_json::Value = validate(
+        &client,
+        &base_url,
+        json!({ "user_identity": "dry-run-user", "required_services": ["asr-service"] }),
+    )
+    .await
+    .json()
+    .await
+    .unwrap();
+    assert_eq!(body["valid"], true);
+
+    // Input validation still applies
+    let response = validate(&client, &base_url, json!({ "user_identity": "bad user" })).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // Nothing was created
+    assert_eq!(room_calls.load(Ordering::SeqCst), 0);
+    let sessions: serde_json::Value = client
+        .get(format!("{}/api/v1/sessions", base_url))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(sessions["sessions"], json!([]));
+
+    server_handle.abort();
+}
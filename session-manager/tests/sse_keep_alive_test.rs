@@ -0,0 +1,61 @@
+use futures::StreamExt;
+use reqwest::Client;
+use session_manager::{api::streams::sse_keep_alive, config::StreamConfig, server::Server};
+use std::time::Duration;
+
+mod common;
+
+const ADMIN_TOKEN: &str = "keep-alive-admin";
+
+fn stream_config(secs: u64, text: &str) -> StreamConfig {
+    StreamConfig {
+        sse_keep_alive_secs: secs,
+        sse_keep_alive_text: text.to_string(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_keep_alive_uses_configured_interval() {
+    let keep_alive = format!("{:?}", sse_keep_alive(&stream_config(5, "ping")));
+    assert!(keep_alive.contains("max_interval: 5s"), "{}", keep_alive);
+
+    let default = format!("{:?}", sse_keep_alive(&StreamConfig::default()));
+    assert!(default.contains("max_interval: 15s"), "{}", default);
+}
+
+#[tokio::test]
+async fn test_invalid_keep_alive_config_is_rejected() {
+    assert!(stream_config(0, "ping").validate().is_err());
+    assert!(stream_config(5, "line\nbreak").validate().is_err());
+    assert!(StreamConfig::default().validate().is_ok());
+
+    let mut config = common::test_config(0);
+    config.streams = stream_config(0, "ping");
+    assert!(Server::new(config).await.is_err());
+}
+
+#[tokio::test]
+async fn test_sse_stream_sends_configured_keep_alive() {
+    let mut config = common::test_config(8784);
+    config.streams = stream_config(1, "still-here");
+    config.auth.admin_token = Some(ADMIN_TOKEN.to_string());
+    let (base_url, server_handle) = common::start_server(config).await;
+
+    let response = Client::new()
+        .get(format!("{}/events", base_url))
+        .bearer_auth(ADMIN_TOKEN)
+        .send()
+        .await
+        .unwrap();
+    let mut body = response.bytes_stream();
+
+    let chunk = tokio::time::timeout(Duration::from_secs(3), body.next())
+        .await
+        .expect("keep-alive within the configured interval")
+        .unwrap()
+        .unwrap();
+    assert_eq!(&chunk[..], b": still-here\n\n");
+
+    server_handle.abort();
+}
@@ -0,0 +1,88 @@
+use futures::StreamExt;
+use session_manager::{
+    api::streams::{coalesce_lag, LagPolicy, StreamItem},
+    events::{EventBus, SessionEvent},
+    server::Server,
+};
+use std::time::Duration;
+
+mod common;
+
+fn publish_burst(event_bus: &EventBus, count: usize) {
+    for i in 0..count {
+        event_bus.publish_to_session(
+            "s1",
+            SessionEvent::Error {
+                session_id: "s1".to_string(),
+                message: format!("event-{}", i),
+            },
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_repeated_lags_are_coalesced_into_one_notice() {
+    let event_bus = EventBus::with_capacity(16, 4);
+    let receiver = event_bus.create_session_stream("s1".to_string());
+    let mut events = Box::pin(coalesce_lag(
+        receiver,
+        event_bus.clone(),
+        LagPolicy {
+            coalesce_window: Duration::from_millis(300),
+            max_skipped: 0,
+        },
+    ));
+
+    // A slow consumer reads one event per burst, overflowing the channel every time
+    for _ in 0..5 {
+        publish_burst(&event_bus, 10);
+        assert!(matches!(events.next().await, Some(StreamItem::Event(_))));
+    }
+
+    tokio::time::sleep(Duration::from_millis(400)).await;
+    match events.next().await {
+        Some(StreamItem::Lagged { skipped, lags }) => {
+            assert_eq!(lags, 5);
+            assert_eq!(skipped, event_bus.dropped_events());
+        }
+        other => panic!("expected a lag summary, got {:?}", other),
+    }
+
+    // The rest of the buffered events follow without further notices
+    let mut remaining = 0;
+    while let Ok(Some(item)) = tokio::time::timeout(Duration::from_millis(100), events.next()).await
+    {
+        assert!(matches!(item, StreamItem::Event(_)), "{:?}", item);
+        remaining += 1;
+    }
+    assert_eq!(remaining, 3);
+}
+
+#[tokio::test]
+async fn test_subscriber_is_disconnected_beyond_lag_threshold() {
+    let event_bus = EventBus::with_capacity(16, 4);
+    let receiver = event_bus.create_session_stream("s1".to_string());
+    let mut events = Box::pin(coalesce_lag(
+        receiver,
+        event_bus.clone(),
+        LagPolicy {
+            coalesce_window: Duration::from_secs(1),
+            max_skipped: 5,
+        },
+    ));
+
+    publish_burst(&event_bus, 10);
+    assert!(matches!(
+        events.next().await,
+        Some(StreamItem::Disconnected { skipped: 6 })
+    ));
+    assert!(events.next().await.is_none());
+}
+
+#[tokio::test]
+async fn test_server_rejects_zero_channel_capacity() {
+    let mut config = common::test_config(0);
+    config.streams.session_channel_capacity = 0;
+
+    assert!(Server::new(config).await.is_err());
+}
@@ -0,0 +1,73 @@
+use futures::StreamExt;
+use microservice_sdk::SessionClient;
+use reqwest::Client;
+use serde_json::json;
+use session_manager::server::Server;
+use std::time::Duration;
+use tokio::sync::oneshot;
+
+mod common;
+
+const ADMIN_TOKEN: &str = "shutdown-admin";
+
+#[tokio::test]
+async fn test_sse_stream_ends_on_server_shutdown() {
+    let mut config = common::test_config(8785);
+    config.auth.admin_token = Some(ADMIN_TOKEN.to_string());
+    config.livekit.server_url = common::spawn_mock_livekit().await;
+    let base_url = format!("http://{}:{}", config.server.host, config.server.port);
+    let server = Server::new(config).await.expect("Failed to create server");
+    let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+    let server_handle = tokio::spawn(server.run_until(async {
+        let _ = shutdown_rx.await;
+    }));
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    let response = Client::new()
+        .get(format!("{}/events", base_url))
+        .bearer_auth(ADMIN_TOKEN)
+        .send()
+        .await
+        .unwrap();
+    let mut body = response.bytes_stream();
+
+    // SDK subscribers see the close event as the end of the stream, not as an error
+    let created = common::create_session(
+        &Client::new(),
+        &base_url,
+        json!({ "user_identity": "shutdown-user", "observe": false }),
+    )
+    .await;
+    let session_id = created["session_id"].as_str().unwrap().to_string();
+    let events = SessionClient::new(base_url.clone(), 30)
+        .expect("session client")
+        .subscribe_events(&session_id)
+        .expect("subscribe");
+    let subscriber = tokio::spawn(events.collect::<Vec<_>>());
+    tokio::time::sleep(Duration::from_millis(300)).await;
+
+    shutdown_tx.send(()).unwrap();
+
+    // The stream ends promptly after a final close event instead of waiting for the client
+    let received = tokio::time::timeout(Duration::from_secs(5), async {
+        let mut received = Vec::new();
+        while let Some(chunk) = body.next().await {
+            received.extend_from_slice(&chunk.unwrap());
+        }
+        String::from_utf8(received).unwrap()
+    })
+    .await
+    .expect("SSE stream should end on shutdown");
+    assert!(received.contains("event: close"), "{}", received);
+
+    let events = tokio::time::timeout(Duration::from_secs(5), subscriber)
+        .await
+        .expect("SDK event stream should end on shutdown")
+        .unwrap();
+    assert!(events.iter().all(|event| event.is_ok()), "{:?}", events);
+
+    let result = tokio::time::timeout(Duration::from_secs(5), server_handle)
+        .await
+        .expect("server should finish shutting down");
+    assert!(result.unwrap().is_ok());
+}
@@ -0,0 +1,117 @@
+use session_manager::{
+    domain::{MicroserviceInfo, Session, SessionStatus},
+    SessionManagerError,
+};
+use std::collections::HashMap;
+
+const LIFECYCLE: [SessionStatus; 6] = [
+    SessionStatus::Creating,
+    SessionStatus::WaitingForServices,
+    SessionStatus::Ready,
+    SessionStatus::Active,
+    SessionStatus::Terminating,
+    SessionStatus::Terminated,
+];
+
+fn session_in(status: &SessionStatus) -> Session {
+    let mut session = Session::new(
+        "session-1".to_string(),
+        "room-1".to_string(),
+        HashMap::new(),
+    );
+    session.status = status.clone();
+    session
+}
+
+#[test]
+fn test_full_lifecycle_is_legal() {
+    let mut session = session_in(&SessionStatus::Creating);
+    for status in &LIFECYCLE[1..] {
+        session.transition_to(status.clone()).unwrap();
+        assert_eq!(&session.status, status);
+    }
+}
+
+#[test]
+fn test_steps_may_be_skipped_forward() {
+    for (from, to) in [
+        // Sessions without services are ready right away
+        (SessionStatus::Creating, SessionStatus::Ready),
+        // Any live session can be terminated directly
+        (SessionStatus::Creating, SessionStatus::Terminating),
+        (SessionStatus::WaitingForServices, SessionStatus::Terminated),
+        (SessionStatus::Ready, SessionStatus::Terminating),
+        (SessionStatus::Active, SessionStatus::Terminated),
+    ] {
+        let mut session = session_in(&from);
+        let before = session.updated_at;
+        session.transition_to(to.clone()).unwrap();
+        assert_eq!(session.status, to);
+        assert!(session.updated_at >= before);
+    }
+}
+
+#[test]
+fn test_staying_in_a_status_is_allowed() {
+    for status in LIFECYCLE {
+        let mut session = session_in(&status);
+        session.transition_to(status.clone()).unwrap();
+        assert_eq!(session.status, status);
+    }
+}
+
+#[test]
+fn test_backward_transitions_are_rejected() {
+    for (i, from) in LIFECYCLE.iter().enumerate() {
+        for to in &LIFECYCLE[..i] {
+            let mut session = session_in(from);
+            let error = session.transition_to(to.clone()).unwrap_err();
+            assert!(
+                matches!(
+                    &error,
+                    SessionManagerError::InvalidStatusTransition { session_id, from: f, to: t }
+                        if session_id == "session-1" && f == from && t == to
+                ),
+                "{}",
+                error
+            );
+            // The session is left untouched
+            assert_eq!(&session.status, from);
+        }
+    }
+}
+
+#[test]
+fn test_terminated_is_final() {
+    for to in &LIFECYCLE[..5] {
+        assert!(!SessionStatus::Terminated.can_transition_to(to), "{:?}", to);
+    }
+
+    // The lenient helper ignores the change instead of reviving the session
+    let mut session = session_in(&SessionStatus::Terminated);
+    session.update_status(SessionStatus::Active);
+    assert_eq!(session.status, SessionStatus::Terminated);
+}
+
+#[test]
+fn test_late_readiness_does_not_revive_a_terminating_session() {
+    let mut session = session_in(&SessionStatus::Terminating);
+    session.add_microservice(MicroserviceInfo::new(
+        "asr-service".to_string(),
+        "http://asr:9000".to_string(),
+        HashMap::new(),
+    ));
+
+    assert!(session.mark_service_ready("asr-service"));
+    assert_eq!(session.status, SessionStatus::Terminating);
+}
+
+#[test]
+fn test_invalid_transition_error_message() {
+    let mut session = session_in(&SessionStatus::Terminated);
+    let error = session.transition_to(SessionStatus::Active).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Session session-1 cannot move from Terminated to Active"
+    );
+}
@@ -0,0 +1,90 @@
+use axum::{extract::Request, http::StatusCode, Router};
+use reqwest::Client;
+use serde_json::json;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+use tokio::net::TcpListener;
+
+mod common;
+
+const ADMIN_TOKEN: &str = "drain-admin";
+
+/// Stand-in LiveKit that answers every room API call and counts `DeleteRoom` calls
+async fn spawn_counting_livekit() -> (String, Arc<AtomicUsize>) {
+    let deleted = Arc::new(AtomicUsize::new(0));
+    let counter = deleted.clone();
+    let app = Router::new().fallback(move |request: Request| {
+        let counter = counter.clone();
+        async move {
+            if request.uri().path().ends_with("DeleteRoom") {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }
+            StatusCode::OK
+        }
+    });
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    (format!("ws://{}", addr), deleted)
+}
+
+async fn terminate_all(client: &Client, base_url: &str, token: &str) -> reqwest::Response {
+    client
+        .post(format!("{}/api/v1/admin/terminate-all", base_url))
+        .bearer_auth(token)
+        .send()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_terminate_all_ends_every_session() {
+    let (livekit_url, deleted_rooms) = spawn_counting_livekit().await;
+    let mut config = common::test_config(8790);
+    config.livekit.server_url = livekit_url;
+    config.auth.admin_token = Some(ADMIN_TOKEN.to_string());
+    let (base_url, server_handle) = common::start_server(config).await;
+
+    let client = Client::new();
+    let mut session_ids = Vec::new();
+    for i in 0..3 {
+        let created = common::create_session(
+            &client,
+            &base_url,
+            json!({ "user_identity": format!("drain-user-{}", i), "observe": false }),
+        )
+        .await;
+        session_ids.push(created["session_id"].as_str().unwrap().to_string());
+    }
+
+    // Only administrators may drain the server
+    let response = terminate_all(&client, &base_url, "not-the-admin").await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = terminate_all(&client, &base_url, ADMIN_TOKEN).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["terminated"], 3);
+    assert_eq!(deleted_rooms.load(Ordering::SeqCst), 3);
+
+    for session_id in &session_ids {
+        let response = client
+            .get(format!("{}/api/v1/sessions/{}", base_url, session_id))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    // Nothing left to terminate on a repeated call
+    let response = terminate_all(&client, &base_url, ADMIN_TOKEN).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["terminated"], 0);
+    assert_eq!(deleted_rooms.load(Ordering::SeqCst), 3);
+
+    server_handle.abort();
+}
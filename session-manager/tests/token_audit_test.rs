@@ -0,0 +1,65 @@
+use session_manager::domain::{
+    ParticipantRole, Session, TokenAudit, TokenAuditRecord, TokenAuditSink,
+};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+mod common;
+
+#[derive(Default)]
+struct RecordingSink {
+    records: Mutex<Vec<TokenAuditRecord>>,
+}
+
+impl TokenAuditSink for RecordingSink {
+    fn record(&self, record: &TokenAuditRecord) {
+        self.records.lock().unwrap().push(record.clone());
+    }
+}
+
+#[tokio::test]
+async fn test_every_minted_token_is_audited_without_the_token() {
+    let sink = Arc::new(RecordingSink::default());
+    let mut config = common::test_config(0).livekit;
+    config.token_audit = TokenAudit::new(sink.clone());
+    let session = Session::new(
+        "session-audit".to_string(),
+        "room-audit".to_string(),
+        HashMap::new(),
+    );
+
+    let client_token = session.generate_client_token(&config).await.unwrap();
+    let service_token = session
+        .generate_microservice_token("asr-service", &config)
+        .await
+        .unwrap();
+
+    let records = sink.records.lock().unwrap();
+    assert_eq!(records.len(), 2);
+
+    assert_eq!(records[0].session_id, "session-audit");
+    assert_eq!(records[0].identity, "client-session-audit");
+    assert_eq!(records[0].role, ParticipantRole::Client);
+    assert_eq!(records[0].room, "room-audit");
+    // LiveKit's grant defaults include data publishing
+    assert_eq!(
+        records[0].grants,
+        [
+            "room_join",
+            "can_publish",
+            "can_subscribe",
+            "can_publish_data"
+        ]
+    );
+    assert_eq!(records[0].ttl_secs, 6 * 3600);
+
+    assert_eq!(records[1].identity, "asr-service");
+    assert_eq!(records[1].role, ParticipantRole::Service);
+    assert!(records[1].grants.contains(&"can_publish_data"));
+
+    for record in records.iter() {
+        let logged = serde_json::to_string(record).unwrap();
+        assert!(!logged.contains(&client_token));
+        assert!(!logged.contains(&service_token));
+    }
+}
@@ -0,0 +1,130 @@
+use livekit_api::access_token::TokenVerifier;
+use session_manager::domain::{IdentityScheme, ParticipantMetadata, ParticipantRole, Session};
+use std::collections::{HashMap, HashSet};
+
+mod common;
+
+fn session() -> Session {
+    Session::new(
+        "session-tokens".to_string(),
+        "room-tokens".to_string(),
+        HashMap::new(),
+    )
+}
+
+fn decoded_metadata(token: &str) -> serde_json::Value {
+    let config = common::test_config(0).livekit;
+    let claims = TokenVerifier::with_api_key(&config.api_key, &config.api_secret)
+        .verify(token)
+        .expect("token verifies");
+    serde_json::from_str(&claims.metadata).expect("metadata is JSON")
+}
+
+#[tokio::test]
+async fn test_client_token_carries_role_metadata() {
+    let config = common::test_config(0).livekit;
+    let token = session().generate_client_token(&config).await.unwrap();
+
+    assert_eq!(
+        decoded_metadata(&token),
+        serde_json::json!({ "role": "client", "session_id": "session-tokens" })
+    );
+}
+
+#[tokio::test]
+async fn test_client_token_uses_configured_identity_prefix() {
+    let mut config = common::test_config(0).livekit;
+    config.identity.client_prefix = "user-eu-".to_string();
+    let token = session().generate_client_token(&config).await.unwrap();
+
+    let claims = TokenVerifier::with_api_key(&config.api_key, &config.api_secret)
+        .verify(&token)
+        .expect("token verifies");
+    assert_eq!(claims.sub, "user-eu-session-tokens");
+}
+
+#[tokio::test]
+async fn test_microservice_token_carries_role_metadata() {
+    let config = common::test_config(0).livekit;
+    let token = session()
+        .generate_microservice_token("gpu-worker-7", &config)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        decoded_metadata(&token),
+        serde_json::json!({ "role": "service", "session_id": "session-tokens" })
+    );
+}
+
+#[test]
+fn test_metadata_role_takes_precedence_over_identity() {
+    let no_services = HashSet::new();
+    let identities = IdentityScheme::default();
+    let metadata = ParticipantMetadata::new(ParticipantRole::Service, "s1").to_json();
+
+    // Not a registered service id, but the token says it is one
+    assert_eq!(
+        ParticipantRole::from_participant(
+            "gpu-worker-7",
+            &metadata,
+            "s1",
+            &no_services,
+            &identities
+        ),
+        ParticipantRole::Service
+    );
+
+    // Metadata minted for another session is ignored
+    assert_eq!(
+        ParticipantRole::from_participant(
+            "gpu-worker-7",
+            &metadata,
+            "s2",
+            &no_services,
+            &identities
+        ),
+        ParticipantRole::Client
+    );
+
+    // No metadata falls back to identity classification
+    assert_eq!(
+        ParticipantRole::from_participant(
+            "session-manager-s1",
+            "",
+            "s1",
+            &no_services,
+            &identities
+        ),
+        ParticipantRole::Manager
+    );
+}
+
+#[tokio::test]
+async fn test_observer_token_is_hidden() {
+    let config = common::test_config(0).livekit;
+    let token = session().generate_room_token(&config).await.unwrap();
+
+    let claims = TokenVerifier::with_api_key(&config.api_key, &config.api_secret)
+        .verify(&token)
+        .expect("token verifies");
+    assert!(claims.video.hidden);
+    assert!(claims.video.room_join);
+    assert_eq!(claims.sub, "session-manager-session-tokens");
+    assert_eq!(
+        decoded_metadata(&token),
+        serde_json::json!({ "role": "manager", "session_id": "session-tokens" })
+    );
+}
+
+#[tokio::test]
+async fn test_observer_can_be_made_visible() {
+    let mut config = common::test_config(0).livekit;
+    config.hidden_observer = false;
+    let token = session().generate_room_token(&config).await.unwrap();
+
+    let claims = TokenVerifier::with_api_key(&config.api_key, &config.api_secret)
+        .verify(&token)
+        .expect("token verifies");
+    assert!(!claims.video.hidden);
+}
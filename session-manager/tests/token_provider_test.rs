@@ -0,0 +1,121 @@
+use async_trait::async_trait;
+use axum::{routing::post, Json, Router};
+use reqwest::Client;
+use serde_json::{json, Value};
+use session_manager::domain::{ParticipantRole, TokenIssuer, TokenProvider, TokenRequest};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::sleep;
+
+mod common;
+
+/// Hands out canned tokens and records what it was asked for
+#[derive(Default)]
+struct CannedTokens {
+    requests: Mutex<Vec<TokenRequest>>,
+}
+
+impl CannedTokens {
+    fn issue(&self, kind: &str, request: &TokenRequest) -> anyhow::Result<String> {
+        self.requests.lock().unwrap().push(request.clone());
+        Ok(format!("canned-{}-{}", kind, request.identity))
+    }
+}
+
+#[async_trait]
+impl TokenProvider for CannedTokens {
+    async fn client_token(&self, request: &TokenRequest) -> anyhow::Result<String> {
+        self.issue("client", request)
+    }
+
+    async fn service_token(&self, request: &TokenRequest) -> anyhow::Result<String> {
+        self.issue("service", request)
+    }
+
+    async fn manager_token(&self, request: &TokenRequest) -> anyhow::Result<String> {
+        self.issue("manager", request)
+    }
+}
+
+/// Start a microservice endpoint that acknowledges joins and keeps the last join request
+async fn spawn_join_recorder() -> (String, Arc<Mutex<Option<Value>>>) {
+    let joined = Arc::new(Mutex::new(None));
+    let recorded = joined.clone();
+    let app = Router::new().route(
+        "/join-room",
+        post(move |Json(request): Json<Value>| {
+            let recorded = recorded.clone();
+            async move {
+                *recorded.lock().unwrap() = Some(request);
+                Json(json!({ "success": true }))
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    (format!("http://{}", addr), joined)
+}
+
+#[tokio::test]
+async fn test_session_creation_uses_injected_token_provider() {
+    let provider = Arc::new(CannedTokens::default());
+    let mut config = common::test_config(8798);
+    config.livekit.server_url = common::spawn_mock_livekit().await;
+    config.livekit.token_provider = TokenIssuer::new(provider.clone());
+    let (base_url, server_handle) = common::start_server(config).await;
+    let (endpoint, joined) = spawn_join_recorder().await;
+
+    let client = Client::new();
+    let response = client
+        .post(format!("{}/api/v1/microservices/register", base_url))
+        .json(&json!({ "service_id": "canned-asr", "endpoint": endpoint }))
+        .send()
+        .await
+        .unwrap();
+    assert!(response.status().is_success());
+
+    let created = common::create_session(
+        &client,
+        &base_url,
+        json!({
+            "user_identity": "canned-user",
+            "required_services": ["canned-asr"],
+            "observe": false
+        }),
+    )
+    .await;
+    let session_id = created["session_id"].as_str().unwrap();
+    assert_eq!(
+        created["access_token"],
+        format!("canned-client-client-{}", session_id)
+    );
+
+    // Join notifications are sent in the background
+    for _ in 0..50 {
+        if joined.lock().unwrap().is_some() {
+            break;
+        }
+        sleep(Duration::from_millis(100)).await;
+    }
+    let join_request = joined
+        .lock()
+        .unwrap()
+        .clone()
+        .expect("service was notified");
+    assert_eq!(join_request["access_token"], "canned-service-canned-asr");
+
+    let requests = provider.requests.lock().unwrap();
+    let service = requests
+        .iter()
+        .find(|request| request.role == ParticipantRole::Service)
+        .expect("service token requested");
+    assert_eq!(service.session_id, session_id);
+    assert_eq!(service.grants.room, created["room_name"].as_str().unwrap());
+    assert!(service.grants.can_publish_data);
+    assert!(service.metadata.contains("\"role\":\"service\""));
+
+    server_handle.abort();
+}
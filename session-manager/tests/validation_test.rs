@@ -0,0 +1,107 @@
+use session_manager::{
+    domain::IdentityScheme,
+    services::{session_service::CreateSessionRequest, SessionService},
+    storage::memory::MemoryStorage,
+    utils::{
+        errors::SessionManagerError,
+        validation::{validate_room_name, validate_user_identity},
+    },
+};
+use std::sync::Arc;
+
+mod common;
+
+fn request(user_identity: &str, room_name: Option<&str>) -> CreateSessionRequest {
+    CreateSessionRequest {
+        user_identity: user_identity.to_string(),
+        user_name: None,
+        room_name: room_name.map(str::to_string),
+        metadata: None,
+        required_services: Some(Vec::new()),
+        required_service_types: None,
+        reuse_existing: false,
+        observe: true,
+        owner: None,
+        max_lifetime_secs: None,
+        min_ready_services: None,
+    }
+}
+
+fn validate_identity(user_identity: &str) -> session_manager::Result<()> {
+    validate_user_identity(user_identity, &IdentityScheme::default())
+}
+
+fn assert_invalid(result: session_manager::Result<()>) {
+    assert!(
+        matches!(result, Err(SessionManagerError::InvalidRequest(_))),
+        "expected InvalidRequest, got {:?}",
+        result
+    );
+}
+
+#[test]
+fn test_user_identity_validation() {
+    assert!(validate_identity("user.name-01_x@example").is_ok());
+
+    assert_invalid(validate_identity(""));
+    assert_invalid(validate_identity(" "));
+    assert_invalid(validate_identity("user name"));
+    assert_invalid(validate_identity("user\u{0007}"));
+    assert_invalid(validate_identity("用户"));
+    assert_invalid(validate_identity(&"u".repeat(129)));
+    assert!(validate_identity(&"u".repeat(128)).is_ok());
+}
+
+#[test]
+fn test_reserved_identity_prefixes_are_rejected() {
+    assert_invalid(validate_identity("client-1234"));
+    assert_invalid(validate_identity("session-manager-1234"));
+    assert!(validate_identity("clientele").is_ok());
+}
+
+#[test]
+fn test_configured_identity_prefixes_are_reserved() {
+    let identities = IdentityScheme {
+        manager_prefix: "sm-eu-".to_string(),
+        client_prefix: "user-eu-".to_string(),
+    };
+
+    assert_invalid(validate_user_identity("sm-eu-1234", &identities));
+    assert_invalid(validate_user_identity("user-eu-1234", &identities));
+    // The default prefixes are ordinary identities for this deployment
+    assert!(validate_user_identity("client-1234", &identities).is_ok());
+}
+
+#[test]
+fn test_room_name_validation() {
+    assert!(validate_room_name("meeting-room_001.v2").is_ok());
+
+    assert_invalid(validate_room_name(""));
+    assert_invalid(validate_room_name("room\n1"));
+    assert_invalid(validate_room_name("room/1"));
+    assert_invalid(validate_room_name("room@1"));
+    assert_invalid(validate_room_name(&"r".repeat(129)));
+}
+
+#[tokio::test]
+async fn test_create_session_rejects_invalid_input() {
+    let storage = Arc::new(MemoryStorage::new());
+    let service = common::session_service(storage);
+
+    for invalid in [
+        request("", None),
+        request("   ", None),
+        request("client-spoof", None),
+        request("valid-user", Some("bad room")),
+    ] {
+        let result = service.create_session(invalid).await;
+        assert!(matches!(
+            result,
+            Err(SessionManagerError::InvalidRequest(_))
+        ));
+    }
+
+    // Nothing was stored for rejected requests
+    let page = service.list_sessions(None, None, 10, None).await.unwrap();
+    assert!(page.sessions.is_empty());
+}
@@ -0,0 +1,237 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::microservice::LeaveReason;
+
+/// Lifecycle status of a session
+///
+/// The serialized names are pinned explicitly because they are persisted and exposed
+/// through the API; renaming a variant must not change them. Statuses written by a newer
+/// version that this one does not know deserialize as `Terminated`, so stored sessions
+/// stay readable and are treated as finished.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SessionStatus {
+    /// The room is being created
+    #[serde(rename = "Creating")]
+    Creating,
+    /// Waiting for the required services to join
+    #[serde(rename = "WaitingForServices")]
+    WaitingForServices,
+    /// Every service is ready; the client token can be handed out
+    #[serde(rename = "Ready")]
+    Ready,
+    /// The client is connected
+    #[serde(rename = "Active")]
+    Active,
+    /// The room is being torn down
+    #[serde(rename = "Terminating")]
+    Terminating,
+    /// The session is over; also any status this version does not know
+    #[serde(rename = "Terminated", other)]
+    Terminated,
+}
+
+impl SessionStatus {
+    /// Whether a session in this status may move to `next`
+    ///
+    /// Sessions only move forward through `Creating -> WaitingForServices -> Ready -> Active
+    /// -> Terminating -> Terminated`. Steps may be skipped, e.g. a session without services
+    /// is ready right away and any session can be terminated, but never undone, so
+    /// `Terminated` is final. Staying in the same status is always allowed.
+    pub fn can_transition_to(&self, next: &SessionStatus) -> bool {
+        self == next || next.rank() > self.rank()
+    }
+
+    fn rank(&self) -> u8 {
+        match self {
+            SessionStatus::Creating => 0,
+            SessionStatus::WaitingForServices => 1,
+            SessionStatus::Ready => 2,
+            SessionStatus::Active => 3,
+            SessionStatus::Terminating => 4,
+            SessionStatus::Terminated => 5,
+        }
+    }
+}
+
+/// SSE event name of the session's full status, sent first on every subscription
+///
+/// Its data is the session status response rather than a [`SessionEvent`].
+pub const SESSION_SNAPSHOT_EVENT: &str = "session_snapshot";
+
+/// What the session manager does after its connection to a session's room drops
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DisconnectAction {
+    /// The drop is transient; the session manager rejoins the room
+    Reconnect,
+    /// The room is gone for good; the session is terminated
+    Terminate,
+}
+
+/// Connection quality LiveKit reports for a participant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionQuality {
+    Excellent,
+    Good,
+    Poor,
+    Lost,
+}
+
+/// One participant of a [`RoomStats`] snapshot
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParticipantStats {
+    pub identity: String,
+    /// Tracks the participant publishes
+    pub track_count: u32,
+    pub connection_quality: ConnectionQuality,
+}
+
+/// Snapshot of a session's room, taken periodically by the session manager's observer
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoomStats {
+    pub participant_count: u32,
+    /// Tracks published by all participants together
+    pub track_count: u32,
+    /// Sorted by identity
+    pub participants: Vec<ParticipantStats>,
+}
+
+impl RoomStats {
+    pub fn from_participants(mut participants: Vec<ParticipantStats>) -> Self {
+        participants.sort_by(|a, b| a.identity.cmp(&b.identity));
+        Self {
+            participant_count: participants.len() as u32,
+            track_count: participants.iter().map(|p| p.track_count).sum(),
+            participants,
+        }
+    }
+}
+
+/// Event published by the session manager on a session's event stream
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum SessionEvent {
+    SessionCreated {
+        session_id: String,
+        room_name: String,
+        access_token: String,
+        livekit_url: String,
+    },
+    MicroserviceJoined {
+        session_id: String,
+        service_id: String,
+    },
+    ClientJoined {
+        session_id: String,
+        user_identity: String,
+    },
+    ClientLeft {
+        session_id: String,
+        user_identity: String,
+    },
+    SessionReady {
+        session_id: String,
+        all_participants_joined: bool,
+    },
+    SessionStatusChanged {
+        session_id: String,
+        status: SessionStatus,
+        /// Why the session is ending; only set on `Terminating`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reason: Option<LeaveReason>,
+    },
+    MetadataChanged {
+        session_id: String,
+        metadata: HashMap<String, String>,
+    },
+    /// The connected client went silent and the session is being terminated
+    ClientTimedOut {
+        session_id: String,
+    },
+    /// A joined service went silent
+    ServiceTimedOut {
+        session_id: String,
+        service_id: String,
+    },
+    /// The room reached its participant limit; further clients cannot connect
+    RoomFull {
+        session_id: String,
+        max_participants: u32,
+    },
+    /// The session manager lost its connection to the room
+    RoomDisconnected {
+        session_id: String,
+        /// LiveKit's disconnect reason, e.g. `RoomDeleted`
+        reason: String,
+        action: DisconnectAction,
+    },
+    /// Periodic snapshot of the room's participants and tracks
+    Stats {
+        session_id: String,
+        stats: RoomStats,
+    },
+    Error {
+        session_id: String,
+        message: String,
+    },
+    /// An event type this version does not know about yet; never published
+    #[serde(other)]
+    Unknown,
+}
+
+impl SessionEvent {
+    /// Event name, used as the SSE `event:` field
+    pub fn event_name(&self) -> &'static str {
+        match self {
+            SessionEvent::SessionCreated { .. } => "session_created",
+            SessionEvent::MicroserviceJoined { .. } => "microservice_joined",
+            SessionEvent::ClientJoined { .. } => "client_joined",
+            SessionEvent::ClientLeft { .. } => "client_left",
+            SessionEvent::SessionReady { .. } => "session_ready",
+            SessionEvent::SessionStatusChanged { .. } => "session_status_changed",
+            SessionEvent::MetadataChanged { .. } => "metadata_changed",
+            SessionEvent::ClientTimedOut { .. } => "client_timed_out",
+            SessionEvent::ServiceTimedOut { .. } => "service_timed_out",
+            SessionEvent::RoomFull { .. } => "room_full",
+            SessionEvent::RoomDisconnected { .. } => "room_disconnected",
+            SessionEvent::Stats { .. } => "stats",
+            SessionEvent::Error { .. } => "error",
+            SessionEvent::Unknown => "unknown",
+        }
+    }
+
+    /// Session the event belongs to; `None` only for [`SessionEvent::Unknown`]
+    pub fn session_id(&self) -> Option<&str> {
+        match self {
+            SessionEvent::SessionCreated { session_id, .. }
+            | SessionEvent::MicroserviceJoined { session_id, .. }
+            | SessionEvent::ClientJoined { session_id, .. }
+            | SessionEvent::ClientLeft { session_id, .. }
+            | SessionEvent::SessionReady { session_id, .. }
+            | SessionEvent::SessionStatusChanged { session_id, .. }
+            | SessionEvent::MetadataChanged { session_id, .. }
+            | SessionEvent::ClientTimedOut { session_id }
+            | SessionEvent::ServiceTimedOut { session_id, .. }
+            | SessionEvent::RoomFull { session_id, .. }
+            | SessionEvent::RoomDisconnected { session_id, .. }
+            | SessionEvent::Stats { session_id, .. }
+            | SessionEvent::Error { session_id, .. } => Some(session_id),
+            SessionEvent::Unknown => None,
+        }
+    }
+}
+
+/// A [`SessionEvent`] stamped with its position in its session's event stream
+///
+/// `seq` starts at 1 for every session and grows by exactly one per published event, so a
+/// consumer seeing a gap knows it missed events. Events published outside any session's
+/// stream (e.g. for an unknown session) carry no sequence number. On the wire the event's
+/// own fields are flattened next to `seq`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SequencedEvent {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seq: Option<u64>,
+    #[serde(flatten)]
+    pub event: SessionEvent,
+}
@@ -0,0 +1,14 @@
+//! Wire types shared by the session manager and the microservice SDK
+//!
+//! Everything here crosses the network between the two: session events and statuses on
+//! the event streams, and the requests exchanged when services register, join and leave
+//! rooms. Both crates re-export these types, so changing a serde representation here
+//! changes it on both sides at once.
+
+pub mod events;
+pub mod microservice;
+pub mod payload;
+
+pub use events::*;
+pub use microservice::*;
+pub use payload::*;
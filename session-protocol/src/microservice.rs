@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// What a service offers in a session (data topics, codecs, model version, ...), reported
+/// in its join-room response
+pub type Capabilities = HashMap<String, serde_json::Value>;
+
+/// Registration metadata key naming the service's type
+///
+/// Sessions can require a type instead of a service id; any available instance
+/// registered with that type then fills the slot.
+pub const SERVICE_TYPE_METADATA_KEY: &str = "type";
+
+/// Request to register a microservice with the session manager
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegisterMicroserviceRequest {
+    pub service_id: String,
+    pub endpoint: String,
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+/// Response from registering a microservice
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegisterMicroserviceResponse {
+    pub success: bool,
+    pub service_id: String,
+    pub message: String,
+}
+
+/// Request to join a LiveKit room, sent by the session manager to `{endpoint}/join-room`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JoinRoomRequest {
+    pub room_name: String,
+    pub session_id: String,
+    pub service_identity: String,
+    pub access_token: String,
+    pub livekit_url: String,
+}
+
+/// A service's answer to a [`JoinRoomRequest`]
+///
+/// Everything but `success` is optional on the wire so services not built on the SDK
+/// only need to acknowledge the request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JoinRoomResponse {
+    pub success: bool,
+    #[serde(default)]
+    pub message: String,
+    #[serde(default)]
+    pub session_id: String,
+    #[serde(default)]
+    pub service_id: String,
+    #[serde(default)]
+    pub capabilities: Capabilities,
+}
+
+/// Why the session manager asks a service to leave a room
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LeaveReason {
+    /// The last client left the session
+    ClientLeft,
+    /// The session was abandoned or its participants timed out
+    Timeout,
+    /// The session was deleted by its owner
+    SessionDeleted,
+    /// An administrator terminated the session, e.g. to drain the server
+    AdminTerminate,
+    /// The session outlived its maximum lifetime
+    Expired,
+    /// A reason added by a newer session manager; never sent
+    #[serde(other)]
+    Unknown,
+}
+
+/// Request to leave a room, sent by the session manager to `{endpoint}/leave-room`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeaveRoomRequest {
+    pub room_name: String,
+    pub session_id: String,
+    pub service_identity: String,
+    pub reason: LeaveReason,
+}
+
+/// Request to notify the session manager that a service is ready
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceReadyRequest {
+    pub service_id: String,
+}
+
+/// Response from notifying service ready
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceReadyResponse {
+    pub success: bool,
+    pub message: String,
+    pub all_services_ready: bool,
+}
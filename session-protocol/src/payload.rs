@@ -0,0 +1,115 @@
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::fmt;
+
+/// Registration metadata key naming the [`PayloadFormat`] a service wants its
+/// `/join-room` and `/leave-room` notifications in
+///
+/// Services that do not set it, or set a format this version does not know, get JSON.
+pub const NOTIFICATION_FORMAT_METADATA_KEY: &str = "notification_format";
+
+pub const JSON_CONTENT_TYPE: &str = "application/json";
+pub const MSGPACK_CONTENT_TYPE: &str = "application/msgpack";
+
+/// Encoding of the request and response bodies exchanged with services
+///
+/// The same structs are sent either way; msgpack encodes them as maps keyed by field
+/// name, so optional and defaulted fields behave exactly as in JSON.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PayloadFormat {
+    #[default]
+    Json,
+    Msgpack,
+}
+
+impl PayloadFormat {
+    /// Value of the `Content-Type` header for bodies in this format
+    pub fn content_type(self) -> &'static str {
+        match self {
+            PayloadFormat::Json => JSON_CONTENT_TYPE,
+            PayloadFormat::Msgpack => MSGPACK_CONTENT_TYPE,
+        }
+    }
+
+    /// Format named by a `Content-Type` header value, ignoring parameters such as
+    /// `charset`; `None` for anything else
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        let mime = content_type.split(';').next().unwrap_or_default().trim();
+        if mime.eq_ignore_ascii_case(JSON_CONTENT_TYPE) {
+            Some(PayloadFormat::Json)
+        } else if mime.eq_ignore_ascii_case(MSGPACK_CONTENT_TYPE)
+            || mime.eq_ignore_ascii_case("application/x-msgpack")
+            || mime.eq_ignore_ascii_case("application/vnd.msgpack")
+        {
+            Some(PayloadFormat::Msgpack)
+        } else {
+            None
+        }
+    }
+
+    /// Format a service asked for in its registration metadata value, JSON if unknown
+    pub fn from_metadata(value: Option<&str>) -> Self {
+        match value {
+            Some(value) if value.eq_ignore_ascii_case("msgpack") => PayloadFormat::Msgpack,
+            _ => PayloadFormat::Json,
+        }
+    }
+
+    /// Name used as the [`NOTIFICATION_FORMAT_METADATA_KEY`] value
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PayloadFormat::Json => "json",
+            PayloadFormat::Msgpack => "msgpack",
+        }
+    }
+
+    pub fn encode<T: Serialize + ?Sized>(self, value: &T) -> Result<Vec<u8>, PayloadError> {
+        match self {
+            PayloadFormat::Json => serde_json::to_vec(value).map_err(PayloadError::from),
+            PayloadFormat::Msgpack => rmp_serde::to_vec_named(value).map_err(PayloadError::from),
+        }
+    }
+
+    pub fn decode<T: DeserializeOwned>(self, bytes: &[u8]) -> Result<T, PayloadError> {
+        match self {
+            PayloadFormat::Json => serde_json::from_slice(bytes).map_err(PayloadError::from),
+            PayloadFormat::Msgpack => rmp_serde::from_slice(bytes).map_err(PayloadError::from),
+        }
+    }
+}
+
+impl fmt::Display for PayloadFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A body could not be encoded or decoded in the expected [`PayloadFormat`]
+#[derive(Debug)]
+pub struct PayloadError(String);
+
+impl fmt::Display for PayloadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for PayloadError {}
+
+impl From<serde_json::Error> for PayloadError {
+    fn from(e: serde_json::Error) -> Self {
+        Self(e.to_string())
+    }
+}
+
+impl From<rmp_serde::encode::Error> for PayloadError {
+    fn from(e: rmp_serde::encode::Error) -> Self {
+        Self(e.to_string())
+    }
+}
+
+impl From<rmp_serde::decode::Error> for PayloadError {
+    fn from(e: rmp_serde::decode::Error) -> Self {
+        Self(e.to_string())
+    }
+}